@@ -0,0 +1,128 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Machine integer helpers shared by the rest of the crate: gcds, modular arithmetic and
+//! primality testing.
+//!
+//! The modular functions work on [`u128`] so that they cover every width, and they're `const` so
+//! that [`PrimeField`](crate::ringlike::finite_field::PrimeField) can check its modulus at compile
+//! time.
+
+use std::ops::Rem;
+
+/// The first 20 primes, which we use for trial division and as Miller–Rabin bases.
+const SMALL_PRIMES: [u128; 20] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+];
+
+/// The greatest common divisor of `a` and `b`, by Euclid's algorithm.
+pub(crate) fn gcd<T: Copy + Eq + From<u8> + Rem<Output = T>>(mut a: T, mut b: T) -> T {
+    while b != T::from(0) {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple of `a` and `b`, which mustn't both be zero.
+pub(crate) fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+/// `a` + `b` mod `m`, where `a` and `b` are already reduced, without overflowing.
+pub(crate) const fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `a` × `b` mod `m`, without overflowing.
+pub(crate) const fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let (mut a, mut b, mut result) = (a % m, b % m, 0);
+    while b > 0 {
+        if b % 2 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b /= 2;
+    }
+    result
+}
+
+/// `a`^`e` mod `m` by repeated squaring.
+pub(crate) const fn pow_mod(mut a: u128, mut e: u128, m: u128) -> u128 {
+    let mut result = 1 % m;
+    a %= m;
+    while e > 0 {
+        if e % 2 == 1 {
+            result = mul_mod(result, a, m);
+        }
+        a = mul_mod(a, a, m);
+        e /= 2;
+    }
+    result
+}
+
+/// The inverse of `a` modulo `m`, by the extended Euclidean algorithm. They must be coprime, and
+/// `m` must fit in an [`i128`].
+pub(crate) const fn inverse_mod(a: u128, m: u128) -> u128 {
+    let (mut r0, mut r1) = (m as i128, (a % m) as i128);
+    let (mut t0, mut t1) = (0_i128, 1_i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    assert!(r0 == 1, "only units have inverses");
+    t0.rem_euclid(m as i128) as u128
+}
+
+/// Whether `n` is prime, by the Miller–Rabin test with the bases in [`SMALL_PRIMES`]. This is
+/// deterministic up to 3.3 × 10²⁴, and beyond that it's a strong probable prime test.
+pub(crate) const fn is_prime(n: u128) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut i = 0;
+    while i < SMALL_PRIMES.len() {
+        if n.is_multiple_of(SMALL_PRIMES[i]) {
+            return n == SMALL_PRIMES[i];
+        }
+        i += 1;
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mut i = 0;
+    'bases: while i < SMALL_PRIMES.len() {
+        let mut x = pow_mod(SMALL_PRIMES[i], d, n);
+        i += 1;
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut r = 1;
+        while r < s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+            r += 1;
+        }
+        return false;
+    }
+    true
+}
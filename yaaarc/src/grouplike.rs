@@ -23,6 +23,11 @@ limitations under the License. */
 //! type level! For that we would need dependent types. Thus, if, say, a [`Monoid`] is not
 //! associative and is passed into a function which expects a [`Monoid`], then the function may
 //! panic.
+
+pub mod character;
+pub mod finite;
+pub mod subgroup;
+
 use crate::operators::BinaryOperator;
 
 /// A set which has a closed [`BinaryOperator`] that is defined for all inputs.
@@ -154,6 +159,23 @@ pub trait Loop<O>: Quasigroup<O> + UnitalMagma<O> {}
 /// A [`Monoid`] that is also commutative.
 pub trait CommutativeMonoid<O>: Semigroup<O> + UnitalMagma<O> + CommutativeMagma<O> {}
 
+/// A [`Semigroup`] where every element is idempotent.
+///
+/// Formally, for all *x* ∈ *X*, we have *xx* = *x*.
+///
+/// # Safety
+///
+/// Idempotency is not guaranteed at the type level! See the note at the beginning of this module
+/// for more details.
+pub trait Band<O>: Semigroup<O> {}
+
+/// A commutative [`Band`].
+///
+/// Every semilattice defines a partial order by *x* ≤ *y* iff *xy* = *x*, where *xy* is then the
+/// greatest lower bound of *x* and *y*. Two semilattices on the same set, with absorption between
+/// them, form a [`crate::latticelike::Lattice`].
+pub trait Semilattice<O>: Band<O> + CommutativeMagma<O> {}
+
 /// A [`Monoid`] with invertibility. Alternatively, a structure which is a [`Semigroup`],
 /// [`UnitalMagma`], and [`Quasigroup`].
 ///
@@ -0,0 +1,554 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Complex characters of finite groups.
+//!
+//! A *class function* on a group *G* is a function *G* → ℂ which is constant on conjugacy classes.
+//! The trace of a representation *G* → GL*ₙ*(ℂ) is a class function called its *character*, and
+//! the characters of the irreducible representations form an orthonormal basis of the class
+//! functions under the inner product
+//!
+//! ⟨*φ*, *ψ*⟩ = (1/|*G*|) Σ*_g* *φ*(*g*) *ψ*(*g*)*.
+//!
+//! Every character value is a sum of *e*-th roots of unity, where *e* is the exponent of *G*, so
+//! the whole character table lives in the cyclotomic field ℚ(ζ*ₑ*). We compute it exactly with
+//! Dixon's modular version of the Dixon–Schneider algorithm, see [`CharacterTable::new`].
+
+use crate::{
+    arithmetic::{add_mod, inverse_mod, is_prime, mul_mod, pow_mod},
+    grouplike::finite::FiniteGroup,
+    ringlike::{cyclotomic::Cyclotomic, rational::Rational, Ring},
+};
+
+/// A class function, stored as its values on the conjugacy classes of a group.
+///
+/// The values are in the same order as the classes of the [`CharacterTable`] it's used with, which
+/// is the order given by [`FiniteGroup::conjugacy_classes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClassFunction {
+    values: Vec<Cyclotomic>,
+}
+
+impl ClassFunction {
+    /// Creates a class function from its values on each conjugacy class.
+    pub fn new(values: Vec<Cyclotomic>) -> Self {
+        ClassFunction { values }
+    }
+
+    /// The values on each conjugacy class.
+    pub fn values(&self) -> &[Cyclotomic] {
+        &self.values
+    }
+
+    /// The value at the identity. For a character, this is the dimension of the representation.
+    pub fn degree(&self) -> &Cyclotomic {
+        &self.values[0]
+    }
+
+    /// The pointwise complex conjugate. For a character, this is the character of the dual
+    /// representation.
+    pub fn conjugate(&self) -> Self {
+        ClassFunction::new(self.values.iter().map(Cyclotomic::conjugate).collect())
+    }
+
+    /// The pointwise sum. For characters, this is the character of the direct sum.
+    pub fn sum(&self, rhs: &Self) -> Self {
+        ClassFunction::new(
+            self.values
+                .iter()
+                .zip(&rhs.values)
+                .map(|(a, b)| a + b)
+                .collect(),
+        )
+    }
+
+    /// The pointwise product. For characters, this is the character of the tensor product.
+    pub fn product(&self, rhs: &Self) -> Self {
+        ClassFunction::new(
+            self.values
+                .iter()
+                .zip(&rhs.values)
+                .map(|(a, b)| a * b)
+                .collect(),
+        )
+    }
+}
+
+/// The character table of a finite group.
+///
+/// The columns are the conjugacy classes in the order of [`FiniteGroup::conjugacy_classes`], so the
+/// first column is always the class of the identity. The rows are the irreducible characters,
+/// sorted by degree, with the trivial character first.
+///
+/// # Example
+///
+/// The symmetric group *S*₃ has three irreducible characters: the trivial one, the sign, and the
+/// two dimensional "standard" representation.
+///
+/// ```rust
+/// use yaaarc::{
+///     grouplike::{character::CharacterTable, finite::FiniteGroup},
+///     ringlike::cyclotomic::Cyclotomic,
+/// };
+///
+/// let table = CharacterTable::new(&FiniteGroup::symmetric(3));
+/// let rows: Vec<Vec<String>> = table
+///     .characters()
+///     .iter()
+///     .map(|chi| chi.values().iter().map(Cyclotomic::to_string).collect())
+///     .collect();
+///
+/// assert_eq!(rows, vec![vec!["1", "1", "1"], vec!["1", "-1", "1"], vec!["2", "0", "-1"]]);
+/// assert!(table.satisfies_row_orthogonality());
+/// assert!(table.satisfies_column_orthogonality());
+///
+/// // The permutation character of S₃ acting on three points is trivial + standard.
+/// let permutation = table.class_function(vec![3.into(), 1.into(), 0.into()]);
+/// assert_eq!(table.decompose(&permutation), vec![1.into(), 0.into(), 1.into()]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CharacterTable {
+    group_order: usize,
+    classes: Vec<Vec<usize>>,
+    characters: Vec<ClassFunction>,
+}
+
+impl CharacterTable {
+    /// Computes the character table of `group`.
+    ///
+    /// This uses Dixon's algorithm. Writing *Kⱼ* for the sum of the elements of the *j*-th class in
+    /// the group algebra, we have *KⱼKₖ* = Σ*ₗ cⱼₖₗKₗ* for some nonnegative integers *cⱼₖₗ*. For an
+    /// irreducible character *χ*, the numbers *ωⱼ* = |*Cⱼ*|*χ*(*gⱼ*)/*χ*(1) then satisfy *ωⱼωₖ* =
+    /// Σ*ₗ cⱼₖₗωₗ*, so *ω* is a common eigenvector of the matrices (*cⱼₖₗ*)*ₖₗ*. Splitting the
+    /// space into common eigenspaces therefore recovers every *χ*, up to a scalar.
+    ///
+    /// Doing this over ℂ would need floating point arithmetic, so instead we work over 𝔽*ₚ* for a
+    /// prime *p* ≡ 1 (mod *e*) which is larger than 2√|*G*|. Such a field contains the *e*-th roots
+    /// of unity, so each character reduces to a vector over 𝔽*ₚ*. Finally, each value *χ*(*g*) is
+    /// the sum of the eigenvalues of a representing matrix. We recover how many times each root of
+    /// unity appears from the reduced values on the powers of *g*, which gives us *χ*(*g*) exactly
+    /// in ℚ(ζ*ₑ*).
+    pub fn new(group: &FiniteGroup) -> Self {
+        let n = group.order();
+        let classes = group.conjugacy_classes();
+        let r = classes.len();
+        let mut class_of = vec![0; n];
+        for (j, class) in classes.iter().enumerate() {
+            for &x in class {
+                class_of[x] = j;
+            }
+        }
+        let exponent = group.exponent();
+        let p = dixon_prime(n as u64, exponent as u64);
+        let field = Modulo(p);
+
+        // matrices[j][k][l] = c_jkl, the number of ways to write a fixed element of class l as xy
+        // with x in class j and y in class k.
+        let mut matrices = vec![vec![vec![0; r]; r]; r];
+        for (l, class) in classes.iter().enumerate() {
+            let z = class[0];
+            for x in 0..n {
+                let y = group.mul(group.inverse(x), z);
+                matrices[class_of[x]][class_of[y]][l] += 1;
+            }
+        }
+
+        // Split F_p^r into the common eigenspaces of the class matrices. The first class is the
+        // identity, whose matrix is the identity matrix, so it can be skipped.
+        let mut spaces = vec![field.row_reduce((0..r).map(|i| unit_vector(r, i)).collect())];
+        for matrix in matrices.iter().skip(1) {
+            if spaces.iter().all(|space| space.len() == 1) {
+                break;
+            }
+            spaces = spaces
+                .into_iter()
+                .flat_map(|space| field.eigenspaces(matrix, space))
+                .collect();
+        }
+        assert!(
+            spaces.iter().all(|space| space.len() == 1),
+            "the class matrices must have one dimensional common eigenspaces"
+        );
+
+        let inverse_class: Vec<usize> = classes
+            .iter()
+            .map(|class| class_of[group.inverse(class[0])])
+            .collect();
+        let sizes: Vec<u64> = classes.iter().map(|class| class.len() as u64).collect();
+        let root_of_unity = field.pow(field.primitive_root(), (p - 1) / exponent as u64);
+        let mut characters: Vec<ClassFunction> = spaces
+            .into_iter()
+            .map(|mut space| {
+                let omega = space.pop().expect("eigenspaces are nonempty");
+                let omega = field.scale(&omega, field.inverse(omega[0]));
+                // Since the squares of the absolute values of χ sum to |G|, we know that
+                // χ(1)^2 Σ_j ω_j ω_j* / |C_j| = |G|, where j* is the class of inverses.
+                let sum = (0..r).fold(0, |acc, j| {
+                    let term = field.mul(omega[j], omega[inverse_class[j]]);
+                    field.add(acc, field.mul(term, field.inverse(sizes[j])))
+                });
+                let degree_squared = field.mul(n as u64 % p, field.inverse(sum));
+                let degree = (1..=n as u64)
+                    .take_while(|d| d * d <= n as u64)
+                    .find(|&d| field.mul(d, d) == degree_squared)
+                    .expect("the degree of a character is at most the square root of |G|");
+                let reduced: Vec<u64> = (0..r)
+                    .map(|j| field.mul(field.mul(omega[j], degree), field.inverse(sizes[j])))
+                    .collect();
+                let values = classes
+                    .iter()
+                    .map(|class| {
+                        lift_character_value(
+                            group,
+                            class[0],
+                            exponent,
+                            degree,
+                            &field,
+                            root_of_unity,
+                            |x| reduced[class_of[x]],
+                        )
+                    })
+                    .collect();
+                ClassFunction::new(values)
+            })
+            .collect();
+        characters.sort_by_key(|chi| {
+            let degree = chi.degree().to_rational().expect("degrees are integers");
+            let trivial = chi.values.iter().all(|v| *v == Cyclotomic::ONE);
+            (degree, !trivial)
+        });
+        CharacterTable {
+            group_order: n,
+            classes,
+            characters,
+        }
+    }
+
+    /// The irreducible characters.
+    pub fn characters(&self) -> &[ClassFunction] {
+        &self.characters
+    }
+
+    /// The conjugacy classes, in the order used for the columns.
+    pub fn classes(&self) -> &[Vec<usize>] {
+        &self.classes
+    }
+
+    /// The size of each conjugacy class.
+    pub fn class_sizes(&self) -> Vec<usize> {
+        self.classes.iter().map(Vec::len).collect()
+    }
+
+    /// The degrees *χ*(1) of the irreducible characters.
+    pub fn degrees(&self) -> Vec<usize> {
+        self.characters
+            .iter()
+            .map(|chi| {
+                let degree = chi.degree().to_rational().expect("degrees are integers");
+                degree.numerator() as usize
+            })
+            .collect()
+    }
+
+    /// Creates a class function from its values on the classes of this table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't exactly one value per conjugacy class.
+    pub fn class_function(&self, values: Vec<Cyclotomic>) -> ClassFunction {
+        assert_eq!(
+            values.len(),
+            self.classes.len(),
+            "a class function needs one value per conjugacy class"
+        );
+        ClassFunction::new(values)
+    }
+
+    /// The inner product ⟨*φ*, *ψ*⟩ = (1/|*G*|) Σ*_g* *φ*(*g*) *ψ*(*g*)*.
+    pub fn inner_product(&self, phi: &ClassFunction, psi: &ClassFunction) -> Cyclotomic {
+        let sum: Cyclotomic = self
+            .classes
+            .iter()
+            .zip(phi.values.iter().zip(&psi.values))
+            .map(|(class, (a, b))| {
+                &Cyclotomic::from(Rational::from(class.len() as i128)) * &(a * &b.conjugate())
+            })
+            .sum();
+        &sum * &Cyclotomic::from(Rational::new(1, self.group_order as i128))
+    }
+
+    /// Writes a class function as a linear combination of the irreducible characters, returning
+    /// the coefficient of each one. For a character, these are the multiplicities of the
+    /// irreducible constituents.
+    pub fn decompose(&self, phi: &ClassFunction) -> Vec<Cyclotomic> {
+        self.characters
+            .iter()
+            .map(|chi| self.inner_product(phi, chi))
+            .collect()
+    }
+
+    /// Checks the first orthogonality relation, ⟨*χ*, *ψ*⟩ = *δ*_*χψ* for irreducible *χ* and
+    /// *ψ*.
+    pub fn satisfies_row_orthogonality(&self) -> bool {
+        self.characters.iter().enumerate().all(|(i, chi)| {
+            self.characters.iter().enumerate().all(|(k, psi)| {
+                let expected = if i == k {
+                    Cyclotomic::ONE
+                } else {
+                    Cyclotomic::ZERO
+                };
+                self.inner_product(chi, psi) == expected
+            })
+        })
+    }
+
+    /// Checks the second orthogonality relation, Σ*_χ χ*(*g*)*χ*(*h*)* = *δ*_*gh* |*C_G*(*g*)|,
+    /// where *g* and *h* range over class representatives.
+    pub fn satisfies_column_orthogonality(&self) -> bool {
+        let r = self.classes.len();
+        (0..r).all(|j| {
+            (0..r).all(|l| {
+                let sum: Cyclotomic = self
+                    .characters
+                    .iter()
+                    .map(|chi| &chi.values[j] * &chi.values[l].conjugate())
+                    .sum();
+                let expected = if j == l {
+                    let centraliser = self.group_order / self.classes[j].len();
+                    Cyclotomic::from(Rational::from(centraliser as i128))
+                } else {
+                    Cyclotomic::ZERO
+                };
+                sum == expected
+            })
+        })
+    }
+}
+
+/// Recovers *χ*(*g*) ∈ ℚ(ζ*ₑ*) from the reductions modulo *p* of *χ* on the powers of *g*.
+///
+/// If *g* has order *o*, the eigenvalues of a matrix representing *g* are *o*-th roots of unity,
+/// and ζ*ₒ*^*k* appears *mₖ* = (1/*o*) Σ*ₗ χ*(*gˡ*)ζ*ₒ*^(-*kl*) times. Each *mₖ* is between 0 and
+/// *χ*(1) < *p*, so it is determined by its reduction modulo *p*.
+fn lift_character_value(
+    group: &FiniteGroup,
+    g: usize,
+    exponent: usize,
+    degree: u64,
+    field: &Modulo,
+    root_of_unity: u64,
+    reduced: impl Fn(usize) -> u64,
+) -> Cyclotomic {
+    let order = group.element_order(g);
+    let step = exponent / order;
+    let root = field.pow(root_of_unity, step as u64);
+    let powers: Vec<u64> = (0..order).map(|l| reduced(group.pow(g, l))).collect();
+    let order_inverse = field.inverse(order as u64 % field.0);
+    let mut coefficients = vec![Rational::ZERO; exponent];
+    for k in 0..order {
+        let inverse_root = field.inverse(field.pow(root, k as u64));
+        let sum = powers.iter().enumerate().fold(0, |acc, (l, &value)| {
+            field.add(acc, field.mul(value, field.pow(inverse_root, l as u64)))
+        });
+        let multiplicity = field.mul(sum, order_inverse);
+        assert!(
+            multiplicity <= degree,
+            "an eigenvalue can't appear more often than the degree"
+        );
+        coefficients[k * step] = Rational::from(multiplicity as i128);
+    }
+    Cyclotomic::new(exponent, coefficients)
+}
+
+/// The smallest prime *p* ≡ 1 (mod `exponent`) with *p* > 2√`order`.
+fn dixon_prime(order: u64, exponent: u64) -> u64 {
+    let mut p = exponent + 1;
+    while p * p <= 4 * order || !is_prime(u128::from(p)) {
+        p += exponent;
+    }
+    p
+}
+
+fn unit_vector(length: usize, i: usize) -> Vec<u64> {
+    let mut v = vec![0; length];
+    v[i] = 1;
+    v
+}
+
+/// Just enough linear algebra over 𝔽*ₚ* for Dixon's algorithm. The prime is only known at run
+/// time, so unlike [`PrimeField`](crate::ringlike::finite_field::PrimeField) this carries it as a
+/// value, and the arithmetic is done on residues with the crate's modular helpers.
+struct Modulo(u64);
+
+impl Modulo {
+    fn add(&self, a: u64, b: u64) -> u64 {
+        add_mod(a.into(), b.into(), self.0.into()) as u64
+    }
+
+    fn sub(&self, a: u64, b: u64) -> u64 {
+        add_mod(a.into(), ((self.0 - b) % self.0).into(), self.0.into()) as u64
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        mul_mod(a.into(), b.into(), self.0.into()) as u64
+    }
+
+    fn pow(&self, base: u64, exponent: u64) -> u64 {
+        pow_mod(base.into(), exponent.into(), self.0.into()) as u64
+    }
+
+    fn inverse(&self, a: u64) -> u64 {
+        inverse_mod(a.into(), self.0.into()) as u64
+    }
+
+    fn scale(&self, v: &[u64], c: u64) -> Vec<u64> {
+        v.iter().map(|&x| self.mul(x, c)).collect()
+    }
+
+    /// A generator of the multiplicative group 𝔽*ₚ*ˣ.
+    fn primitive_root(&self) -> u64 {
+        let p = self.0;
+        let mut factors = Vec::new();
+        let mut m = p - 1;
+        let mut d = 2;
+        while d * d <= m {
+            if m.is_multiple_of(d) {
+                factors.push(d);
+                while m.is_multiple_of(d) {
+                    m /= d;
+                }
+            }
+            d += 1;
+        }
+        if m > 1 {
+            factors.push(m);
+        }
+        (2..p)
+            .find(|&g| factors.iter().all(|&q| self.pow(g, (p - 1) / q) != 1))
+            .unwrap_or(1)
+    }
+
+    /// Puts the rows into reduced row echelon form, dropping zero rows.
+    fn row_reduce(&self, mut rows: Vec<Vec<u64>>) -> Vec<Vec<u64>> {
+        let columns = rows.first().map_or(0, Vec::len);
+        let mut rank = 0;
+        for column in 0..columns {
+            let Some(pivot) = (rank..rows.len()).find(|&i| rows[i][column] != 0) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            let scale = self.inverse(rows[rank][column]);
+            rows[rank] = self.scale(&rows[rank], scale);
+            let pivot_row = rows[rank].clone();
+            for (i, row) in rows.iter_mut().enumerate() {
+                if i != rank && row[column] != 0 {
+                    let factor = row[column];
+                    for (x, &y) in row.iter_mut().zip(&pivot_row) {
+                        *x = self.sub(*x, self.mul(factor, y));
+                    }
+                }
+            }
+            rank += 1;
+        }
+        rows.truncate(rank);
+        rows
+    }
+
+    /// A basis of the solutions *x* of *Ax* = 0.
+    fn nullspace(&self, matrix: Vec<Vec<u64>>) -> Vec<Vec<u64>> {
+        let columns = matrix.first().map_or(0, Vec::len);
+        let reduced = self.row_reduce(matrix);
+        let pivots: Vec<usize> = reduced
+            .iter()
+            .map(|row| row.iter().position(|&x| x != 0).expect("rows are nonzero"))
+            .collect();
+        (0..columns)
+            .filter(|c| !pivots.contains(c))
+            .map(|free| {
+                let mut v = unit_vector(columns, free);
+                for (row, &pivot) in reduced.iter().zip(&pivots) {
+                    v[pivot] = self.sub(0, row[free]);
+                }
+                v
+            })
+            .collect()
+    }
+
+    /// Splits a subspace invariant under `matrix`, given by a row reduced basis, into the
+    /// eigenspaces of `matrix`. Vectors are treated as columns, so `matrix` acts on the left.
+    fn eigenspaces(&self, matrix: &[Vec<u64>], space: Vec<Vec<u64>>) -> Vec<Vec<Vec<u64>>> {
+        let d = space.len();
+        if d == 1 {
+            return vec![space];
+        }
+        let pivots: Vec<usize> = space
+            .iter()
+            .map(|row| row.iter().position(|&x| x != 0).expect("rows are nonzero"))
+            .collect();
+        // The restriction of the matrix to the space. Column i holds the coordinates of the image
+        // of the i-th basis vector, which can be read off at the pivot positions.
+        let images: Vec<Vec<u64>> = space
+            .iter()
+            .map(|v| {
+                matrix
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .zip(v)
+                            .fold(0, |acc, (&a, &b)| self.add(acc, self.mul(a % self.0, b)))
+                    })
+                    .collect()
+            })
+            .collect();
+        let restriction: Vec<Vec<u64>> = pivots
+            .iter()
+            .map(|&pivot| images.iter().map(|image| image[pivot]).collect())
+            .collect();
+        let mut result = Vec::new();
+        let mut found = 0;
+        for eigenvalue in 0..self.0 {
+            if found == d {
+                break;
+            }
+            let shifted = restriction
+                .iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    let mut row = row.clone();
+                    row[i] = self.sub(row[i], eigenvalue);
+                    row
+                })
+                .collect();
+            let kernel = self.nullspace(shifted);
+            if kernel.is_empty() {
+                continue;
+            }
+            found += kernel.len();
+            let vectors = kernel
+                .iter()
+                .map(|coordinates| {
+                    let mut v = vec![0; matrix.len()];
+                    for (c, basis) in coordinates.iter().zip(&space) {
+                        for (x, &b) in v.iter_mut().zip(basis) {
+                            *x = self.add(*x, self.mul(*c, b));
+                        }
+                    }
+                    v
+                })
+                .collect();
+            result.push(self.row_reduce(vectors));
+        }
+        result
+    }
+}
@@ -0,0 +1,467 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Concrete finite groups.
+//!
+//! The traits in [`crate::grouplike`] describe a group at the type level, which is great when
+//! every value of a type is an element of the same group. But lots of interesting groups are only
+//! known at runtime: the group generated by a handful of permutations, or one given by its
+//! multiplication table. For those, we provide [`FiniteGroup`], whose elements are simply the
+//! indices 0, 1, …, *n* - 1 and whose operation is looked up in a Cayley table.
+
+use std::{collections::HashMap, error::Error, fmt, hash::Hash};
+
+use crate::{
+    arithmetic::lcm,
+    grouplike::{Group, Magma, Monoid, Quasigroup, Semigroup, UnitalMagma},
+    operators::{BinaryOperator, Times},
+};
+
+/// The reasons a [`FiniteGroup`] or [`Permutation`] can fail to be constructed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FiniteGroupError {
+    /// The Cayley table has no rows, so there's no identity.
+    Empty,
+    /// The Cayley table is not square.
+    NotSquare,
+    /// An entry of the Cayley table does not name an element.
+    OutOfRange { row: usize, column: usize },
+    /// The operation isn't associative: (*xy*)*z* ≠ *x*(*yz*) for these elements.
+    NotAssociative { x: usize, y: usize, z: usize },
+    /// No element acts as an identity.
+    NoIdentity,
+    /// This element has no inverse.
+    NoInverse(usize),
+    /// The given images don't describe a bijection.
+    NotAPermutation,
+}
+
+impl fmt::Display for FiniteGroupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FiniteGroupError::Empty => write!(f, "a group must have at least one element"),
+            FiniteGroupError::NotSquare => write!(f, "the Cayley table is not square"),
+            FiniteGroupError::OutOfRange { row, column } => {
+                write!(
+                    f,
+                    "entry ({row}, {column}) of the Cayley table is out of range"
+                )
+            }
+            FiniteGroupError::NotAssociative { x, y, z } => {
+                write!(f, "the operation is not associative on ({x}, {y}, {z})")
+            }
+            FiniteGroupError::NoIdentity => write!(f, "the operation has no identity"),
+            FiniteGroupError::NoInverse(x) => write!(f, "element {x} has no inverse"),
+            FiniteGroupError::NotAPermutation => write!(f, "the images are not a bijection"),
+        }
+    }
+}
+
+impl Error for FiniteGroupError {}
+
+/// A permutation of the natural numbers which moves only finitely many points.
+///
+/// Permutations are composed like functions, from right to left: *στ* means "first apply *τ*, then
+/// *σ*". Every point not mentioned is fixed, so permutations of different degrees can be composed
+/// freely. This makes the permutations a [`Group`] under [`Times`], with the identity being the
+/// permutation which fixes everything.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::{
+///     grouplike::{finite::Permutation, Quasigroup},
+///     operators::{BinaryOperator, Times},
+/// };
+///
+/// let sigma = Permutation::from_cycles(&[&[0, 1, 2]]).unwrap();
+/// let tau = Permutation::from_cycles(&[&[0, 1]]).unwrap();
+///
+/// // First swap 0 and 1, then cycle 0 → 1 → 2 → 0.
+/// let product = BinaryOperator::<Times>::op(&sigma, tau);
+/// assert_eq!(product, Permutation::from_cycles(&[&[0, 2]]).unwrap());
+/// assert_eq!(sigma.order(), 3);
+/// assert_eq!(Quasigroup::<Times>::inverse(&sigma).apply(0), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Permutation {
+    /// `images[i]` is the image of `i`. Trailing fixed points are trimmed so that equal
+    /// permutations have equal representations.
+    images: Vec<usize>,
+}
+
+impl Permutation {
+    /// Creates the permutation sending `i` to `images[i]`, fixing every point past the end.
+    pub fn from_images(images: Vec<usize>) -> Result<Self, FiniteGroupError> {
+        let mut seen = vec![false; images.len()];
+        for &image in &images {
+            match seen.get_mut(image) {
+                Some(seen) if !*seen => *seen = true,
+                _ => return Err(FiniteGroupError::NotAPermutation),
+            }
+        }
+        Ok(Permutation::trimmed(images))
+    }
+
+    /// Creates a permutation from its disjoint cycles, so `&[&[0, 1, 2]]` sends 0 to 1, 1 to 2 and
+    /// 2 to 0.
+    pub fn from_cycles(cycles: &[&[usize]]) -> Result<Self, FiniteGroupError> {
+        let degree = cycles.iter().flat_map(|c| c.iter()).map(|&i| i + 1).max();
+        let mut images: Vec<Option<usize>> = vec![None; degree.unwrap_or(0)];
+        for cycle in cycles {
+            for (k, &i) in cycle.iter().enumerate() {
+                let image = cycle[(k + 1) % cycle.len()];
+                if images[i].replace(image).is_some() {
+                    return Err(FiniteGroupError::NotAPermutation);
+                }
+            }
+        }
+        let images = images
+            .into_iter()
+            .enumerate()
+            .map(|(i, image)| image.unwrap_or(i))
+            .collect();
+        Permutation::from_images(images)
+    }
+
+    fn trimmed(mut images: Vec<usize>) -> Self {
+        while images
+            .last()
+            .is_some_and(|&image| image == images.len() - 1)
+        {
+            images.pop();
+        }
+        Permutation { images }
+    }
+
+    /// The image of the point `i`.
+    pub fn apply(&self, i: usize) -> usize {
+        self.images.get(i).copied().unwrap_or(i)
+    }
+
+    /// One more than the largest point moved, or 0 for the identity.
+    pub fn degree(&self) -> usize {
+        self.images.len()
+    }
+
+    /// The disjoint cycles of length at least 2, each starting with its smallest point.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.degree()];
+        let mut cycles = Vec::new();
+        for start in 0..self.degree() {
+            if seen[start] || self.apply(start) == start {
+                continue;
+            }
+            let mut cycle = vec![start];
+            seen[start] = true;
+            let mut i = self.apply(start);
+            while i != start {
+                seen[i] = true;
+                cycle.push(i);
+                i = self.apply(i);
+            }
+            cycles.push(cycle);
+        }
+        cycles
+    }
+
+    /// The order of the permutation, which is the lowest common multiple of its cycle lengths.
+    pub fn order(&self) -> usize {
+        self.cycles().iter().fold(1, |acc, c| lcm(acc, c.len()))
+    }
+}
+
+impl fmt::Display for Permutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cycles = self.cycles();
+        if cycles.is_empty() {
+            return write!(f, "()");
+        }
+        for cycle in cycles {
+            let points: Vec<String> = cycle.iter().map(usize::to_string).collect();
+            write!(f, "({})", points.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl BinaryOperator<Times> for Permutation {
+    fn op(&self, rhs: Self) -> Self {
+        let degree = self.degree().max(rhs.degree());
+        Permutation::trimmed((0..degree).map(|i| self.apply(rhs.apply(i))).collect())
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = BinaryOperator::<Times>::op(self, rhs);
+    }
+}
+
+impl Magma<Times> for Permutation {}
+impl Semigroup<Times> for Permutation {}
+impl UnitalMagma<Times> for Permutation {
+    const IDENTITY: Self = Permutation { images: Vec::new() };
+}
+impl Quasigroup<Times> for Permutation {
+    fn inverse(&self) -> Self {
+        let mut images = vec![0; self.degree()];
+        for (i, &image) in self.images.iter().enumerate() {
+            images[image] = i;
+        }
+        Permutation { images }
+    }
+}
+impl Monoid<Times> for Permutation {}
+impl Group<Times> for Permutation {}
+
+/// A finite group given by its Cayley table.
+///
+/// The elements are the indices 0, …, *n* - 1, and the product of *x* and *y* is
+/// `table[x][y]`. Every constructor checks the group axioms, so all the methods here can assume
+/// that they hold.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::grouplike::finite::{FiniteGroup, Permutation};
+///
+/// let s3 = FiniteGroup::from_generators(&[
+///     Permutation::from_cycles(&[&[0, 1, 2]]).unwrap(),
+///     Permutation::from_cycles(&[&[0, 1]]).unwrap(),
+/// ]);
+///
+/// assert_eq!(s3.order(), 6);
+/// assert!(!s3.is_abelian());
+/// // The identity, the transpositions and the 3-cycles.
+/// let sizes: Vec<usize> = s3.conjugacy_classes().iter().map(Vec::len).collect();
+/// assert_eq!(sizes, vec![1, 3, 2]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FiniteGroup {
+    table: Vec<Vec<usize>>,
+    identity: usize,
+    inverses: Vec<usize>,
+}
+
+impl FiniteGroup {
+    /// Creates a group from its Cayley table, checking the group axioms.
+    pub fn from_cayley_table(table: Vec<Vec<usize>>) -> Result<Self, FiniteGroupError> {
+        let n = table.len();
+        if n == 0 {
+            return Err(FiniteGroupError::Empty);
+        }
+        if table.iter().any(|row| row.len() != n) {
+            return Err(FiniteGroupError::NotSquare);
+        }
+        for (row, entries) in table.iter().enumerate() {
+            if let Some(column) = entries.iter().position(|&x| x >= n) {
+                return Err(FiniteGroupError::OutOfRange { row, column });
+            }
+        }
+        for x in 0..n {
+            for y in 0..n {
+                for z in 0..n {
+                    if table[table[x][y]][z] != table[x][table[y][z]] {
+                        return Err(FiniteGroupError::NotAssociative { x, y, z });
+                    }
+                }
+            }
+        }
+        let identity = (0..n)
+            .find(|&e| (0..n).all(|x| table[e][x] == x && table[x][e] == x))
+            .ok_or(FiniteGroupError::NoIdentity)?;
+        let inverses = (0..n)
+            .map(|x| {
+                (0..n)
+                    .find(|&y| table[x][y] == identity && table[y][x] == identity)
+                    .ok_or(FiniteGroupError::NoInverse(x))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(FiniteGroup {
+            table,
+            identity,
+            inverses,
+        })
+    }
+
+    /// Creates the group generated by `generators` inside some ambient [`Group`], such as the
+    /// group of [`Permutation`]s.
+    ///
+    /// The identity becomes element 0, and the other elements are numbered in the order they are
+    /// found by multiplying known elements by generators. The ambient group must be such that the
+    /// generated subgroup is finite, otherwise this never returns!
+    pub fn from_generators<O, T>(generators: &[T]) -> Self
+    where
+        T: Group<O> + Clone + Eq + Hash,
+    {
+        let mut elements = vec![T::IDENTITY];
+        let mut index: HashMap<T, usize> = HashMap::from([(T::IDENTITY, 0)]);
+        let mut next = 0;
+        while next < elements.len() {
+            for generator in generators {
+                let product = elements[next].op(generator.clone());
+                if !index.contains_key(&product) {
+                    index.insert(product.clone(), elements.len());
+                    elements.push(product);
+                }
+            }
+            next += 1;
+        }
+        let table = elements
+            .iter()
+            .map(|x| elements.iter().map(|y| index[&x.op(y.clone())]).collect())
+            .collect();
+        let inverses = elements.iter().map(|x| index[&x.inverse()]).collect();
+        FiniteGroup {
+            table,
+            identity: 0,
+            inverses,
+        }
+    }
+
+    /// The cyclic group ℤ/*n*ℤ, written multiplicatively, where element *k* stands for *gᵏ*.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn cyclic(n: usize) -> Self {
+        assert!(n > 0, "a group must have at least one element");
+        FiniteGroup {
+            table: (0..n)
+                .map(|i| (0..n).map(|j| (i + j) % n).collect())
+                .collect(),
+            identity: 0,
+            inverses: (0..n).map(|i| (n - i) % n).collect(),
+        }
+    }
+
+    /// The symmetric group on `n` points.
+    pub fn symmetric(n: usize) -> Self {
+        let mut generators = Vec::new();
+        if n >= 2 {
+            generators.push(Permutation::trimmed(vec![1, 0]));
+            generators.push(Permutation::trimmed((1..n).chain([0]).collect()));
+        }
+        FiniteGroup::from_generators(&generators)
+    }
+
+    /// The number of elements.
+    pub fn order(&self) -> usize {
+        self.table.len()
+    }
+
+    /// The identity element.
+    pub fn identity(&self) -> usize {
+        self.identity
+    }
+
+    /// The Cayley table.
+    pub fn cayley_table(&self) -> &[Vec<usize>] {
+        &self.table
+    }
+
+    /// The product *xy*.
+    pub fn mul(&self, x: usize, y: usize) -> usize {
+        self.table[x][y]
+    }
+
+    /// The inverse *x*⁻¹.
+    pub fn inverse(&self, x: usize) -> usize {
+        self.inverses[x]
+    }
+
+    /// The power *x*ᵏ.
+    pub fn pow(&self, x: usize, k: usize) -> usize {
+        (0..k).fold(self.identity, |acc, _| self.mul(acc, x))
+    }
+
+    /// The conjugate *yxy*⁻¹.
+    pub fn conjugate(&self, x: usize, y: usize) -> usize {
+        self.mul(self.mul(y, x), self.inverse(y))
+    }
+
+    /// The smallest *k* > 0 such that *x*ᵏ is the identity.
+    pub fn element_order(&self, x: usize) -> usize {
+        let mut k = 1;
+        let mut power = x;
+        while power != self.identity {
+            power = self.mul(power, x);
+            k += 1;
+        }
+        k
+    }
+
+    /// The exponent of the group, the lowest common multiple of the element orders.
+    pub fn exponent(&self) -> usize {
+        (0..self.order()).fold(1, |acc, x| lcm(acc, self.element_order(x)))
+    }
+
+    /// The subgroup generated by `generators`, as a sorted list of elements.
+    pub fn generated_subgroup(&self, generators: &[usize]) -> Vec<usize> {
+        let mut elements = vec![self.identity];
+        let mut seen = vec![false; self.order()];
+        seen[self.identity] = true;
+        let mut next = 0;
+        while next < elements.len() {
+            for &generator in generators {
+                let product = self.mul(elements[next], generator);
+                if !seen[product] {
+                    seen[product] = true;
+                    elements.push(product);
+                }
+            }
+            next += 1;
+        }
+        elements.sort_unstable();
+        elements
+    }
+
+    /// Whether the sorted list of elements `subgroup` is closed under conjugation. This assumes
+    /// it's a subgroup.
+    pub fn is_normal(&self, subgroup: &[usize]) -> bool {
+        subgroup.iter().all(|&x| {
+            (0..self.order()).all(|y| subgroup.binary_search(&self.conjugate(x, y)).is_ok())
+        })
+    }
+
+    /// Whether *xy* = *yx* for all *x* and *y*.
+    pub fn is_abelian(&self) -> bool {
+        (0..self.order()).all(|x| (0..x).all(|y| self.mul(x, y) == self.mul(y, x)))
+    }
+
+    /// The conjugacy classes of the group.
+    ///
+    /// The class of the identity comes first, and the remaining classes are sorted by the order of
+    /// their elements, then by size, then by their smallest element. Each class is sorted.
+    pub fn conjugacy_classes(&self) -> Vec<Vec<usize>> {
+        let n = self.order();
+        let mut seen = vec![false; n];
+        let mut classes = Vec::new();
+        for x in 0..n {
+            if seen[x] {
+                continue;
+            }
+            let mut class: Vec<usize> = (0..n).map(|y| self.conjugate(x, y)).collect();
+            class.sort_unstable();
+            class.dedup();
+            for &y in &class {
+                seen[y] = true;
+            }
+            classes.push(class);
+        }
+        classes.sort_by_key(|class| {
+            let x = class[0];
+            (x != self.identity, self.element_order(x), class.len(), x)
+        });
+        classes
+    }
+}
@@ -0,0 +1,207 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! The lattice of subgroups of a finite group.
+//!
+//! The subgroups of a group, ordered by inclusion, form a lattice: the meet of two subgroups is
+//! their intersection, and their join is the subgroup they generate. The normal subgroups form a
+//! sublattice, which is always modular (Dedekind). So the subgroup lattice of an abelian group is
+//! modular, but non-abelian groups such as *S*₄ can have non-modular subgroup lattices.
+//!
+//! This enumerates every subgroup, so it's only practical for small groups.
+
+use std::collections::HashSet;
+
+use crate::{
+    grouplike::finite::FiniteGroup,
+    latticelike::poset::{FiniteLattice, Poset},
+};
+
+/// Every subgroup of a [`FiniteGroup`], with its lattice structure and conjugacy classes.
+///
+/// Subgroups are numbered in order of size, and then lexicographically by their elements. The
+/// lattice has the same numbering, and each subgroup is labelled by its elements.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::grouplike::finite::{FiniteGroup, Permutation};
+///
+/// // The Klein four-group has three subgroups of order 2, so its lattice is the diamond M₃.
+/// let klein = FiniteGroup::from_generators(&[
+///     Permutation::from_cycles(&[&[0, 1]]).unwrap(),
+///     Permutation::from_cycles(&[&[2, 3]]).unwrap(),
+/// ]);
+/// let subgroups = klein.subgroup_lattice();
+/// assert_eq!(subgroups.subgroups().len(), 5);
+/// assert!(subgroups.lattice().is_modular());
+/// assert!(!subgroups.lattice().is_distributive());
+///
+/// // S₄ has 30 subgroups in 11 conjugacy classes, and its subgroup lattice isn't modular.
+/// let s4 = FiniteGroup::symmetric(4).subgroup_lattice();
+/// assert_eq!(s4.subgroups().len(), 30);
+/// assert_eq!(s4.conjugacy_classes().len(), 11);
+/// assert!(s4.lattice().find_n5().is_some());
+/// // But its normal subgroups 1 < V₄ < A₄ < S₄ form a chain.
+/// assert_eq!(s4.normal_subgroups().len(), 4);
+/// assert!(s4.normal_lattice().is_distributive());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SubgroupLattice {
+    subgroups: Vec<Vec<usize>>,
+    lattice: FiniteLattice,
+    classes: Vec<Vec<usize>>,
+    normal: Vec<usize>,
+}
+
+impl SubgroupLattice {
+    /// Computes every subgroup of `group`.
+    ///
+    /// Every subgroup is generated by its cyclic subgroups, so we start with the cyclic subgroups
+    /// and keep joining a cyclic subgroup to a subgroup we've already found, until nothing new
+    /// turns up.
+    pub fn new(group: &FiniteGroup) -> Self {
+        let mut cyclic: Vec<(usize, Vec<usize>)> = Vec::new();
+        for x in 0..group.order() {
+            let subgroup = group.generated_subgroup(&[x]);
+            if !cyclic.iter().any(|(_, c)| *c == subgroup) {
+                cyclic.push((x, subgroup));
+            }
+        }
+        let mut found: HashSet<Vec<usize>> = cyclic.iter().map(|(_, c)| c.clone()).collect();
+        let mut queue: Vec<Vec<usize>> = found.iter().cloned().collect();
+        while let Some(subgroup) = queue.pop() {
+            for (generator, _) in &cyclic {
+                if subgroup.binary_search(generator).is_ok() {
+                    continue;
+                }
+                let mut generators = subgroup.clone();
+                generators.push(*generator);
+                let join = group.generated_subgroup(&generators);
+                if found.insert(join.clone()) {
+                    queue.push(join);
+                }
+            }
+        }
+        let mut subgroups: Vec<Vec<usize>> = found.into_iter().collect();
+        subgroups.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+
+        let index_of = |subgroup: &Vec<usize>| {
+            subgroups
+                .binary_search_by(|h| h.len().cmp(&subgroup.len()).then_with(|| h.cmp(subgroup)))
+                .expect("conjugates of subgroups are subgroups")
+        };
+        let mut class_of = vec![None; subgroups.len()];
+        let mut classes = Vec::new();
+        for i in 0..subgroups.len() {
+            if class_of[i].is_some() {
+                continue;
+            }
+            let mut class: Vec<usize> = (0..group.order())
+                .map(|y| {
+                    let mut conjugate: Vec<usize> = subgroups[i]
+                        .iter()
+                        .map(|&x| group.conjugate(x, y))
+                        .collect();
+                    conjugate.sort_unstable();
+                    index_of(&conjugate)
+                })
+                .collect();
+            class.sort_unstable();
+            class.dedup();
+            for &j in &class {
+                class_of[j] = Some(classes.len());
+            }
+            classes.push(class);
+        }
+        let normal = classes
+            .iter()
+            .filter(|class| class.len() == 1)
+            .map(|class| class[0])
+            .collect();
+        let lattice = SubgroupLattice::inclusion_lattice(
+            &subgroups,
+            &(0..subgroups.len()).collect::<Vec<_>>(),
+        );
+        SubgroupLattice {
+            subgroups,
+            lattice,
+            classes,
+            normal,
+        }
+    }
+
+    /// The lattice of the given subgroups under inclusion, labelled by their elements.
+    fn inclusion_lattice(subgroups: &[Vec<usize>], indices: &[usize]) -> FiniteLattice {
+        let contains = |h: &[usize], k: &[usize]| k.iter().all(|x| h.binary_search(x).is_ok());
+        let labels = indices
+            .iter()
+            .map(|&i| {
+                let elements: Vec<String> = subgroups[i].iter().map(ToString::to_string).collect();
+                format!("{{{}}}", elements.join(", "))
+            })
+            .collect();
+        Poset::from_fn(indices.len(), |i, j| {
+            contains(&subgroups[indices[j]], &subgroups[indices[i]])
+        })
+        .and_then(|poset| poset.with_labels(labels))
+        .expect("inclusion is a partial order")
+        .to_lattice()
+        .expect("the subgroups form a lattice")
+    }
+
+    /// Every subgroup, as a sorted list of elements.
+    pub fn subgroups(&self) -> &[Vec<usize>] {
+        &self.subgroups
+    }
+
+    /// The subgroups ordered by inclusion.
+    pub fn lattice(&self) -> &FiniteLattice {
+        &self.lattice
+    }
+
+    /// The index of a subgroup, given as a sorted list of elements.
+    pub fn index_of(&self, subgroup: &[usize]) -> Option<usize> {
+        self.subgroups.iter().position(|h| h == subgroup)
+    }
+
+    /// The conjugacy classes of subgroups, as lists of indices. They're ordered by the index of
+    /// their first subgroup, so by the order of the subgroups.
+    pub fn conjugacy_classes(&self) -> &[Vec<usize>] {
+        &self.classes
+    }
+
+    /// The first subgroup of each conjugacy class, so the subgroups up to conjugacy.
+    pub fn class_representatives(&self) -> Vec<usize> {
+        self.classes.iter().map(|class| class[0]).collect()
+    }
+
+    /// The indices of the normal subgroups, the subgroups which are their own conjugacy class.
+    pub fn normal_subgroups(&self) -> &[usize] {
+        &self.normal
+    }
+
+    /// The normal subgroups ordered by inclusion, which is a sublattice of
+    /// [`SubgroupLattice::lattice`]. Its indices follow [`SubgroupLattice::normal_subgroups`].
+    pub fn normal_lattice(&self) -> FiniteLattice {
+        SubgroupLattice::inclusion_lattice(&self.subgroups, &self.normal)
+    }
+}
+
+impl FiniteGroup {
+    /// Every subgroup of the group. See [`SubgroupLattice`].
+    pub fn subgroup_lattice(&self) -> SubgroupLattice {
+        SubgroupLattice::new(self)
+    }
+}
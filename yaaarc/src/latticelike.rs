@@ -14,4 +14,223 @@ limitations under the License. */
 
 //! Latticelike structures have two binary operators with absorption.
 //!
-//! I need to fill this in :)
+//! The absorption laws set latticelike structures apart from [`crate::ringlike`]s, which have
+//! distributivity rather than absorption. We call the two operators *join* ([`Join`], written ∨)
+//! and *meet* ([`Meet`], written ∧). Each of them on its own forms a
+//! [`crate::grouplike::Semilattice`].
+//!
+//! Every lattice is also a partially ordered set: we say *x* ≤ *y* iff *x* ∧ *y* = *x*, or
+//! equivalently, iff *x* ∨ *y* = *y*. Then *x* ∨ *y* is the least upper bound of *x* and *y*, and
+//! *x* ∧ *y* is their greatest lower bound.
+//!
+//! # Safety
+//!
+//! Just like in [`crate::grouplike`], the laws (absorption, distributivity, modularity, and so on)
+//! are not guaranteed at the type level! Functions which expect, say, a [`DistributiveLattice`]
+//! may panic or return nonsense if the laws don't hold.
+
+pub mod bdd;
+pub mod boolean;
+pub mod expr;
+pub mod galois;
+pub mod heyting;
+pub mod poset;
+
+use crate::{
+    grouplike::{Semilattice, UnitalMagma},
+    operators::{BinaryOperator, Join, Meet},
+};
+
+/// A set with a [`Join`] operator which forms a [`Semilattice`].
+///
+/// Formally, join is associative, commutative and idempotent, so *x* ∨ *x* = *x*.
+pub trait JoinSemilattice: Semilattice<Join> + Sized {
+    /// A convenience function to call the join operator (i.e. [`BinaryOperator<Join>::op`]).
+    fn join(&self, rhs: Self) -> Self {
+        <Self as BinaryOperator<Join>>::op(self, rhs)
+    }
+}
+
+/// A set with a [`Meet`] operator which forms a [`Semilattice`].
+///
+/// Formally, meet is associative, commutative and idempotent, so *x* ∧ *x* = *x*.
+pub trait MeetSemilattice: Semilattice<Meet> + Sized {
+    /// A convenience function to call the meet operator (i.e. [`BinaryOperator<Meet>::op`]).
+    fn meet(&self, rhs: Self) -> Self {
+        <Self as BinaryOperator<Meet>>::op(self, rhs)
+    }
+}
+
+/// A lattice.
+///
+/// That is, a set *L* which is both a [`JoinSemilattice`] and a [`MeetSemilattice`], where the two
+/// operators are linked by the absorption laws, for all *x*, *y* ∈ *L*:
+///
+/// * *x* ∨ (*x* ∧ *y*) = *x*.
+/// * *x* ∧ (*x* ∨ *y*) = *x*.
+///
+/// # Safety
+///
+/// Absorption is not guaranteed at the type level! See the note at the beginning of this module
+/// for more details.
+pub trait Lattice: JoinSemilattice + MeetSemilattice {
+    /// The partial order of the lattice, *x* ≤ *y* iff *x* ∧ *y* = *x*.
+    fn leq(&self, rhs: &Self) -> bool
+    where
+        Self: Clone + PartialEq,
+    {
+        self.meet(rhs.clone()) == *self
+    }
+}
+
+/// A [`Lattice`] with a greatest element ⊤ (top) and a least element ⊥ (bottom).
+///
+/// Bottom is the identity of join, and top is the identity of meet, so for all *x* ∈ *L*, *x* ∨ ⊥
+/// = *x* and *x* ∧ ⊤ = *x*.
+pub trait BoundedLattice: Lattice + UnitalMagma<Join> + UnitalMagma<Meet> {
+    const BOTTOM: Self;
+    const TOP: Self;
+}
+
+/// A [`Lattice`] satisfying the modular law.
+///
+/// Formally, for all *x*, *y*, *z* ∈ *L*, if *x* ≤ *z*, then *x* ∨ (*y* ∧ *z*) = (*x* ∨ *y*) ∧ *z*.
+/// The normal subgroups of a group form a modular lattice, as do the submodules of a module.
+///
+/// # Safety
+///
+/// Modularity is not guaranteed at the type level! See the note at the beginning of this module
+/// for more details.
+pub trait ModularLattice: Lattice {}
+
+/// A [`Lattice`] where join and meet distribute over each other.
+///
+/// Formally, for all *x*, *y*, *z* ∈ *L*, *x* ∧ (*y* ∨ *z*) = (*x* ∧ *y*) ∨ (*x* ∧ *z*). This
+/// implies the dual law, *x* ∨ (*y* ∧ *z*) = (*x* ∨ *y*) ∧ (*x* ∨ *z*), and the modular law.
+///
+/// # Safety
+///
+/// Distributivity is not guaranteed at the type level! See the note at the beginning of this
+/// module for more details.
+pub trait DistributiveLattice: ModularLattice {}
+
+/// A [`BoundedLattice`] where every element has a complement.
+///
+/// A *complement* of *x* is an element *y* with *x* ∨ *y* = ⊤ and *x* ∧ *y* = ⊥. In general,
+/// complements need not be unique, in which case [`ComplementedLattice::complement`] picks one.
+/// In a [`DistributiveLattice`], they are always unique.
+pub trait ComplementedLattice: BoundedLattice {
+    /// A complement of `self`.
+    fn complement(&self) -> Self;
+}
+
+/// A Heyting algebra, the algebra of intuitionistic logic.
+///
+/// That is, a [`BoundedLattice`] with a binary operator → called *implication*, where *x* → *y* is
+/// the greatest element *z* with *x* ∧ *z* ≤ *y*. Every Heyting algebra is distributive.
+///
+/// The *pseudo-complement* ¬*x* = *x* → ⊥ is the greatest element disjoint from *x*. Unlike a
+/// complement, we can have *x* ∨ ¬*x* ≠ ⊤, which is exactly why the law of the excluded middle
+/// fails in intuitionistic logic.
+pub trait HeytingAlgebra: BoundedLattice + DistributiveLattice {
+    /// The relative pseudo-complement *x* → *y*.
+    fn implies(&self, rhs: Self) -> Self;
+
+    /// The pseudo-complement ¬*x* = *x* → ⊥.
+    fn pseudo_complement(&self) -> Self {
+        self.implies(Self::BOTTOM)
+    }
+}
+
+/// A Boolean algebra, the algebra of classical logic.
+///
+/// That is, a [`HeytingAlgebra`] which is also a [`ComplementedLattice`]. Equivalently, a
+/// complemented [`DistributiveLattice`]. The complement and pseudo-complement coincide, and
+/// implication is *x* → *y* = ¬*x* ∨ *y*.
+///
+/// # Example
+///
+/// The two-element Boolean algebra is what we use for truth values.
+///
+/// ```rust
+/// use yaaarc::{
+///     grouplike::{Band, CommutativeMagma, Magma, Semigroup, Semilattice, UnitalMagma},
+///     latticelike::{
+///         BooleanAlgebra,
+///         BoundedLattice,
+///         ComplementedLattice,
+///         DistributiveLattice,
+///         HeytingAlgebra,
+///         JoinSemilattice,
+///         Lattice,
+///         MeetSemilattice,
+///         ModularLattice,
+///     },
+///     operators::{BinaryOperator, Join, Meet},
+/// };
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Truth(bool);
+///
+/// impl BinaryOperator<Join> for Truth {
+///     fn op(&self, rhs: Self) -> Self {
+///         Truth(self.0 || rhs.0)
+///     }
+///     fn op_assign(&mut self, rhs: Self) {
+///         self.0 |= rhs.0;
+///     }
+/// }
+///
+/// impl BinaryOperator<Meet> for Truth {
+///     fn op(&self, rhs: Self) -> Self {
+///         Truth(self.0 && rhs.0)
+///     }
+///     fn op_assign(&mut self, rhs: Self) {
+///         self.0 &= rhs.0;
+///     }
+/// }
+///
+/// impl Magma<Join> for Truth {}
+/// impl Semigroup<Join> for Truth {}
+/// impl Band<Join> for Truth {}
+/// impl CommutativeMagma<Join> for Truth {}
+/// impl Semilattice<Join> for Truth {}
+/// impl UnitalMagma<Join> for Truth {
+///     const IDENTITY: Self = Truth(false);
+/// }
+/// impl JoinSemilattice for Truth {}
+///
+/// impl Magma<Meet> for Truth {}
+/// impl Semigroup<Meet> for Truth {}
+/// impl Band<Meet> for Truth {}
+/// impl CommutativeMagma<Meet> for Truth {}
+/// impl Semilattice<Meet> for Truth {}
+/// impl UnitalMagma<Meet> for Truth {
+///     const IDENTITY: Self = Truth(true);
+/// }
+/// impl MeetSemilattice for Truth {}
+///
+/// impl Lattice for Truth {}
+/// impl BoundedLattice for Truth {
+///     const BOTTOM: Self = Truth(false);
+///     const TOP: Self = Truth(true);
+/// }
+/// impl ModularLattice for Truth {}
+/// impl DistributiveLattice for Truth {}
+/// impl ComplementedLattice for Truth {
+///     fn complement(&self) -> Self {
+///         Truth(!self.0)
+///     }
+/// }
+/// impl HeytingAlgebra for Truth {
+///     fn implies(&self, rhs: Self) -> Self {
+///         self.complement().join(rhs)
+///     }
+/// }
+/// impl BooleanAlgebra for Truth {}
+///
+/// assert!(Truth::BOTTOM.leq(&Truth::TOP));
+/// assert_eq!(Truth(true).implies(Truth(false)), Truth(false));
+/// assert_eq!(Truth(false).pseudo_complement(), Truth(true));
+/// ```
+pub trait BooleanAlgebra: HeytingAlgebra + ComplementedLattice {}
@@ -0,0 +1,795 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Reduced ordered binary decision diagrams.
+//!
+//! A binary decision diagram (BDD) represents a Boolean function of variables *x*₀, *x*₁, … as a
+//! directed acyclic graph. Each inner node tests a variable and has a *low* child (taken when the
+//! variable is false) and a *high* child (taken when it's true), and the two leaves are ⊥ and ⊤.
+//! The variables are tested in the same order along every path, and no node is redundant (has equal
+//! children) or duplicated. Under these conditions, every function has exactly one BDD, so
+//! checking equivalence is a pointer comparison.
+//!
+//! All the nodes live in a [`BddManager`], which keeps a *unique table* so that nodes are never
+//! duplicated, and a cache of the results of [`Bdd::ite`], which every other operator is built on.
+//! A [`Bdd`] is a cheap handle to a node in a manager. The constants [`Bdd::TOP`] and
+//! [`Bdd::BOTTOM`] don't belong to any manager, and can be combined with BDDs from any of them.
+//!
+//! The size of a BDD can depend dramatically on the variable order, so the manager can reorder
+//! variables with [`BddManager::sift`]. Reordering rewrites nodes in place, so existing handles stay
+//! valid and keep representing the same functions.
+//!
+//! Nodes are never freed. If a manager accumulates too many dead nodes, rebuild the functions you
+//! need in a fresh one.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
+use crate::{
+    grouplike::{Band, CommutativeMagma, Magma, Semigroup, Semilattice, UnitalMagma},
+    latticelike::{
+        boolean::boolean_algebra_markers, BooleanAlgebra, BoundedLattice, ComplementedLattice,
+        DistributiveLattice, HeytingAlgebra, JoinSemilattice, Lattice, MeetSemilattice,
+        ModularLattice,
+    },
+    operators::{BinaryOperator, Join, Meet},
+};
+
+/// The index of a node in [`Inner::nodes`]. The leaves ⊥ and ⊤ are always 0 and 1.
+type NodeId = usize;
+
+const FALSE: NodeId = 0;
+const TRUE: NodeId = 1;
+
+/// The variable of the leaves, which is below every real variable.
+const LEAF: usize = usize::MAX;
+
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    variable: usize,
+    low: NodeId,
+    high: NodeId,
+}
+
+#[derive(Debug)]
+struct Inner {
+    nodes: Vec<Node>,
+    unique: HashMap<(usize, NodeId, NodeId), NodeId>,
+    ite_cache: HashMap<(NodeId, NodeId, NodeId), NodeId>,
+    /// The level of each variable, where level 0 is tested first.
+    levels: Vec<usize>,
+    /// The variable at each level, the inverse permutation of `levels`.
+    order: Vec<usize>,
+}
+
+/// A collection of BDD nodes sharing a variable order.
+///
+/// Cloning a manager gives another handle to the same nodes.
+///
+/// # Example
+///
+/// Evaluating a [`crate::latticelike::expr::BoolExpr`] into BDDs gives a canonical form, so two
+/// formulas are equivalent exactly when their BDDs are equal.
+///
+/// ```rust
+/// use yaaarc::latticelike::{
+///     bdd::{Bdd, BddManager},
+///     expr::BoolExpr,
+///     BoundedLattice,
+/// };
+///
+/// let manager = BddManager::new();
+/// let to_bdd = |formula: &str| {
+///     let formula: BoolExpr = formula.parse().unwrap();
+///     formula.evaluate(|v: &str| manager.variable(v[1..].parse().unwrap()))
+/// };
+///
+/// let f = to_bdd("x0 & x1 | x0 & x2");
+/// assert_eq!(f, to_bdd("x0 & (x1 | x2)"));
+/// assert_eq!(f.sat_count(3), 3);
+/// assert_eq!(f.exists(&[0]), to_bdd("x1 | x2"));
+/// assert_eq!(f.restrict(1, false), to_bdd("x0 & x2"));
+/// assert_eq!(to_bdd("x0 | !x0"), Bdd::TOP);
+/// ```
+#[derive(Clone)]
+pub struct BddManager {
+    inner: Rc<RefCell<Inner>>,
+}
+
+/// A Boolean function, as a node of a [`BddManager`].
+///
+/// BDDs form a [`BooleanAlgebra`], so they can be used wherever one is expected.
+#[derive(Clone)]
+pub struct Bdd {
+    /// The manager owning the node, or `None` for the constants, which are the same in every
+    /// manager.
+    manager: Option<Rc<RefCell<Inner>>>,
+    node: NodeId,
+}
+
+impl Inner {
+    fn level(&self, node: NodeId) -> usize {
+        match self.nodes[node].variable {
+            LEAF => LEAF,
+            variable => self.levels[variable],
+        }
+    }
+
+    fn make(&mut self, variable: usize, low: NodeId, high: NodeId) -> NodeId {
+        if low == high {
+            return low;
+        }
+        if let Some(&node) = self.unique.get(&(variable, low, high)) {
+            return node;
+        }
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            variable,
+            low,
+            high,
+        });
+        self.unique.insert((variable, low, high), node);
+        node
+    }
+
+    /// The cofactors of `node` with respect to the variable at `level`.
+    fn cofactors(&self, node: NodeId, level: usize) -> (NodeId, NodeId) {
+        if self.level(node) == level {
+            (self.nodes[node].low, self.nodes[node].high)
+        } else {
+            (node, node)
+        }
+    }
+
+    fn ite(&mut self, f: NodeId, g: NodeId, h: NodeId) -> NodeId {
+        match (f, g, h) {
+            (TRUE, _, _) => return g,
+            (FALSE, _, _) => return h,
+            _ if g == h => return g,
+            (_, TRUE, FALSE) => return f,
+            _ => {}
+        }
+        if let Some(&result) = self.ite_cache.get(&(f, g, h)) {
+            return result;
+        }
+        let level = self.level(f).min(self.level(g)).min(self.level(h));
+        let (f0, f1) = self.cofactors(f, level);
+        let (g0, g1) = self.cofactors(g, level);
+        let (h0, h1) = self.cofactors(h, level);
+        let low = self.ite(f0, g0, h0);
+        let high = self.ite(f1, g1, h1);
+        let result = self.make(self.order[level], low, high);
+        self.ite_cache.insert((f, g, h), result);
+        result
+    }
+
+    fn not(&mut self, f: NodeId) -> NodeId {
+        self.ite(f, FALSE, TRUE)
+    }
+
+    fn or(&mut self, f: NodeId, g: NodeId) -> NodeId {
+        self.ite(f, TRUE, g)
+    }
+
+    fn and(&mut self, f: NodeId, g: NodeId) -> NodeId {
+        self.ite(f, g, FALSE)
+    }
+
+    /// Quantifies away the variables in `variables`, combining the cofactors with `or` (for ∃)
+    /// or `and` (for ∀).
+    fn quantify(
+        &mut self,
+        f: NodeId,
+        variables: &HashSet<usize>,
+        existential: bool,
+        memo: &mut HashMap<NodeId, NodeId>,
+    ) -> NodeId {
+        if f == FALSE || f == TRUE {
+            return f;
+        }
+        if let Some(&result) = memo.get(&f) {
+            return result;
+        }
+        let Node {
+            variable,
+            low,
+            high,
+        } = self.nodes[f];
+        let low = self.quantify(low, variables, existential, memo);
+        let high = self.quantify(high, variables, existential, memo);
+        let result = match (variables.contains(&variable), existential) {
+            (true, true) => self.or(low, high),
+            (true, false) => self.and(low, high),
+            (false, _) => self.make(variable, low, high),
+        };
+        memo.insert(f, result);
+        result
+    }
+
+    fn restrict(
+        &mut self,
+        f: NodeId,
+        variable: usize,
+        value: bool,
+        memo: &mut HashMap<NodeId, NodeId>,
+    ) -> NodeId {
+        if self.level(f) > self.levels[variable] {
+            return f;
+        }
+        if let Some(&result) = memo.get(&f) {
+            return result;
+        }
+        let node = self.nodes[f];
+        let result = if node.variable == variable {
+            if value {
+                node.high
+            } else {
+                node.low
+            }
+        } else {
+            let low = self.restrict(node.low, variable, value, memo);
+            let high = self.restrict(node.high, variable, value, memo);
+            self.make(node.variable, low, high)
+        };
+        memo.insert(f, result);
+        result
+    }
+
+    /// The nodes reachable from `roots`, including the leaves.
+    fn reachable(&self, roots: impl IntoIterator<Item = NodeId>) -> HashSet<NodeId> {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<NodeId> = roots.into_iter().collect();
+        while let Some(node) = stack.pop() {
+            if seen.insert(node) && node != FALSE && node != TRUE {
+                stack.push(self.nodes[node].low);
+                stack.push(self.nodes[node].high);
+            }
+        }
+        seen
+    }
+
+    /// Swaps the variables at `level` and `level + 1`, rewriting nodes in place so that every
+    /// node keeps representing the same function.
+    fn swap(&mut self, level: usize) {
+        let x = self.order[level];
+        let y = self.order[level + 1];
+        let is_y = |inner: &Inner, node: NodeId| inner.nodes[node].variable == y;
+        // Only the x nodes with a y child need to change. The rest are fine as they are.
+        let affected: Vec<NodeId> = (2..self.nodes.len())
+            .filter(|&node| {
+                let Node {
+                    variable,
+                    low,
+                    high,
+                } = self.nodes[node];
+                variable == x && (is_y(self, low) || is_y(self, high))
+            })
+            .collect();
+        self.order.swap(level, level + 1);
+        self.levels[x] = level + 1;
+        self.levels[y] = level;
+        self.ite_cache.clear();
+        for node in affected {
+            let Node { low, high, .. } = self.nodes[node];
+            let split = |inner: &Inner, child: NodeId| {
+                if is_y(inner, child) {
+                    (inner.nodes[child].low, inner.nodes[child].high)
+                } else {
+                    (child, child)
+                }
+            };
+            let (f00, f01) = split(self, low);
+            let (f10, f11) = split(self, high);
+            // f = x ? (y ? f11 : f10) : (y ? f01 : f00) = y ? (x ? f11 : f01) : (x ? f10 : f00).
+            let new_low = self.make(x, f00, f10);
+            let new_high = self.make(x, f01, f11);
+            self.unique.remove(&(x, low, high));
+            self.nodes[node] = Node {
+                variable: y,
+                low: new_low,
+                high: new_high,
+            };
+            self.unique.insert((y, new_low, new_high), node);
+        }
+    }
+}
+
+impl BddManager {
+    /// Creates a manager with no variables.
+    pub fn new() -> Self {
+        let leaf = |node| Node {
+            variable: LEAF,
+            low: node,
+            high: node,
+        };
+        BddManager {
+            inner: Rc::new(RefCell::new(Inner {
+                nodes: vec![leaf(FALSE), leaf(TRUE)],
+                unique: HashMap::new(),
+                ite_cache: HashMap::new(),
+                levels: Vec::new(),
+                order: Vec::new(),
+            })),
+        }
+    }
+
+    fn handle(&self, node: NodeId) -> Bdd {
+        Bdd {
+            manager: Some(Rc::clone(&self.inner)),
+            node,
+        }
+    }
+
+    /// The function which is true exactly when *x*ᵢ is.
+    ///
+    /// The manager grows to include variable `i` if needed. New variables are placed last in the
+    /// order.
+    pub fn variable(&self, i: usize) -> Bdd {
+        let mut inner = self.inner.borrow_mut();
+        while inner.levels.len() <= i {
+            let next = inner.levels.len();
+            inner.levels.push(next);
+            inner.order.push(next);
+        }
+        let node = inner.make(i, FALSE, TRUE);
+        drop(inner);
+        self.handle(node)
+    }
+
+    /// The number of variables the manager knows about.
+    pub fn variable_count(&self) -> usize {
+        self.inner.borrow().levels.len()
+    }
+
+    /// The total number of nodes ever created, including the two leaves.
+    pub fn node_count(&self) -> usize {
+        self.inner.borrow().nodes.len()
+    }
+
+    /// The variables in the order they're tested, from the root down.
+    pub fn variable_order(&self) -> Vec<usize> {
+        self.inner.borrow().order.clone()
+    }
+
+    /// Swaps the variables at `level` and `level + 1` in the order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level + 1` isn't a level.
+    pub fn swap_levels(&self, level: usize) {
+        let mut inner = self.inner.borrow_mut();
+        assert!(level + 1 < inner.order.len(), "level out of range");
+        inner.swap(level);
+    }
+
+    /// Reorders the variables to make `roots` smaller, using Rudell's sifting algorithm.
+    ///
+    /// Each variable in turn is moved through every level by swapping adjacent levels, and is left
+    /// wherever the total number of nodes reachable from `roots` was smallest. Returns that total.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the roots belongs to a different manager.
+    ///
+    /// # Example
+    ///
+    /// The function *x*₀*x*₃ ∨ *x*₁*x*₄ ∨ *x*₂*x*₅ has an exponentially large BDD when the
+    /// pairs are far apart, but a small one when they're next to each other.
+    ///
+    /// ```rust
+    /// use yaaarc::latticelike::{bdd::BddManager, JoinSemilattice, MeetSemilattice};
+    ///
+    /// let manager = BddManager::new();
+    /// let x = |i| manager.variable(i);
+    /// let f = x(0).meet(x(3)).join(x(1).meet(x(4))).join(x(2).meet(x(5)));
+    /// let before = f.size();
+    /// let after = manager.sift(&[&f]);
+    ///
+    /// assert_eq!((before, after), (16, 8));
+    /// assert_eq!(f, x(0).meet(x(3)).join(x(1).meet(x(4))).join(x(2).meet(x(5))));
+    /// ```
+    pub fn sift(&self, roots: &[&Bdd]) -> usize {
+        let roots: Vec<NodeId> = roots
+            .iter()
+            .map(|root| {
+                if let Some(manager) = &root.manager {
+                    assert!(
+                        Rc::ptr_eq(manager, &self.inner),
+                        "BDDs from different managers can't be mixed"
+                    );
+                }
+                root.node
+            })
+            .collect();
+        let mut inner = self.inner.borrow_mut();
+        let size = |inner: &Inner| inner.reachable(roots.iter().copied()).len();
+        let levels = inner.order.len();
+        let mut best_size = size(&inner);
+        // Sift the variables with the most nodes first.
+        let mut variables: Vec<usize> = (0..levels).collect();
+        let reachable = inner.reachable(roots.iter().copied());
+        let count = |variable: usize| {
+            reachable
+                .iter()
+                .filter(|&&node| inner.nodes[node].variable == variable)
+                .count()
+        };
+        variables.sort_by_key(|&variable| std::cmp::Reverse(count(variable)));
+        for variable in variables {
+            let mut level = inner.levels[variable];
+            let mut best_level = level;
+            while level + 1 < levels {
+                inner.swap(level);
+                level += 1;
+                let current = size(&inner);
+                if current < best_size {
+                    best_size = current;
+                    best_level = level;
+                }
+            }
+            while level > 0 {
+                inner.swap(level - 1);
+                level -= 1;
+                let current = size(&inner);
+                if current < best_size {
+                    best_size = current;
+                    best_level = level;
+                }
+            }
+            while level < best_level {
+                inner.swap(level);
+                level += 1;
+            }
+        }
+        best_size
+    }
+}
+
+impl Default for BddManager {
+    fn default() -> Self {
+        BddManager::new()
+    }
+}
+
+impl fmt::Debug for BddManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.borrow();
+        f.debug_struct("BddManager")
+            .field("nodes", &inner.nodes.len())
+            .field("order", &inner.order)
+            .finish()
+    }
+}
+
+impl Bdd {
+    /// The manager of whichever of the BDDs has one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two of the BDDs belong to different managers.
+    fn shared_manager<'a>(bdds: &[&'a Bdd]) -> Option<&'a Rc<RefCell<Inner>>> {
+        let mut managers = bdds.iter().filter_map(|bdd| bdd.manager.as_ref());
+        let first = managers.next()?;
+        assert!(
+            managers.all(|other| Rc::ptr_eq(first, other)),
+            "BDDs from different managers can't be mixed"
+        );
+        Some(first)
+    }
+
+    /// Applies an operation of the manager to some BDDs.
+    fn apply(bdds: &[&Bdd], operation: impl FnOnce(&mut Inner, &[NodeId]) -> NodeId) -> Bdd {
+        let nodes: Vec<NodeId> = bdds.iter().map(|bdd| bdd.node).collect();
+        match Bdd::shared_manager(bdds) {
+            Some(manager) => {
+                let node = operation(&mut manager.borrow_mut(), &nodes);
+                Bdd {
+                    manager: Some(Rc::clone(manager)),
+                    node,
+                }
+            }
+            // Only the constants have no manager, and ITE on leaves never creates a node.
+            None => Bdd {
+                manager: None,
+                node: operation(&mut BddManager::new().inner.borrow_mut(), &nodes),
+            },
+        }
+    }
+
+    /// If-then-else: the function which is `then` where `self` is true, and `otherwise` where it's
+    /// false.
+    pub fn ite(&self, then: &Bdd, otherwise: &Bdd) -> Bdd {
+        Bdd::apply(&[self, then, otherwise], |inner, nodes| {
+            inner.ite(nodes[0], nodes[1], nodes[2])
+        })
+    }
+
+    /// Exclusive or.
+    pub fn xor(&self, rhs: &Bdd) -> Bdd {
+        Bdd::apply(&[self, rhs], |inner, nodes| {
+            let not_rhs = inner.not(nodes[1]);
+            inner.ite(nodes[0], not_rhs, nodes[1])
+        })
+    }
+
+    /// Whether this is ⊤ or ⊥.
+    pub fn is_constant(&self) -> bool {
+        self.node == FALSE || self.node == TRUE
+    }
+
+    /// The variable tested at the root, or `None` for a constant.
+    pub fn root_variable(&self) -> Option<usize> {
+        let manager = self.manager.as_ref()?;
+        match manager.borrow().nodes[self.node].variable {
+            LEAF => None,
+            variable => Some(variable),
+        }
+    }
+
+    /// The BDDs of the low and high children of the root, or `None` for a constant.
+    pub fn children(&self) -> Option<(Bdd, Bdd)> {
+        let manager = self.manager.as_ref()?;
+        let node = manager.borrow().nodes[self.node];
+        let child = |node| Bdd {
+            manager: Some(Rc::clone(manager)),
+            node,
+        };
+        (node.variable != LEAF).then(|| (child(node.low), child(node.high)))
+    }
+
+    /// Substitutes `value` for *x*ᵢ.
+    pub fn restrict(&self, i: usize, value: bool) -> Bdd {
+        Bdd::apply(&[self], |inner, nodes| {
+            if i >= inner.levels.len() {
+                return nodes[0];
+            }
+            inner.restrict(nodes[0], i, value, &mut HashMap::new())
+        })
+    }
+
+    /// The existential quantification ∃*x*ᵢ… *f* over the given variables.
+    pub fn exists(&self, variables: &[usize]) -> Bdd {
+        let variables: HashSet<usize> = variables.iter().copied().collect();
+        Bdd::apply(&[self], |inner, nodes| {
+            inner.quantify(nodes[0], &variables, true, &mut HashMap::new())
+        })
+    }
+
+    /// The universal quantification ∀*x*ᵢ… *f* over the given variables.
+    pub fn forall(&self, variables: &[usize]) -> Bdd {
+        let variables: HashSet<usize> = variables.iter().copied().collect();
+        Bdd::apply(&[self], |inner, nodes| {
+            inner.quantify(nodes[0], &variables, false, &mut HashMap::new())
+        })
+    }
+
+    /// Evaluates the function, where `assignment` gives the value of each variable.
+    pub fn evaluate(&self, assignment: impl Fn(usize) -> bool) -> bool {
+        let Some(manager) = &self.manager else {
+            return self.node == TRUE;
+        };
+        let inner = manager.borrow();
+        let mut node = self.node;
+        while node != FALSE && node != TRUE {
+            let Node {
+                variable,
+                low,
+                high,
+            } = inner.nodes[node];
+            node = if assignment(variable) { high } else { low };
+        }
+        node == TRUE
+    }
+
+    /// The variables the function depends on, in increasing order.
+    pub fn support(&self) -> Vec<usize> {
+        let Some(manager) = &self.manager else {
+            return Vec::new();
+        };
+        let inner = manager.borrow();
+        let mut support: Vec<usize> = inner
+            .reachable([self.node])
+            .into_iter()
+            .map(|node| inner.nodes[node].variable)
+            .filter(|&variable| variable != LEAF)
+            .collect();
+        support.sort_unstable();
+        support.dedup();
+        support
+    }
+
+    /// The number of nodes in the diagram, including the leaves.
+    pub fn size(&self) -> usize {
+        match &self.manager {
+            Some(manager) => manager.borrow().reachable([self.node]).len(),
+            None => 1,
+        }
+    }
+
+    /// The number of assignments of *x*₀, …, *x*ₙ₋₁ which make the function true, where `n` is
+    /// `variables`.
+    ///
+    /// Only the levels of the support are counted over, so the manager may have any number of
+    /// variables.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::latticelike::{bdd::BddManager, JoinSemilattice, MeetSemilattice};
+    ///
+    /// let manager = BddManager::new();
+    /// let (x0, x2) = (manager.variable(0), manager.variable(2));
+    /// // The manager has 200 variables, but we only count over the first three.
+    /// manager.variable(199);
+    /// assert_eq!(x0.join(x2.clone()).sat_count(3), 6);
+    /// assert_eq!(x0.meet(x2).sat_count(127), 1 << 125);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function depends on a variable *x*ᵢ with *i* ≥ `n`, or if `n` > 127.
+    pub fn sat_count(&self, variables: usize) -> u128 {
+        assert!(variables < 128, "can only count over at most 127 variables");
+        assert!(
+            self.support().iter().all(|&i| i < variables),
+            "the function depends on a variable which isn't being counted"
+        );
+        let Some(manager) = &self.manager else {
+            return if self.node == TRUE { 1 << variables } else { 0 };
+        };
+        let support = self.support();
+        let inner = manager.borrow();
+        // The rank of each support variable's level among the levels of the support, so that a
+        // node's count only covers the support, and the shifts stay below `variables`.
+        let mut levels: Vec<usize> = support.iter().map(|&v| inner.levels[v]).collect();
+        levels.sort_unstable();
+        let rank = |node: NodeId| match inner.level(node) {
+            LEAF => levels.len(),
+            level => levels
+                .binary_search(&level)
+                .expect("the node is in the support"),
+        };
+        // Count over the support variables at or below each node's rank, then scale up.
+        fn count(
+            inner: &Inner,
+            rank: &dyn Fn(NodeId) -> usize,
+            node: NodeId,
+            memo: &mut HashMap<NodeId, u128>,
+        ) -> u128 {
+            if node == FALSE || node == TRUE {
+                return node as u128;
+            }
+            if let Some(&result) = memo.get(&node) {
+                return result;
+            }
+            let mut scaled =
+                |child: NodeId| count(inner, rank, child, memo) << (rank(child) - rank(node) - 1);
+            let result = scaled(inner.nodes[node].low) + scaled(inner.nodes[node].high);
+            memo.insert(node, result);
+            result
+        }
+        let over_support = count(&inner, &rank, self.node, &mut HashMap::new()) << rank(self.node);
+        // The counted variables outside the support are free.
+        over_support << (variables - support.len())
+    }
+
+    /// An assignment of the support which makes the function true, or `None` if it's ⊥.
+    pub fn satisfying_assignment(&self) -> Option<Vec<(usize, bool)>> {
+        if self.node == FALSE {
+            return None;
+        }
+        let mut assignment = Vec::new();
+        if let Some(manager) = &self.manager {
+            let inner = manager.borrow();
+            let mut node = self.node;
+            while node != TRUE {
+                let Node {
+                    variable,
+                    low,
+                    high,
+                } = inner.nodes[node];
+                let value = low == FALSE;
+                assignment.push((variable, value));
+                node = if value { high } else { low };
+            }
+        }
+        Some(assignment)
+    }
+}
+
+impl PartialEq for Bdd {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+            && (self.is_constant()
+                || match (&self.manager, &other.manager) {
+                    (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+                    _ => false,
+                })
+    }
+}
+
+impl Eq for Bdd {}
+
+impl Hash for Bdd {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node.hash(state);
+    }
+}
+
+impl fmt::Debug for Bdd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.node {
+            FALSE => write!(f, "Bdd(⊥)"),
+            TRUE => write!(f, "Bdd(⊤)"),
+            node => write!(f, "Bdd({node})"),
+        }
+    }
+}
+
+impl BinaryOperator<Join> for Bdd {
+    fn op(&self, rhs: Self) -> Self {
+        Bdd::apply(&[self, &rhs], |inner, nodes| inner.or(nodes[0], nodes[1]))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.join(rhs);
+    }
+}
+
+impl BinaryOperator<Meet> for Bdd {
+    fn op(&self, rhs: Self) -> Self {
+        Bdd::apply(&[self, &rhs], |inner, nodes| inner.and(nodes[0], nodes[1]))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.meet(rhs);
+    }
+}
+
+impl UnitalMagma<Join> for Bdd {
+    const IDENTITY: Self = Bdd::BOTTOM;
+}
+impl UnitalMagma<Meet> for Bdd {
+    const IDENTITY: Self = Bdd::TOP;
+}
+
+impl BoundedLattice for Bdd {
+    const BOTTOM: Self = Bdd {
+        manager: None,
+        node: FALSE,
+    };
+    const TOP: Self = Bdd {
+        manager: None,
+        node: TRUE,
+    };
+}
+
+impl ComplementedLattice for Bdd {
+    fn complement(&self) -> Self {
+        Bdd::apply(&[self], |inner, nodes| inner.not(nodes[0]))
+    }
+}
+
+impl HeytingAlgebra for Bdd {
+    fn implies(&self, rhs: Self) -> Self {
+        Bdd::apply(&[self, &rhs], |inner, nodes| {
+            inner.ite(nodes[0], nodes[1], TRUE)
+        })
+    }
+}
+
+boolean_algebra_markers!([] Bdd);
@@ -0,0 +1,766 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Concrete Boolean algebras.
+//!
+//! We implement [`BooleanAlgebra`] for:
+//!
+//! * [`bool`], the two-element Boolean algebra of truth values.
+//! * The unsigned integers [`u8`], [`u16`], [`u32`], [`u64`] and [`u128`], seen as bit masks.
+//!   Join is bitwise or, meet is bitwise and, and the complement flips every bit.
+//! * Arrays `[B; N]` of Boolean algebras, where everything is done componentwise. So `[bool; N]`
+//!   is a fixed-width bit vector, and `[u64; N]` is a bit vector of width 64*N*.
+//! * [`BitSet`], the finite and cofinite sets of natural numbers, which acts as a bitset of
+//!   arbitrary length.
+//! * [`PowerSet`], the set of all subsets of a [`Finite`] type.
+//!
+//! Every Boolean algebra is also a [`BooleanRing`], with symmetric difference as addition and meet
+//! as multiplication. Conversely, every Boolean ring is a Boolean algebra. We provide these
+//! conversions with the wrappers [`AsBooleanRing`] and [`AsBooleanAlgebra`].
+
+use std::{fmt, marker::PhantomData};
+
+use crate::{
+    grouplike::{
+        AbelianGroup, Band, CommutativeMagma, Magma, Monoid, Quasigroup, Semigroup, Semilattice,
+        UnitalMagma,
+    },
+    latticelike::{
+        BooleanAlgebra, BoundedLattice, ComplementedLattice, DistributiveLattice, HeytingAlgebra,
+        JoinSemilattice, Lattice, MeetSemilattice, ModularLattice,
+    },
+    operators::{BinaryOperator, Join, Meet, Plus, Times},
+    ringlike::{BooleanRing, CommutativeRing, Ring},
+};
+
+/// Implements all the marker traits on the way to a [`DistributiveLattice`].
+macro_rules! distributive_lattice_markers {
+    ([$($generics:tt)*] $ty:ty) => {
+        impl<$($generics)*> Magma<Join> for $ty {}
+        impl<$($generics)*> Semigroup<Join> for $ty {}
+        impl<$($generics)*> Band<Join> for $ty {}
+        impl<$($generics)*> CommutativeMagma<Join> for $ty {}
+        impl<$($generics)*> Semilattice<Join> for $ty {}
+        impl<$($generics)*> JoinSemilattice for $ty {}
+
+        impl<$($generics)*> Magma<Meet> for $ty {}
+        impl<$($generics)*> Semigroup<Meet> for $ty {}
+        impl<$($generics)*> Band<Meet> for $ty {}
+        impl<$($generics)*> CommutativeMagma<Meet> for $ty {}
+        impl<$($generics)*> Semilattice<Meet> for $ty {}
+        impl<$($generics)*> MeetSemilattice for $ty {}
+
+        impl<$($generics)*> Lattice for $ty {}
+        impl<$($generics)*> ModularLattice for $ty {}
+        impl<$($generics)*> DistributiveLattice for $ty {}
+    };
+}
+pub(crate) use distributive_lattice_markers;
+
+/// Implements all the marker traits on the way to a [`BooleanAlgebra`].
+macro_rules! boolean_algebra_markers {
+    ([$($generics:tt)*] $ty:ty) => {
+        $crate::latticelike::boolean::distributive_lattice_markers!([$($generics)*] $ty);
+        impl<$($generics)*> BooleanAlgebra for $ty {}
+    };
+}
+pub(crate) use boolean_algebra_markers;
+
+/// Implements [`BooleanAlgebra`] for a type with the bitwise operators `|`, `&` and `!`.
+macro_rules! bitwise_boolean_algebra {
+    ($ty:ty, $bottom:expr, $top:expr) => {
+        impl BinaryOperator<Join> for $ty {
+            fn op(&self, rhs: Self) -> Self {
+                *self | rhs
+            }
+            fn op_assign(&mut self, rhs: Self) {
+                *self |= rhs;
+            }
+        }
+
+        impl BinaryOperator<Meet> for $ty {
+            fn op(&self, rhs: Self) -> Self {
+                *self & rhs
+            }
+            fn op_assign(&mut self, rhs: Self) {
+                *self &= rhs;
+            }
+        }
+
+        impl UnitalMagma<Join> for $ty {
+            const IDENTITY: Self = $bottom;
+        }
+        impl UnitalMagma<Meet> for $ty {
+            const IDENTITY: Self = $top;
+        }
+
+        impl BoundedLattice for $ty {
+            const BOTTOM: Self = $bottom;
+            const TOP: Self = $top;
+        }
+
+        impl ComplementedLattice for $ty {
+            fn complement(&self) -> Self {
+                !*self
+            }
+        }
+
+        impl HeytingAlgebra for $ty {
+            fn implies(&self, rhs: Self) -> Self {
+                !*self | rhs
+            }
+        }
+
+        boolean_algebra_markers!([] $ty);
+    };
+}
+
+bitwise_boolean_algebra!(bool, false, true);
+bitwise_boolean_algebra!(u8, 0, u8::MAX);
+bitwise_boolean_algebra!(u16, 0, u16::MAX);
+bitwise_boolean_algebra!(u32, 0, u32::MAX);
+bitwise_boolean_algebra!(u64, 0, u64::MAX);
+bitwise_boolean_algebra!(u128, 0, u128::MAX);
+
+impl<B: BooleanAlgebra, const N: usize> BinaryOperator<Join> for [B; N] {
+    fn op(&self, rhs: Self) -> Self {
+        let mut rhs = rhs.into_iter();
+        std::array::from_fn(|i| self[i].join(rhs.next().expect("arrays have the same length")))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        for (x, y) in self.iter_mut().zip(rhs) {
+            <B as BinaryOperator<Join>>::op_assign(x, y);
+        }
+    }
+}
+
+impl<B: BooleanAlgebra, const N: usize> BinaryOperator<Meet> for [B; N] {
+    fn op(&self, rhs: Self) -> Self {
+        let mut rhs = rhs.into_iter();
+        std::array::from_fn(|i| self[i].meet(rhs.next().expect("arrays have the same length")))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        for (x, y) in self.iter_mut().zip(rhs) {
+            <B as BinaryOperator<Meet>>::op_assign(x, y);
+        }
+    }
+}
+
+impl<B: BooleanAlgebra, const N: usize> UnitalMagma<Join> for [B; N] {
+    const IDENTITY: Self = [const { B::BOTTOM }; N];
+}
+impl<B: BooleanAlgebra, const N: usize> UnitalMagma<Meet> for [B; N] {
+    const IDENTITY: Self = [const { B::TOP }; N];
+}
+
+impl<B: BooleanAlgebra, const N: usize> BoundedLattice for [B; N] {
+    const BOTTOM: Self = [const { B::BOTTOM }; N];
+    const TOP: Self = [const { B::TOP }; N];
+}
+
+impl<B: BooleanAlgebra, const N: usize> ComplementedLattice for [B; N] {
+    fn complement(&self) -> Self {
+        std::array::from_fn(|i| self[i].complement())
+    }
+}
+
+impl<B: BooleanAlgebra, const N: usize> HeytingAlgebra for [B; N] {
+    fn implies(&self, rhs: Self) -> Self {
+        let mut rhs = rhs.into_iter();
+        std::array::from_fn(|i| self[i].implies(rhs.next().expect("arrays have the same length")))
+    }
+}
+
+boolean_algebra_markers!([B: BooleanAlgebra, const N: usize] [B; N]);
+
+/// A set of natural numbers which is either finite or cofinite (its complement is finite).
+///
+/// These sets are closed under union, intersection and complement, so they form a Boolean
+/// algebra, with the empty set as ⊥ and ℕ as ⊤. In practice, this is a bitset which grows as
+/// needed, and which can also represent "everything except these bits".
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::latticelike::{
+///     boolean::BitSet, BoundedLattice, ComplementedLattice, JoinSemilattice, MeetSemilattice,
+/// };
+///
+/// let evens: BitSet = (0..200).step_by(2).collect();
+/// let small: BitSet = (0..5).collect();
+///
+/// assert_eq!(evens.meet(small.clone()), [0, 2, 4].into_iter().collect());
+/// assert_eq!(evens.join(evens.complement()), BitSet::TOP);
+/// assert_eq!(small.complement().to_string(), "ℕ ∖ {0, 1, 2, 3, 4}");
+/// assert!(small.complement().contains(1000));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    /// The bits, 64 at a time. Trailing words which are all equal to the fill word are trimmed,
+    /// so equal sets have equal representations.
+    words: Vec<u64>,
+    /// Whether every bit past the end of `words` is set.
+    cofinite: bool,
+}
+
+impl BitSet {
+    /// Creates the empty set.
+    pub fn new() -> Self {
+        BitSet::default()
+    }
+
+    fn fill_word(&self) -> u64 {
+        if self.cofinite {
+            u64::MAX
+        } else {
+            0
+        }
+    }
+
+    /// The `k`-th word of bits, which is the fill word if past the end.
+    fn word(&self, k: usize) -> u64 {
+        self.words.get(k).copied().unwrap_or(self.fill_word())
+    }
+
+    fn normalised(mut self) -> Self {
+        let fill = self.fill_word();
+        while self.words.last() == Some(&fill) {
+            self.words.pop();
+        }
+        self
+    }
+
+    fn zip_with(&self, rhs: &Self, cofinite: bool, f: impl Fn(u64, u64) -> u64) -> Self {
+        let length = self.words.len().max(rhs.words.len());
+        BitSet {
+            words: (0..length).map(|k| f(self.word(k), rhs.word(k))).collect(),
+            cofinite,
+        }
+        .normalised()
+    }
+
+    /// Whether `i` is in the set.
+    pub fn contains(&self, i: usize) -> bool {
+        self.word(i / 64) & (1 << (i % 64)) != 0
+    }
+
+    /// Adds `i` to the set, returning whether it was newly added.
+    pub fn insert(&mut self, i: usize) -> bool {
+        let present = self.contains(i);
+        if !present {
+            self.toggle(i);
+        }
+        !present
+    }
+
+    /// Removes `i` from the set, returning whether it was present.
+    pub fn remove(&mut self, i: usize) -> bool {
+        let present = self.contains(i);
+        if present {
+            self.toggle(i);
+        }
+        present
+    }
+
+    fn toggle(&mut self, i: usize) {
+        let fill = self.fill_word();
+        if self.words.len() <= i / 64 {
+            self.words.resize(i / 64 + 1, fill);
+        }
+        self.words[i / 64] ^= 1 << (i % 64);
+        *self = std::mem::take(self).normalised();
+    }
+
+    /// Whether the set is finite. If not, its complement is.
+    pub fn is_finite(&self) -> bool {
+        !self.cofinite
+    }
+
+    /// Whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        !self.cofinite && self.words.is_empty()
+    }
+
+    /// The number of elements, or [`None`] if the set is infinite.
+    pub fn len(&self) -> Option<usize> {
+        self.is_finite()
+            .then(|| self.words.iter().map(|w| w.count_ones() as usize).sum())
+    }
+
+    /// Iterates over the elements in increasing order.
+    ///
+    /// If the set is cofinite, the iterator never ends!
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        // Past the stored words, a finite set has nothing left.
+        (0..)
+            .take_while(|&i| self.cofinite || i < 64 * self.words.len())
+            .filter(|&i| self.contains(i))
+    }
+
+    /// The elements of the complement of a cofinite set, or of the set itself if it's finite.
+    fn finite_part(&self) -> Vec<usize> {
+        (0..64 * self.words.len())
+            .filter(|&i| self.contains(i) != self.cofinite)
+            .collect()
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        for i in iter {
+            set.insert(i);
+        }
+        set
+    }
+}
+
+impl fmt::Display for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let elements: Vec<String> = self.finite_part().iter().map(usize::to_string).collect();
+        match (self.cofinite, elements.is_empty()) {
+            (true, true) => write!(f, "ℕ"),
+            (true, false) => write!(f, "ℕ ∖ {{{}}}", elements.join(", ")),
+            (false, _) => write!(f, "{{{}}}", elements.join(", ")),
+        }
+    }
+}
+
+impl BinaryOperator<Join> for BitSet {
+    fn op(&self, rhs: Self) -> Self {
+        self.zip_with(&rhs, self.cofinite || rhs.cofinite, |a, b| a | b)
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.join(rhs);
+    }
+}
+
+impl BinaryOperator<Meet> for BitSet {
+    fn op(&self, rhs: Self) -> Self {
+        self.zip_with(&rhs, self.cofinite && rhs.cofinite, |a, b| a & b)
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.meet(rhs);
+    }
+}
+
+impl UnitalMagma<Join> for BitSet {
+    const IDENTITY: Self = BitSet::BOTTOM;
+}
+impl UnitalMagma<Meet> for BitSet {
+    const IDENTITY: Self = BitSet::TOP;
+}
+
+impl BoundedLattice for BitSet {
+    const BOTTOM: Self = BitSet {
+        words: Vec::new(),
+        cofinite: false,
+    };
+    const TOP: Self = BitSet {
+        words: Vec::new(),
+        cofinite: true,
+    };
+}
+
+impl ComplementedLattice for BitSet {
+    fn complement(&self) -> Self {
+        BitSet {
+            words: self.words.iter().map(|w| !w).collect(),
+            cofinite: !self.cofinite,
+        }
+    }
+}
+
+impl HeytingAlgebra for BitSet {
+    fn implies(&self, rhs: Self) -> Self {
+        self.complement().join(rhs)
+    }
+}
+
+boolean_algebra_markers!([] BitSet);
+
+/// A type with finitely many values, which can be numbered 0, …, [`Finite::CARDINALITY`] - 1.
+pub trait Finite: Sized {
+    /// The number of values of the type.
+    const CARDINALITY: usize;
+
+    /// The number of this value, which is less than [`Finite::CARDINALITY`].
+    fn index(&self) -> usize;
+
+    /// The value with the given number.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `index` is not less than [`Finite::CARDINALITY`].
+    fn from_index(index: usize) -> Self;
+}
+
+impl Finite for bool {
+    const CARDINALITY: usize = 2;
+
+    fn index(&self) -> usize {
+        usize::from(*self)
+    }
+
+    fn from_index(index: usize) -> Self {
+        assert!(index < 2, "bool has only two values");
+        index == 1
+    }
+}
+
+impl Finite for u8 {
+    const CARDINALITY: usize = 256;
+
+    fn index(&self) -> usize {
+        usize::from(*self)
+    }
+
+    fn from_index(index: usize) -> Self {
+        u8::try_from(index).expect("u8 has only 256 values")
+    }
+}
+
+/// The power set of a [`Finite`] type `T`, i.e. the set of all sets of values of type `T`.
+///
+/// Ordered by inclusion, this is a Boolean algebra, with the empty set as ⊥ and the set of every
+/// value as ⊤.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::latticelike::{boolean::PowerSet, BoundedLattice, ComplementedLattice};
+///
+/// let only_true: PowerSet<bool> = [true].into_iter().collect();
+///
+/// assert_eq!(only_true.complement().iter().collect::<Vec<_>>(), vec![false]);
+/// assert_eq!(PowerSet::<bool>::TOP, [false, true].into_iter().collect());
+/// assert_eq!(PowerSet::<u8>::TOP.len(), 256);
+/// ```
+pub struct PowerSet<T> {
+    /// The indices of the members. Bits past [`Finite::CARDINALITY`] are ignored.
+    bits: BitSet,
+    universe: PhantomData<fn() -> T>,
+}
+
+impl<T: Finite> PowerSet<T> {
+    /// Creates the empty set.
+    pub fn new() -> Self {
+        PowerSet::BOTTOM
+    }
+
+    /// Whether `x` is in the set.
+    pub fn contains(&self, x: &T) -> bool {
+        self.bits.contains(x.index())
+    }
+
+    /// Adds `x` to the set, returning whether it was newly added.
+    pub fn insert(&mut self, x: &T) -> bool {
+        self.bits.insert(x.index())
+    }
+
+    /// Removes `x` from the set, returning whether it was present.
+    pub fn remove(&mut self, x: &T) -> bool {
+        self.bits.remove(x.index())
+    }
+
+    /// Iterates over the members, in the order of [`Finite::index`].
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        (0..T::CARDINALITY)
+            .filter(|&i| self.bits.contains(i))
+            .map(T::from_index)
+    }
+
+    /// The number of members.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
+
+impl<T: Finite> Default for PowerSet<T> {
+    fn default() -> Self {
+        PowerSet::new()
+    }
+}
+
+impl<T> Clone for PowerSet<T> {
+    fn clone(&self) -> Self {
+        PowerSet {
+            bits: self.bits.clone(),
+            universe: PhantomData,
+        }
+    }
+}
+
+impl<T: Finite + fmt::Debug> fmt::Debug for PowerSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Finite> PartialEq for PowerSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (0..T::CARDINALITY.div_ceil(64)).all(|k| {
+            let remaining = T::CARDINALITY - 64 * k;
+            let mask = if remaining >= 64 {
+                u64::MAX
+            } else {
+                (1 << remaining) - 1
+            };
+            (self.bits.word(k) ^ other.bits.word(k)) & mask == 0
+        })
+    }
+}
+
+impl<T: Finite> Eq for PowerSet<T> {}
+
+impl<T: Finite> FromIterator<T> for PowerSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        PowerSet {
+            bits: iter.into_iter().map(|x| x.index()).collect(),
+            universe: PhantomData,
+        }
+    }
+}
+
+impl<T: Finite> BinaryOperator<Join> for PowerSet<T> {
+    fn op(&self, rhs: Self) -> Self {
+        PowerSet {
+            bits: self.bits.join(rhs.bits),
+            universe: PhantomData,
+        }
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        <BitSet as BinaryOperator<Join>>::op_assign(&mut self.bits, rhs.bits);
+    }
+}
+
+impl<T: Finite> BinaryOperator<Meet> for PowerSet<T> {
+    fn op(&self, rhs: Self) -> Self {
+        PowerSet {
+            bits: self.bits.meet(rhs.bits),
+            universe: PhantomData,
+        }
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        <BitSet as BinaryOperator<Meet>>::op_assign(&mut self.bits, rhs.bits);
+    }
+}
+
+impl<T: Finite> UnitalMagma<Join> for PowerSet<T> {
+    const IDENTITY: Self = PowerSet::BOTTOM;
+}
+impl<T: Finite> UnitalMagma<Meet> for PowerSet<T> {
+    const IDENTITY: Self = PowerSet::TOP;
+}
+
+impl<T: Finite> BoundedLattice for PowerSet<T> {
+    const BOTTOM: Self = PowerSet {
+        bits: BitSet::BOTTOM,
+        universe: PhantomData,
+    };
+    const TOP: Self = PowerSet {
+        bits: BitSet::TOP,
+        universe: PhantomData,
+    };
+}
+
+impl<T: Finite> ComplementedLattice for PowerSet<T> {
+    fn complement(&self) -> Self {
+        PowerSet {
+            bits: self.bits.complement(),
+            universe: PhantomData,
+        }
+    }
+}
+
+impl<T: Finite> HeytingAlgebra for PowerSet<T> {
+    fn implies(&self, rhs: Self) -> Self {
+        self.complement().join(rhs)
+    }
+}
+
+boolean_algebra_markers!([T: Finite] PowerSet<T>);
+
+/// A [`BooleanAlgebra`] viewed as a [`BooleanRing`].
+///
+/// Addition is symmetric difference, *x* + *y* = (*x* ∧ ¬*y*) ∨ (¬*x* ∧ *y*), and multiplication
+/// is meet, so zero is ⊥ and one is ⊤. To go back, just take the inner value.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::{latticelike::boolean::AsBooleanRing, ringlike::Ring};
+///
+/// let x = AsBooleanRing(0b1100u8);
+/// let y = AsBooleanRing(0b1010u8);
+///
+/// assert_eq!(x.add(y.clone()), AsBooleanRing(0b0110));
+/// assert_eq!(x.mul(y), AsBooleanRing(0b1000));
+/// // Every element is its own additive inverse.
+/// assert_eq!(x.add(x.clone()), AsBooleanRing::ZERO);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AsBooleanRing<B>(pub B);
+
+impl<B> AsBooleanRing<B> {
+    /// Converts back to the Boolean algebra.
+    pub fn into_inner(self) -> B {
+        self.0
+    }
+}
+
+impl<B: BooleanAlgebra + Clone> BinaryOperator<Plus> for AsBooleanRing<B> {
+    fn op(&self, rhs: Self) -> Self {
+        let left = self.0.meet(rhs.0.complement());
+        let right = self.0.complement().meet(rhs.0);
+        AsBooleanRing(left.join(right))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = BinaryOperator::<Plus>::op(self, rhs);
+    }
+}
+
+impl<B: BooleanAlgebra + Clone> BinaryOperator<Times> for AsBooleanRing<B> {
+    fn op(&self, rhs: Self) -> Self {
+        AsBooleanRing(self.0.meet(rhs.0))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = BinaryOperator::<Times>::op(self, rhs);
+    }
+}
+
+impl<B: BooleanAlgebra + Clone + PartialEq> Magma<Plus> for AsBooleanRing<B> {}
+impl<B: BooleanAlgebra + Clone + PartialEq> Semigroup<Plus> for AsBooleanRing<B> {}
+impl<B: BooleanAlgebra + Clone + PartialEq> UnitalMagma<Plus> for AsBooleanRing<B> {
+    const IDENTITY: Self = AsBooleanRing(B::BOTTOM);
+}
+impl<B: BooleanAlgebra + Clone + PartialEq> Quasigroup<Plus> for AsBooleanRing<B> {
+    fn inverse(&self) -> Self {
+        self.clone()
+    }
+}
+impl<B: BooleanAlgebra + Clone + PartialEq> CommutativeMagma<Plus> for AsBooleanRing<B> {}
+impl<B: BooleanAlgebra + Clone + PartialEq> AbelianGroup<Plus> for AsBooleanRing<B> {}
+
+impl<B: BooleanAlgebra + Clone + PartialEq> Magma<Times> for AsBooleanRing<B> {}
+impl<B: BooleanAlgebra + Clone + PartialEq> Semigroup<Times> for AsBooleanRing<B> {}
+impl<B: BooleanAlgebra + Clone + PartialEq> Band<Times> for AsBooleanRing<B> {}
+impl<B: BooleanAlgebra + Clone + PartialEq> UnitalMagma<Times> for AsBooleanRing<B> {
+    const IDENTITY: Self = AsBooleanRing(B::TOP);
+}
+impl<B: BooleanAlgebra + Clone + PartialEq> CommutativeMagma<Times> for AsBooleanRing<B> {}
+impl<B: BooleanAlgebra + Clone + PartialEq> Monoid<Times> for AsBooleanRing<B> {}
+
+impl<B: BooleanAlgebra + Clone + PartialEq> Ring for AsBooleanRing<B> {
+    const ZERO: Self = AsBooleanRing(B::BOTTOM);
+    const ONE: Self = AsBooleanRing(B::TOP);
+
+    fn left_inverse_mul(&self) -> Option<Self> {
+        self.inverse_mul()
+    }
+
+    fn right_inverse_mul(&self) -> Option<Self> {
+        self.inverse_mul()
+    }
+
+    /// If *xy* = 1, then *x* = *x*(*xy*) = *x*²*y* = *xy* = 1, so one is the only unit.
+    fn inverse_mul(&self) -> Option<Self> {
+        (self.0 == B::TOP).then_some(Self::ONE)
+    }
+}
+
+impl<B: BooleanAlgebra + Clone + PartialEq> CommutativeRing for AsBooleanRing<B> {}
+impl<B: BooleanAlgebra + Clone + PartialEq> BooleanRing for AsBooleanRing<B> {}
+
+/// A [`BooleanRing`] viewed as a [`BooleanAlgebra`].
+///
+/// Meet is multiplication and join is *x* ∨ *y* = *x* + *y* + *xy*, so ⊥ is zero and ⊤ is one. The
+/// complement is ¬*x* = 1 + *x*. To go back, just take the inner value.
+///
+/// # Example
+///
+/// Converting a Boolean algebra to a ring and back again gives us the same operations.
+///
+/// ```rust
+/// use yaaarc::latticelike::{
+///     boolean::{AsBooleanAlgebra, AsBooleanRing},
+///     ComplementedLattice,
+///     JoinSemilattice,
+/// };
+///
+/// let x = AsBooleanAlgebra(AsBooleanRing([true, false, false]));
+/// let y = AsBooleanAlgebra(AsBooleanRing([false, false, true]));
+///
+/// assert_eq!(x.join(y).into_inner().into_inner(), [true, false, true]);
+/// assert_eq!(x.complement().into_inner().into_inner(), [false, true, true]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AsBooleanAlgebra<R>(pub R);
+
+impl<R> AsBooleanAlgebra<R> {
+    /// Converts back to the Boolean ring.
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+impl<R: BooleanRing + Clone> BinaryOperator<Join> for AsBooleanAlgebra<R> {
+    fn op(&self, rhs: Self) -> Self {
+        let product = self.0.mul(rhs.0.clone());
+        AsBooleanAlgebra(self.0.add(rhs.0).add(product))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.join(rhs);
+    }
+}
+
+impl<R: BooleanRing + Clone> BinaryOperator<Meet> for AsBooleanAlgebra<R> {
+    fn op(&self, rhs: Self) -> Self {
+        AsBooleanAlgebra(self.0.mul(rhs.0))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.meet(rhs);
+    }
+}
+
+impl<R: BooleanRing + Clone> UnitalMagma<Join> for AsBooleanAlgebra<R> {
+    const IDENTITY: Self = AsBooleanAlgebra(R::ZERO);
+}
+impl<R: BooleanRing + Clone> UnitalMagma<Meet> for AsBooleanAlgebra<R> {
+    const IDENTITY: Self = AsBooleanAlgebra(R::ONE);
+}
+
+impl<R: BooleanRing + Clone> BoundedLattice for AsBooleanAlgebra<R> {
+    const BOTTOM: Self = AsBooleanAlgebra(R::ZERO);
+    const TOP: Self = AsBooleanAlgebra(R::ONE);
+}
+
+impl<R: BooleanRing + Clone> ComplementedLattice for AsBooleanAlgebra<R> {
+    fn complement(&self) -> Self {
+        AsBooleanAlgebra(R::ONE.add(self.0.clone()))
+    }
+}
+
+impl<R: BooleanRing + Clone> HeytingAlgebra for AsBooleanAlgebra<R> {
+    fn implies(&self, rhs: Self) -> Self {
+        self.complement().join(rhs)
+    }
+}
+
+boolean_algebra_markers!([R: BooleanRing + Clone] AsBooleanAlgebra<R>);
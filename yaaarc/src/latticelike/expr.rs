@@ -0,0 +1,1022 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Boolean formulas.
+//!
+//! A [`BoolExpr`] is a formula built from variables and constants with the usual connectives. It
+//! can be parsed from a string, evaluated in any [`HeytingAlgebra`], and rewritten into one of
+//! several normal forms:
+//!
+//! * *Conjunctive normal form* (CNF), an ∧ of clauses, each of which is an ∨ of literals.
+//! * *Disjunctive normal form* (DNF), an ∨ of terms, each of which is an ∧ of literals.
+//! * *Algebraic normal form* (ANF), a ⊕ of products of variables. This is the formula written as a
+//!   polynomial over 𝔽₂, or in other words, as an element of the free [`crate::ringlike::BooleanRing`].
+//!
+//! We also provide [`BoolExpr::minimise`], which finds a smallest DNF with the Quine–McCluskey
+//! algorithm and Petrick's method.
+//!
+//! Anything that goes through a truth table takes time and space exponential in the number of
+//! variables, so those functions panic with more than 63 variables. Realistically, they're only
+//! usable up to twenty or so.
+//!
+//! Evaluating in a [`HeytingAlgebra`] rather than a Boolean one gives intuitionistic logic. A
+//! formula is intuitionistically valid iff it evaluates to ⊤ in every Heyting algebra, and
+//! [`BoolExpr::counterexample_in`] searches a finite one, such as those in
+//! [`crate::latticelike::heyting`], for a valuation which refutes it.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    error::Error,
+    fmt,
+    str::FromStr,
+};
+
+use crate::latticelike::HeytingAlgebra;
+
+/// A Boolean formula.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::latticelike::expr::BoolExpr;
+///
+/// let formula: BoolExpr = "(a -> b) & (b -> c)".parse().unwrap();
+///
+/// // Evaluate with a = 1, b = 0, c = 1, and then in the Boolean algebra of 8-bit masks.
+/// assert!(!formula.evaluate(|v| v != "b"));
+/// assert_eq!(formula.evaluate(|v| match v {
+///     "a" => 0b1100u8,
+///     "b" => 0b1010,
+///     _ => 0b0110,
+/// }), 0b1111_0011);
+///
+/// assert!(formula.implies_formula(&"a -> c".parse().unwrap()));
+/// assert_eq!(formula.minimise().to_string(), "¬a ∧ ¬b ∨ b ∧ c");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BoolExpr {
+    /// ⊤ or ⊥.
+    Constant(bool),
+    /// A named variable.
+    Variable(String),
+    /// ¬*a*.
+    Not(Box<BoolExpr>),
+    /// *a* ∧ *b*.
+    And(Box<BoolExpr>, Box<BoolExpr>),
+    /// *a* ∨ *b*.
+    Or(Box<BoolExpr>, Box<BoolExpr>),
+    /// *a* ⊕ *b*, exclusive or.
+    Xor(Box<BoolExpr>, Box<BoolExpr>),
+    /// *a* → *b*.
+    Implies(Box<BoolExpr>, Box<BoolExpr>),
+    /// *a* ↔ *b*.
+    Iff(Box<BoolExpr>, Box<BoolExpr>),
+}
+
+/// A truth table of a [`BoolExpr`].
+///
+/// Row *r* gives each variable a value by reading *r* in binary, with the first variable as the
+/// most significant bit. So with variables `a` and `b`, the rows are *ab* = 00, 01, 10, 11.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TruthTable {
+    variables: Vec<String>,
+    values: Vec<bool>,
+}
+
+/// A product of literals, as found by the Quine–McCluskey algorithm.
+///
+/// This uses the same bit order as [`TruthTable`]. A bit set in the mask means that variable is
+/// absent from the product; otherwise the variable appears, negated if its bit in the value is 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Implicant {
+    value: u64,
+    mask: u64,
+}
+
+/// The reasons a [`BoolExpr`] can fail to parse.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseBoolExprError {
+    /// A character which doesn't start any token, at the given byte offset.
+    UnexpectedCharacter { position: usize, character: char },
+    /// A token which doesn't fit the grammar, at the given byte offset.
+    UnexpectedToken { position: usize },
+    /// The input ended in the middle of a formula.
+    UnexpectedEnd,
+}
+
+impl fmt::Display for ParseBoolExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBoolExprError::UnexpectedCharacter {
+                position,
+                character,
+            } => write!(f, "unexpected character {character:?} at {position}"),
+            ParseBoolExprError::UnexpectedToken { position } => {
+                write!(f, "unexpected token at {position}")
+            }
+            ParseBoolExprError::UnexpectedEnd => write!(f, "unexpected end of formula"),
+        }
+    }
+}
+
+impl Error for ParseBoolExprError {}
+
+/// A clause or term of a normal form, mapping each variable to the sign of its literal.
+type Literals = BTreeMap<String, bool>;
+
+impl BoolExpr {
+    /// The variable with the given name.
+    pub fn var(name: &str) -> Self {
+        BoolExpr::Variable(name.to_string())
+    }
+
+    /// ¬`self`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        BoolExpr::Not(Box::new(self))
+    }
+
+    /// `self` ∧ `rhs`.
+    pub fn and(self, rhs: Self) -> Self {
+        BoolExpr::And(Box::new(self), Box::new(rhs))
+    }
+
+    /// `self` ∨ `rhs`.
+    pub fn or(self, rhs: Self) -> Self {
+        BoolExpr::Or(Box::new(self), Box::new(rhs))
+    }
+
+    /// `self` ⊕ `rhs`.
+    pub fn xor(self, rhs: Self) -> Self {
+        BoolExpr::Xor(Box::new(self), Box::new(rhs))
+    }
+
+    /// `self` → `rhs`.
+    pub fn implies(self, rhs: Self) -> Self {
+        BoolExpr::Implies(Box::new(self), Box::new(rhs))
+    }
+
+    /// `self` ↔ `rhs`.
+    pub fn iff(self, rhs: Self) -> Self {
+        BoolExpr::Iff(Box::new(self), Box::new(rhs))
+    }
+
+    /// The names of the variables in the formula, in alphabetical order.
+    pub fn variables(&self) -> Vec<String> {
+        fn collect<'a>(expr: &'a BoolExpr, names: &mut BTreeSet<&'a str>) {
+            match expr {
+                BoolExpr::Constant(_) => {}
+                BoolExpr::Variable(name) => {
+                    names.insert(name);
+                }
+                BoolExpr::Not(a) => collect(a, names),
+                BoolExpr::And(a, b)
+                | BoolExpr::Or(a, b)
+                | BoolExpr::Xor(a, b)
+                | BoolExpr::Implies(a, b)
+                | BoolExpr::Iff(a, b) => {
+                    collect(a, names);
+                    collect(b, names);
+                }
+            }
+        }
+        let mut names = BTreeSet::new();
+        collect(self, &mut names);
+        names.into_iter().map(str::to_string).collect()
+    }
+
+    /// Evaluates the formula in a [`HeytingAlgebra`], where `valuation` gives the value of each
+    /// variable.
+    ///
+    /// Negation is the pseudo-complement, → is [`HeytingAlgebra::implies`], *a* ↔ *b* is (*a* →
+    /// *b*) ∧ (*b* → *a*), and *a* ⊕ *b* is (*a* ∨ *b*) ∧ ¬(*a* ∧ *b*). In a
+    /// [`crate::latticelike::BooleanAlgebra`], these are the classical connectives.
+    pub fn evaluate<B, F>(&self, valuation: F) -> B
+    where
+        B: HeytingAlgebra + Clone,
+        F: Fn(&str) -> B,
+    {
+        self.evaluate_with(&valuation)
+    }
+
+    fn evaluate_with<B, F>(&self, valuation: &F) -> B
+    where
+        B: HeytingAlgebra + Clone,
+        F: Fn(&str) -> B,
+    {
+        match self {
+            BoolExpr::Constant(true) => B::TOP,
+            BoolExpr::Constant(false) => B::BOTTOM,
+            BoolExpr::Variable(name) => valuation(name),
+            BoolExpr::Not(a) => a.evaluate_with(valuation).pseudo_complement(),
+            BoolExpr::And(a, b) => a.evaluate_with(valuation).meet(b.evaluate_with(valuation)),
+            BoolExpr::Or(a, b) => a.evaluate_with(valuation).join(b.evaluate_with(valuation)),
+            BoolExpr::Xor(a, b) => {
+                let (a, b) = (a.evaluate_with(valuation), b.evaluate_with(valuation));
+                let both = a.meet(b.clone());
+                a.join(b).meet(both.pseudo_complement())
+            }
+            BoolExpr::Implies(a, b) => a
+                .evaluate_with(valuation)
+                .implies(b.evaluate_with(valuation)),
+            BoolExpr::Iff(a, b) => {
+                let (a, b) = (a.evaluate_with(valuation), b.evaluate_with(valuation));
+                a.implies(b.clone()).meet(b.implies(a))
+            }
+        }
+    }
+
+    /// The truth table over the variables of the formula, in alphabetical order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than 63 variables.
+    pub fn truth_table(&self) -> TruthTable {
+        self.truth_table_over(self.variables())
+    }
+
+    /// The truth table over the given variables, which must include every variable of the
+    /// formula. Listing extra variables is fine; the formula just won't depend on them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a variable of the formula is missing or if there are more than 63 variables.
+    pub fn truth_table_over(&self, variables: Vec<String>) -> TruthTable {
+        let n = variables.len();
+        assert!(n < 64, "truth tables are limited to 63 variables");
+        let index: HashMap<&str, usize> = variables
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (v.as_str(), n - 1 - i))
+            .collect();
+        let values = (0..1u64 << n)
+            .map(|row| {
+                self.evaluate(|v| {
+                    let bit = index.get(v).expect("every variable must be listed");
+                    row >> bit & 1 == 1
+                })
+            })
+            .collect();
+        TruthTable { variables, values }
+    }
+
+    fn table_with(&self, other: &BoolExpr) -> (TruthTable, TruthTable) {
+        let mut variables = self.variables();
+        variables.extend(other.variables());
+        variables.sort();
+        variables.dedup();
+        (
+            self.truth_table_over(variables.clone()),
+            other.truth_table_over(variables),
+        )
+    }
+
+    /// A valuation in `elements`, which should list a finite [`HeytingAlgebra`], under which the
+    /// formula isn't ⊤. Returns `None` if the formula is valid in the algebra.
+    ///
+    /// This tries all |`elements`|<sup>*k*</sup> valuations of the *k* variables.
+    ///
+    /// # Example
+    ///
+    /// The law of the excluded middle and double negation elimination are classically valid but
+    /// fail in the three-element chain. Their double negations hold in every Heyting algebra.
+    ///
+    /// ```rust
+    /// use yaaarc::latticelike::{expr::BoolExpr, heyting::Chain};
+    ///
+    /// let three = Chain::<3>::elements();
+    /// for formula in ["p | !p", "!!p -> p", "(p -> q) | (q -> p)", "!!(p | !p)"] {
+    ///     let formula: BoolExpr = formula.parse().unwrap();
+    ///     assert!(formula.is_tautology());
+    /// }
+    ///
+    /// let lem: BoolExpr = "p | !p".parse().unwrap();
+    /// let counterexample = lem.counterexample_in(&three).unwrap();
+    /// assert_eq!(counterexample, vec![(String::from("p"), Chain::new(1))]);
+    /// assert!(!"!!p -> p".parse::<BoolExpr>().unwrap().is_valid_in(&three));
+    /// // Every chain validates the linearity axiom of Gödel–Dummett logic.
+    /// assert!("(p -> q) | (q -> p)".parse::<BoolExpr>().unwrap().is_valid_in(&three));
+    /// assert!("!!(p | !p)".parse::<BoolExpr>().unwrap().is_valid_in(&three));
+    /// ```
+    pub fn counterexample_in<H>(&self, elements: &[H]) -> Option<Vec<(String, H)>>
+    where
+        H: HeytingAlgebra + Clone + PartialEq,
+    {
+        let variables = self.variables();
+        if elements.is_empty() {
+            return None;
+        }
+        // An odometer over the indices of the values of the variables.
+        let mut indices = vec![0; variables.len()];
+        loop {
+            let value = self.evaluate(|v| {
+                let i = variables.binary_search_by(|w| w.as_str().cmp(v)).unwrap();
+                elements[indices[i]].clone()
+            });
+            if value != H::TOP {
+                return Some(
+                    variables
+                        .into_iter()
+                        .zip(indices)
+                        .map(|(v, i)| (v, elements[i].clone()))
+                        .collect(),
+                );
+            }
+            let position = indices.iter().rposition(|&i| i + 1 < elements.len())?;
+            indices[position] += 1;
+            indices[position + 1..].fill(0);
+        }
+    }
+
+    /// Whether the formula evaluates to ⊤ under every valuation in `elements`, which should list a
+    /// finite [`HeytingAlgebra`]. See [`BoolExpr::counterexample_in`].
+    pub fn is_valid_in<H>(&self, elements: &[H]) -> bool
+    where
+        H: HeytingAlgebra + Clone + PartialEq,
+    {
+        self.counterexample_in(elements).is_none()
+    }
+
+    /// Whether the two formulas take the same value under every assignment of their variables.
+    pub fn is_equivalent(&self, other: &BoolExpr) -> bool {
+        let (a, b) = self.table_with(other);
+        a.values == b.values
+    }
+
+    /// Whether `other` is true under every assignment which makes `self` true.
+    pub fn implies_formula(&self, other: &BoolExpr) -> bool {
+        let (a, b) = self.table_with(other);
+        a.values.iter().zip(&b.values).all(|(&x, &y)| !x || y)
+    }
+
+    /// Whether the formula is true under every assignment.
+    pub fn is_tautology(&self) -> bool {
+        self.truth_table().values.iter().all(|&x| x)
+    }
+
+    /// Whether the formula is true under some assignment.
+    pub fn is_satisfiable(&self) -> bool {
+        self.truth_table().values.iter().any(|&x| x)
+    }
+
+    /// The negation normal form, where ¬ is only applied to variables and the only other
+    /// connectives are ∧ and ∨.
+    pub fn to_nnf(&self) -> BoolExpr {
+        self.nnf(false)
+    }
+
+    fn nnf(&self, negated: bool) -> BoolExpr {
+        let join = |a: BoolExpr, b: BoolExpr, and: bool| if and { a.and(b) } else { a.or(b) };
+        match self {
+            BoolExpr::Constant(c) => BoolExpr::Constant(c ^ negated),
+            BoolExpr::Variable(_) if negated => self.clone().not(),
+            BoolExpr::Variable(_) => self.clone(),
+            BoolExpr::Not(a) => a.nnf(!negated),
+            BoolExpr::And(a, b) => join(a.nnf(negated), b.nnf(negated), !negated),
+            BoolExpr::Or(a, b) => join(a.nnf(negated), b.nnf(negated), negated),
+            // a → b = ¬a ∨ b.
+            BoolExpr::Implies(a, b) => join(a.nnf(!negated), b.nnf(negated), negated),
+            // a ↔ b = (¬a ∨ b) ∧ (a ∨ ¬b), and ¬(a ↔ b) = (a ∨ b) ∧ (¬a ∨ ¬b).
+            BoolExpr::Iff(a, b) => {
+                let left = a.nnf(!negated).or(b.nnf(false));
+                let right = a.nnf(negated).or(b.nnf(true));
+                left.and(right)
+            }
+            // a ⊕ b = ¬(a ↔ b).
+            BoolExpr::Xor(a, b) => {
+                let left = a.nnf(negated).or(b.nnf(false));
+                let right = a.nnf(!negated).or(b.nnf(true));
+                left.and(right)
+            }
+        }
+    }
+
+    /// The terms of the disjunctive normal form (if `disjunctive`) or the clauses of the
+    /// conjunctive normal form (if not) of a formula in negation normal form.
+    fn normal_form(nnf: &BoolExpr, disjunctive: bool) -> Vec<Literals> {
+        // For a DNF, ∨ concatenates and ∧ distributes. For a CNF, it's the other way around.
+        let product = |a: Vec<Literals>, b: Vec<Literals>| -> Vec<Literals> {
+            let mut result = Vec::new();
+            for x in &a {
+                for y in &b {
+                    let mut merged = x.clone();
+                    let consistent = y
+                        .iter()
+                        .all(|(v, &sign)| *merged.entry(v.clone()).or_insert(sign) == sign);
+                    if consistent {
+                        result.push(merged);
+                    }
+                }
+            }
+            result
+        };
+        let parts = match nnf {
+            BoolExpr::Constant(c) if *c == disjunctive => vec![Literals::new()],
+            BoolExpr::Constant(_) => Vec::new(),
+            BoolExpr::Variable(v) => vec![Literals::from([(v.clone(), true)])],
+            BoolExpr::Not(a) => match a.as_ref() {
+                BoolExpr::Variable(v) => vec![Literals::from([(v.clone(), false)])],
+                _ => unreachable!("negation normal form only negates variables"),
+            },
+            BoolExpr::And(a, b) | BoolExpr::Or(a, b) => {
+                let a = BoolExpr::normal_form(a, disjunctive);
+                let b = BoolExpr::normal_form(b, disjunctive);
+                if matches!(nnf, BoolExpr::Or(..)) == disjunctive {
+                    a.into_iter().chain(b).collect()
+                } else {
+                    product(a, b)
+                }
+            }
+            _ => unreachable!("negation normal form only uses ∧ and ∨"),
+        };
+        // Absorption: drop anything which contains another part.
+        let mut parts: Vec<Literals> = parts
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        parts.sort_by_key(BTreeMap::len);
+        let mut kept: Vec<Literals> = Vec::new();
+        for part in parts {
+            let absorbed = kept
+                .iter()
+                .any(|k| k.iter().all(|(v, sign)| part.get(v) == Some(sign)));
+            if !absorbed {
+                kept.push(part);
+            }
+        }
+        kept
+    }
+
+    /// Builds an ∨ of ∧s (if `disjunctive`) or an ∧ of ∨s (if not) from the parts of a normal
+    /// form.
+    fn from_normal_form(parts: Vec<Literals>, disjunctive: bool) -> BoolExpr {
+        let (outer, inner) = if disjunctive {
+            (
+                BoolExpr::or as fn(_, _) -> _,
+                BoolExpr::and as fn(_, _) -> _,
+            )
+        } else {
+            (
+                BoolExpr::and as fn(_, _) -> _,
+                BoolExpr::or as fn(_, _) -> _,
+            )
+        };
+        parts
+            .into_iter()
+            .map(|part| {
+                part.into_iter()
+                    .map(|(v, sign)| {
+                        let literal = BoolExpr::Variable(v);
+                        if sign {
+                            literal
+                        } else {
+                            literal.not()
+                        }
+                    })
+                    .reduce(inner)
+                    .unwrap_or(BoolExpr::Constant(disjunctive))
+            })
+            .reduce(outer)
+            .unwrap_or(BoolExpr::Constant(!disjunctive))
+    }
+
+    /// A disjunctive normal form, found by distributing ∧ over ∨.
+    ///
+    /// Contradictory and absorbed terms are removed, but the result isn't necessarily minimal;
+    /// see [`BoolExpr::minimise`] for that. This can be exponentially larger than the formula.
+    pub fn to_dnf(&self) -> BoolExpr {
+        BoolExpr::from_normal_form(BoolExpr::normal_form(&self.to_nnf(), true), true)
+    }
+
+    /// A conjunctive normal form, found by distributing ∨ over ∧.
+    ///
+    /// Tautological and absorbed clauses are removed. This can be exponentially larger than the
+    /// formula.
+    pub fn to_cnf(&self) -> BoolExpr {
+        BoolExpr::from_normal_form(BoolExpr::normal_form(&self.to_nnf(), false), false)
+    }
+
+    /// The algebraic normal form, also known as the Zhegalkin polynomial.
+    ///
+    /// Every Boolean function can be written uniquely as a ⊕ of distinct products of variables (the
+    /// empty product being ⊤). The products are listed by degree, then alphabetically.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than 63 variables.
+    pub fn to_anf(&self) -> BoolExpr {
+        let table = self.truth_table();
+        let n = table.variables.len();
+        // The Möbius transform over 𝔽₂ turns the values into the coefficients.
+        let mut coefficients = table.values.clone();
+        for bit in 0..n {
+            for row in 0..coefficients.len() {
+                if row >> bit & 1 == 1 {
+                    coefficients[row] ^= coefficients[row ^ (1 << bit)];
+                }
+            }
+        }
+        let mut monomials: Vec<Vec<&String>> = (0..coefficients.len())
+            .filter(|&row| coefficients[row])
+            .map(|row| {
+                (0..n)
+                    .filter(|i| row >> (n - 1 - i) & 1 == 1)
+                    .map(|i| &table.variables[i])
+                    .collect()
+            })
+            .collect();
+        monomials.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        monomials
+            .into_iter()
+            .map(|monomial| {
+                monomial
+                    .into_iter()
+                    .map(|v| BoolExpr::Variable(v.clone()))
+                    .reduce(BoolExpr::and)
+                    .unwrap_or(BoolExpr::Constant(true))
+            })
+            .reduce(BoolExpr::xor)
+            .unwrap_or(BoolExpr::Constant(false))
+    }
+
+    /// A smallest equivalent formula in disjunctive normal form.
+    ///
+    /// "Smallest" means with the fewest terms, and then with the fewest literals. This is found
+    /// with [`TruthTable::minimal_cover`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than 63 variables.
+    pub fn minimise(&self) -> BoolExpr {
+        let table = self.truth_table();
+        table
+            .minimal_cover()
+            .iter()
+            .map(|implicant| implicant.to_expr(&table.variables))
+            .reduce(BoolExpr::or)
+            .unwrap_or(BoolExpr::Constant(false))
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            BoolExpr::Iff(..) => 0,
+            BoolExpr::Implies(..) => 1,
+            BoolExpr::Or(..) => 2,
+            BoolExpr::Xor(..) => 3,
+            BoolExpr::And(..) => 4,
+            BoolExpr::Not(..) => 5,
+            BoolExpr::Constant(_) | BoolExpr::Variable(_) => 6,
+        }
+    }
+}
+
+impl std::ops::Not for BoolExpr {
+    type Output = BoolExpr;
+
+    fn not(self) -> Self::Output {
+        BoolExpr::Not(Box::new(self))
+    }
+}
+
+impl std::ops::BitAnd for BoolExpr {
+    type Output = BoolExpr;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        BoolExpr::and(self, rhs)
+    }
+}
+
+impl std::ops::BitOr for BoolExpr {
+    type Output = BoolExpr;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        BoolExpr::or(self, rhs)
+    }
+}
+
+impl std::ops::BitXor for BoolExpr {
+    type Output = BoolExpr;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        BoolExpr::xor(self, rhs)
+    }
+}
+
+impl fmt::Display for BoolExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Parenthesise a child if it binds more loosely than its parent, or equally loosely on the
+        // side where the parser wouldn't put it.
+        let child = |f: &mut fmt::Formatter<'_>, expr: &BoolExpr, strict: bool| {
+            let needs_parentheses = expr.precedence() < self.precedence()
+                || (strict && expr.precedence() == self.precedence());
+            if needs_parentheses {
+                write!(f, "({expr})")
+            } else {
+                write!(f, "{expr}")
+            }
+        };
+        let binary = |f: &mut fmt::Formatter<'_>, a, b, symbol, right_associative| {
+            child(f, a, right_associative)?;
+            write!(f, " {symbol} ")?;
+            child(f, b, !right_associative)
+        };
+        match self {
+            BoolExpr::Constant(true) => write!(f, "⊤"),
+            BoolExpr::Constant(false) => write!(f, "⊥"),
+            BoolExpr::Variable(name) => write!(f, "{name}"),
+            BoolExpr::Not(a) => {
+                write!(f, "¬")?;
+                child(f, a, false)
+            }
+            BoolExpr::And(a, b) => binary(f, a, b, "∧", false),
+            BoolExpr::Or(a, b) => binary(f, a, b, "∨", false),
+            BoolExpr::Xor(a, b) => binary(f, a, b, "⊕", false),
+            BoolExpr::Implies(a, b) => binary(f, a, b, "→", true),
+            BoolExpr::Iff(a, b) => binary(f, a, b, "↔", false),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Constant(bool),
+    Identifier(String),
+    Not,
+    And,
+    Or,
+    Xor,
+    Implies,
+    Iff,
+    Open,
+    Close,
+}
+
+fn tokenise(input: &str) -> Result<Vec<(usize, Token)>, ParseBoolExprError> {
+    const SYMBOLS: [(&str, Token); 20] = [
+        ("<->", Token::Iff),
+        ("<=>", Token::Iff),
+        ("↔", Token::Iff),
+        ("->", Token::Implies),
+        ("=>", Token::Implies),
+        ("→", Token::Implies),
+        ("&&", Token::And),
+        ("&", Token::And),
+        ("∧", Token::And),
+        ("||", Token::Or),
+        ("|", Token::Or),
+        ("∨", Token::Or),
+        ("^", Token::Xor),
+        ("⊕", Token::Xor),
+        ("!", Token::Not),
+        ("~", Token::Not),
+        ("¬", Token::Not),
+        ("⊤", Token::Constant(true)),
+        ("⊥", Token::Constant(false)),
+        ("(", Token::Open),
+    ];
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while let Some(character) = input[position..].chars().next() {
+        let rest = &input[position..];
+        if character.is_whitespace() {
+            position += character.len_utf8();
+        } else if let Some((symbol, token)) = SYMBOLS.iter().find(|(s, _)| rest.starts_with(s)) {
+            tokens.push((position, token.clone()));
+            position += symbol.len();
+        } else if character == ')' {
+            tokens.push((position, Token::Close));
+            position += 1;
+        } else if character == '0' || character == '1' {
+            tokens.push((position, Token::Constant(character == '1')));
+            position += 1;
+        } else if character.is_alphabetic() || character == '_' {
+            let length = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let token = match &rest[..length] {
+                "true" => Token::Constant(true),
+                "false" => Token::Constant(false),
+                name => Token::Identifier(name.to_string()),
+            };
+            tokens.push((position, token));
+            position += length;
+        } else {
+            return Err(ParseBoolExprError::UnexpectedCharacter {
+                position,
+                character,
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+/// A recursive descent parser. From loosest to tightest, the connectives are ↔, → (which is right
+/// associative), ∨, ⊕, ∧ and ¬.
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, token)| token)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let matches = self.peek() == Some(token);
+        if matches {
+            self.next += 1;
+        }
+        matches
+    }
+
+    fn error(&self) -> ParseBoolExprError {
+        match self.tokens.get(self.next) {
+            Some(&(position, _)) => ParseBoolExprError::UnexpectedToken { position },
+            None => ParseBoolExprError::UnexpectedEnd,
+        }
+    }
+
+    fn left_associative(
+        &mut self,
+        operator: Token,
+        operand: fn(&mut Parser) -> Result<BoolExpr, ParseBoolExprError>,
+        combine: fn(BoolExpr, BoolExpr) -> BoolExpr,
+    ) -> Result<BoolExpr, ParseBoolExprError> {
+        let mut expr = operand(self)?;
+        while self.eat(&operator) {
+            expr = combine(expr, operand(self)?);
+        }
+        Ok(expr)
+    }
+
+    fn iff(&mut self) -> Result<BoolExpr, ParseBoolExprError> {
+        self.left_associative(Token::Iff, Parser::implies, BoolExpr::iff)
+    }
+
+    fn implies(&mut self) -> Result<BoolExpr, ParseBoolExprError> {
+        let premise = self.or()?;
+        if self.eat(&Token::Implies) {
+            Ok(premise.implies(self.implies()?))
+        } else {
+            Ok(premise)
+        }
+    }
+
+    fn or(&mut self) -> Result<BoolExpr, ParseBoolExprError> {
+        self.left_associative(Token::Or, Parser::xor, BoolExpr::or)
+    }
+
+    fn xor(&mut self) -> Result<BoolExpr, ParseBoolExprError> {
+        self.left_associative(Token::Xor, Parser::and, BoolExpr::xor)
+    }
+
+    fn and(&mut self) -> Result<BoolExpr, ParseBoolExprError> {
+        self.left_associative(Token::And, Parser::unary, BoolExpr::and)
+    }
+
+    fn unary(&mut self) -> Result<BoolExpr, ParseBoolExprError> {
+        if self.eat(&Token::Not) {
+            return Ok(self.unary()?.not());
+        }
+        let expr = match self.peek() {
+            Some(Token::Constant(c)) => BoolExpr::Constant(*c),
+            Some(Token::Identifier(name)) => BoolExpr::Variable(name.clone()),
+            Some(Token::Open) => {
+                self.next += 1;
+                let expr = self.iff()?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(self.error());
+                }
+                expr
+            }
+            _ => return Err(self.error()),
+        };
+        self.next += 1;
+        Ok(expr)
+    }
+}
+
+impl FromStr for BoolExpr {
+    type Err = ParseBoolExprError;
+
+    /// Parses a formula.
+    ///
+    /// Variables are identifiers such as `x` or `is_valid2`. The constants are `1`, `true` and `⊤`,
+    /// and `0`, `false` and `⊥`. The connectives can be written in ASCII or Unicode:
+    ///
+    /// * ¬: `!`, `~` or `¬`.
+    /// * ∧: `&`, `&&` or `∧`.
+    /// * ∨: `|`, `||` or `∨`.
+    /// * ⊕: `^` or `⊕`.
+    /// * →: `->`, `=>` or `→`.
+    /// * ↔: `<->`, `<=>` or `↔`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenise(s)?,
+            next: 0,
+        };
+        let expr = parser.iff()?;
+        if parser.next < parser.tokens.len() {
+            return Err(parser.error());
+        }
+        Ok(expr)
+    }
+}
+
+impl TruthTable {
+    /// The variables, from the most significant bit of the row number to the least.
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
+    /// The value of the formula in each row.
+    pub fn values(&self) -> &[bool] {
+        &self.values
+    }
+
+    /// The rows where the formula is true.
+    pub fn minterms(&self) -> Vec<u64> {
+        (0..self.values.len() as u64)
+            .filter(|&row| self.values[row as usize])
+            .collect()
+    }
+
+    /// The rows where the formula is false.
+    pub fn maxterms(&self) -> Vec<u64> {
+        (0..self.values.len() as u64)
+            .filter(|&row| !self.values[row as usize])
+            .collect()
+    }
+
+    /// The prime implicants, found with the Quine–McCluskey algorithm.
+    ///
+    /// An *implicant* is a product of literals which implies the formula, and it's *prime* if
+    /// removing any literal makes it stop being an implicant. We start from the minterms, and
+    /// repeatedly merge pairs of implicants which differ in exactly one literal. The implicants
+    /// which never get merged are the prime ones.
+    pub fn prime_implicants(&self) -> Vec<Implicant> {
+        let mut current: BTreeSet<Implicant> = self
+            .minterms()
+            .into_iter()
+            .map(|value| Implicant { value, mask: 0 })
+            .collect();
+        let mut primes = BTreeSet::new();
+        while !current.is_empty() {
+            let mut merged = BTreeSet::new();
+            let mut used = BTreeSet::new();
+            // Only implicants with the same mask whose values differ in one bit can merge.
+            let mut by_mask: BTreeMap<u64, Vec<Implicant>> = BTreeMap::new();
+            for &implicant in &current {
+                by_mask.entry(implicant.mask).or_default().push(implicant);
+            }
+            for group in by_mask.values() {
+                for (i, a) in group.iter().enumerate() {
+                    for b in &group[i + 1..] {
+                        let difference = a.value ^ b.value;
+                        if difference.count_ones() == 1 {
+                            merged.insert(Implicant {
+                                value: a.value & !difference,
+                                mask: a.mask | difference,
+                            });
+                            used.insert(*a);
+                            used.insert(*b);
+                        }
+                    }
+                }
+            }
+            primes.extend(current.difference(&used).copied());
+            current = merged;
+        }
+        primes.into_iter().collect()
+    }
+
+    /// A smallest set of prime implicants covering every minterm.
+    ///
+    /// First we take the *essential* prime implicants, the ones which are the only cover of some
+    /// minterm. The rest of the minterms are covered with Petrick's method: writing *Pᵢ* for "the
+    /// *i*-th prime implicant is chosen", each remaining minterm gives a clause ∨ *Pᵢ* over the
+    /// implicants covering it. Multiplying out the ∧ of these clauses gives every possible cover,
+    /// and we pick one with the fewest implicants, then the fewest literals.
+    pub fn minimal_cover(&self) -> Vec<Implicant> {
+        let n = self.variables.len();
+        let primes = self.prime_implicants();
+        let minterms = self.minterms();
+        let mut chosen: BTreeSet<usize> = BTreeSet::new();
+        for &minterm in &minterms {
+            let covering: Vec<usize> = (0..primes.len())
+                .filter(|&i| primes[i].covers(minterm))
+                .collect();
+            if let [only] = covering[..] {
+                chosen.insert(only);
+            }
+        }
+        let remaining: Vec<u64> = minterms
+            .into_iter()
+            .filter(|&m| !chosen.iter().any(|&i| primes[i].covers(m)))
+            .collect();
+
+        let cost = |products: &BTreeSet<usize>| {
+            let literals: u32 = products.iter().map(|&i| primes[i].literal_count(n)).sum();
+            (products.len(), literals)
+        };
+        let mut products: Vec<BTreeSet<usize>> = vec![BTreeSet::new()];
+        for &minterm in &remaining {
+            let clause: Vec<usize> = (0..primes.len())
+                .filter(|&i| primes[i].covers(minterm))
+                .collect();
+            let mut expanded: BTreeSet<BTreeSet<usize>> = BTreeSet::new();
+            for product in &products {
+                if clause.iter().any(|i| product.contains(i)) {
+                    expanded.insert(product.clone());
+                    continue;
+                }
+                for &i in &clause {
+                    let mut product = product.clone();
+                    product.insert(i);
+                    expanded.insert(product);
+                }
+            }
+            // Absorption, X ∨ XY = X, keeps the expansion from blowing up.
+            let mut expanded: Vec<BTreeSet<usize>> = expanded.into_iter().collect();
+            expanded.sort_by_key(BTreeSet::len);
+            products = Vec::new();
+            for product in expanded {
+                if !products.iter().any(|p| p.is_subset(&product)) {
+                    products.push(product);
+                }
+            }
+        }
+        let best = products
+            .into_iter()
+            .min_by_key(|p| cost(p))
+            .unwrap_or_default();
+        chosen.extend(best);
+        chosen.into_iter().map(|i| primes[i]).collect()
+    }
+}
+
+impl fmt::Display for TruthTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.variables.len();
+        writeln!(f, "{} |", self.variables.join(" "))?;
+        for (row, &value) in self.values.iter().enumerate() {
+            let bits: Vec<String> = (0..n)
+                .map(|i| {
+                    let bit = (row >> (n - 1 - i)) & 1;
+                    format!("{bit:>width$}", width = self.variables[i].chars().count())
+                })
+                .collect();
+            writeln!(f, "{} | {}", bits.join(" "), u8::from(value))?;
+        }
+        Ok(())
+    }
+}
+
+impl Implicant {
+    /// Whether the minterm (a row of the truth table) satisfies this product.
+    pub fn covers(&self, minterm: u64) -> bool {
+        minterm & !self.mask == self.value
+    }
+
+    /// The literals of the product as pairs of a variable index and its sign, for a formula with
+    /// `n` variables.
+    pub fn literals(&self, n: usize) -> Vec<(usize, bool)> {
+        (0..n)
+            .filter(|i| self.mask >> (n - 1 - i) & 1 == 0)
+            .map(|i| (i, self.value >> (n - 1 - i) & 1 == 1))
+            .collect()
+    }
+
+    fn literal_count(&self, n: usize) -> u32 {
+        n as u32 - self.mask.count_ones()
+    }
+
+    /// The product as a formula in the given variables.
+    pub fn to_expr(&self, variables: &[String]) -> BoolExpr {
+        self.literals(variables.len())
+            .into_iter()
+            .map(|(i, sign)| {
+                let literal = BoolExpr::Variable(variables[i].clone());
+                if sign {
+                    literal
+                } else {
+                    literal.not()
+                }
+            })
+            .reduce(BoolExpr::and)
+            .unwrap_or(BoolExpr::Constant(true))
+    }
+}
@@ -0,0 +1,634 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Galois connections, closure operators and formal concept analysis.
+//!
+//! A (monotone) *Galois connection* between lattices *A* and *B* is a pair of monotone maps, the
+//! *lower adjoint* *f*: *A* → *B* and the *upper adjoint* *g*: *B* → *A*, such that for all *a* ∈
+//! *A* and *b* ∈ *B*,
+//!
+//! *f*(*a*) ≤ *b* iff *a* ≤ *g*(*b*).
+//!
+//! Then *g* ∘ *f* is a *closure operator* on *A* (monotone, idempotent, and *a* ≤ *g*(*f*(*a*))),
+//! and *f* ∘ *g* is a *kernel operator* on *B* (monotone, idempotent, and *f*(*g*(*b*)) ≤ *b*).
+//! The closed elements of *A* and the open elements of *B* form isomorphic lattices.
+//!
+//! Many classical correspondences are *antitone*, reversing the order instead. These are
+//! monotone Galois connections into the [`Dual`] of a lattice, where both composites become
+//! closure operators. The examples here are:
+//!
+//! * a [`FormalContext`], whose closed sets are the extents and intents of its formal concepts,
+//! * the [`IdealVariety`] correspondence between ideals of 𝔽*_q*\[*x*₁, …, *xₙ*\] and sets of
+//!   points of 𝔽*_q*ⁿ, and
+//! * the [`SubgroupFixedField`] correspondence between subgroups of the Galois group of a finite
+//!   field and its subfields.
+
+use std::{fmt, marker::PhantomData};
+
+use crate::{
+    grouplike::{Band, CommutativeMagma, Magma, Semigroup, Semilattice, UnitalMagma},
+    latticelike::{
+        boolean::BitSet, poset::FiniteLattice, poset::Poset, BooleanAlgebra, BoundedLattice,
+        ComplementedLattice, DistributiveLattice, HeytingAlgebra, JoinSemilattice, Lattice,
+        MeetSemilattice, ModularLattice,
+    },
+    operators::{BinaryOperator, Join, Meet},
+    ringlike::{
+        finite_field::FiniteField,
+        ideal::Ideal,
+        multivariate::{MPoly, MonomialOrder},
+        Ring,
+    },
+};
+
+/// A monotone Galois connection between the lattices *A* and *B*.
+///
+/// # Safety
+///
+/// The adjunction *f*(*a*) ≤ *b* iff *a* ≤ *g*(*b*) is not guaranteed at the type level! See the
+/// note at the beginning of [`crate::latticelike`] for more details.
+pub trait GaloisConnection<A: Lattice, B: Lattice> {
+    /// The lower (left) adjoint *f*: *A* → *B*, which preserves joins.
+    fn lower(&self, a: &A) -> B;
+
+    /// The upper (right) adjoint *g*: *B* → *A*, which preserves meets.
+    fn upper(&self, b: &B) -> A;
+
+    /// The closure operator *g* ∘ *f* on *A*.
+    fn closure(&self, a: &A) -> A {
+        self.upper(&self.lower(a))
+    }
+
+    /// The kernel operator *f* ∘ *g* on *B*.
+    fn kernel(&self, b: &B) -> B {
+        self.lower(&self.upper(b))
+    }
+
+    /// Whether *a* is a fixed point of the closure operator.
+    fn is_closed(&self, a: &A) -> bool
+    where
+        A: PartialEq,
+    {
+        self.closure(a) == *a
+    }
+
+    /// Whether *b* is a fixed point of the kernel operator.
+    fn is_open(&self, b: &B) -> bool
+    where
+        B: PartialEq,
+    {
+        self.kernel(b) == *b
+    }
+
+    /// The lattice of closed elements of *A*, given a list of all the elements of *A*.
+    ///
+    /// Returns the lattice and the closed elements, in the same order as the lattice's indices. The
+    /// meet of closed elements is their meet in *A*, but their join is the closure of their join
+    /// in *A*.
+    fn closed_lattice(&self, elements: &[A]) -> (FiniteLattice, Vec<A>)
+    where
+        A: Clone + PartialEq,
+    {
+        let mut closed: Vec<A> = Vec::new();
+        for a in elements {
+            let c = self.closure(a);
+            if !closed.contains(&c) {
+                closed.push(c);
+            }
+        }
+        let lattice = Poset::from_fn(closed.len(), |i, j| closed[i].leq(&closed[j]))
+            .expect("the order of a lattice is antisymmetric")
+            .to_lattice()
+            .expect("the closed elements of a closure operator form a lattice");
+        (lattice, closed)
+    }
+}
+
+/// The order dual of a lattice, with join and meet swapped.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::latticelike::{galois::Dual, BoundedLattice, JoinSemilattice, Lattice};
+///
+/// assert_eq!(Dual(0b1100u8).join(Dual(0b1010)), Dual(0b1000));
+/// assert!(Dual(0b1111u8).leq(&Dual(0b0101)));
+/// assert_eq!(Dual::<u8>::TOP, Dual(0));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Dual<L>(pub L);
+
+impl<L> Dual<L> {
+    /// Unwraps the element of the original lattice.
+    pub fn into_inner(self) -> L {
+        self.0
+    }
+}
+
+impl<L: Lattice> BinaryOperator<Join> for Dual<L> {
+    fn op(&self, rhs: Self) -> Self {
+        Dual(self.0.meet(rhs.0))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        <L as BinaryOperator<Meet>>::op_assign(&mut self.0, rhs.0);
+    }
+}
+
+impl<L: Lattice> BinaryOperator<Meet> for Dual<L> {
+    fn op(&self, rhs: Self) -> Self {
+        Dual(self.0.join(rhs.0))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        <L as BinaryOperator<Join>>::op_assign(&mut self.0, rhs.0);
+    }
+}
+
+impl<L: Lattice> Magma<Join> for Dual<L> {}
+impl<L: Lattice> Semigroup<Join> for Dual<L> {}
+impl<L: Lattice> Band<Join> for Dual<L> {}
+impl<L: Lattice> CommutativeMagma<Join> for Dual<L> {}
+impl<L: Lattice> Semilattice<Join> for Dual<L> {}
+impl<L: Lattice> JoinSemilattice for Dual<L> {}
+
+impl<L: Lattice> Magma<Meet> for Dual<L> {}
+impl<L: Lattice> Semigroup<Meet> for Dual<L> {}
+impl<L: Lattice> Band<Meet> for Dual<L> {}
+impl<L: Lattice> CommutativeMagma<Meet> for Dual<L> {}
+impl<L: Lattice> Semilattice<Meet> for Dual<L> {}
+impl<L: Lattice> MeetSemilattice for Dual<L> {}
+
+impl<L: Lattice> Lattice for Dual<L> {}
+
+impl<L: BoundedLattice> UnitalMagma<Join> for Dual<L> {
+    const IDENTITY: Self = Dual(L::TOP);
+}
+impl<L: BoundedLattice> UnitalMagma<Meet> for Dual<L> {
+    const IDENTITY: Self = Dual(L::BOTTOM);
+}
+
+impl<L: BoundedLattice> BoundedLattice for Dual<L> {
+    const BOTTOM: Self = Dual(L::TOP);
+    const TOP: Self = Dual(L::BOTTOM);
+}
+
+/// The modular law is self-dual.
+impl<L: ModularLattice> ModularLattice for Dual<L> {}
+
+/// Distributivity is self-dual.
+impl<L: DistributiveLattice> DistributiveLattice for Dual<L> {}
+
+impl<L: ComplementedLattice> ComplementedLattice for Dual<L> {
+    fn complement(&self) -> Self {
+        Dual(self.0.complement())
+    }
+}
+
+/// The dual of a Heyting algebra is a co-Heyting algebra, which needn't be Heyting. But the dual of
+/// a Boolean algebra is Boolean, with *x* → *y* = *y* ∧ ¬*x* in the original order.
+impl<L: BooleanAlgebra + Clone> HeytingAlgebra for Dual<L> {
+    fn implies(&self, rhs: Self) -> Self {
+        Dual(rhs.0.meet(self.0.complement()))
+    }
+}
+
+impl<L: BooleanAlgebra + Clone> BooleanAlgebra for Dual<L> {}
+
+/// A formal concept of a [`FormalContext`].
+///
+/// Its *extent* is a set of objects and its *intent* is a set of attributes, where the intent is
+/// exactly the attributes shared by all the objects of the extent, and vice versa.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Concept {
+    pub extent: BitSet,
+    pub intent: BitSet,
+}
+
+/// A formal context: a set of objects, a set of attributes, and which objects have which
+/// attributes.
+///
+/// Sending a set of objects to its shared attributes, and a set of attributes to the objects
+/// having all of them, is an antitone Galois connection. So a context is a [`GaloisConnection`]
+/// from sets of objects to the [`Dual`] of sets of attributes. Its closed pairs are the formal
+/// concepts, which form the *concept lattice*.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::latticelike::galois::FormalContext;
+///
+/// let animals = ["dog", "cat", "duck", "trout"];
+/// let traits = ["mammal", "flies", "swims", "has legs"];
+/// let has = [
+///     [true, false, true, true],
+///     [true, false, false, true],
+///     [false, true, true, true],
+///     [false, false, true, false],
+/// ];
+/// let context = FormalContext::from_fn(
+///     animals.map(String::from).to_vec(),
+///     traits.map(String::from).to_vec(),
+///     |object, attribute| has[object][attribute],
+/// );
+///
+/// let concepts = context.concepts();
+/// assert_eq!(concepts.len(), 8);
+/// // Everything which has legs and swims.
+/// let dog_and_duck = context.concept_of_attributes([2, 3]);
+/// assert_eq!(dog_and_duck.extent.iter().collect::<Vec<_>>(), [0, 2]);
+///
+/// let (lattice, _) = context.concept_lattice();
+/// assert_eq!(lattice.len(), 8);
+/// let top = lattice.poset().top().unwrap();
+/// assert_eq!(lattice.poset().labels()[top], "({dog, cat, duck, trout}, {})");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormalContext {
+    objects: Vec<String>,
+    attributes: Vec<String>,
+    /// The attributes of each object.
+    incidence: Vec<BitSet>,
+}
+
+impl FormalContext {
+    /// Creates the context where object *g* has attribute *m* iff `has(g, m)`.
+    pub fn from_fn(
+        objects: Vec<String>,
+        attributes: Vec<String>,
+        has: impl Fn(usize, usize) -> bool,
+    ) -> Self {
+        let incidence = (0..objects.len())
+            .map(|g| (0..attributes.len()).filter(|&m| has(g, m)).collect())
+            .collect();
+        FormalContext {
+            objects,
+            attributes,
+            incidence,
+        }
+    }
+
+    /// The names of the objects.
+    pub fn objects(&self) -> &[String] {
+        &self.objects
+    }
+
+    /// The names of the attributes.
+    pub fn attributes(&self) -> &[String] {
+        &self.attributes
+    }
+
+    /// Whether object *g* has attribute *m*.
+    pub fn has(&self, g: usize, m: usize) -> bool {
+        self.incidence[g].contains(m)
+    }
+
+    /// The attributes shared by every object in `objects`.
+    pub fn intent(&self, objects: &BitSet) -> BitSet {
+        (0..self.objects.len())
+            .filter(|&g| objects.contains(g))
+            .fold((0..self.attributes.len()).collect(), |shared: BitSet, g| {
+                shared.meet(self.incidence[g].clone())
+            })
+    }
+
+    /// The objects having every attribute in `attributes`.
+    pub fn extent(&self, attributes: &BitSet) -> BitSet {
+        (0..self.objects.len())
+            .filter(|&g| {
+                (0..self.attributes.len()).all(|m| !attributes.contains(m) || self.has(g, m))
+            })
+            .collect()
+    }
+
+    /// The smallest concept whose extent contains the given objects.
+    pub fn concept_of_objects(&self, objects: impl IntoIterator<Item = usize>) -> Concept {
+        let intent = self.intent(&objects.into_iter().collect());
+        Concept {
+            extent: self.extent(&intent),
+            intent,
+        }
+    }
+
+    /// The largest concept whose intent contains the given attributes.
+    pub fn concept_of_attributes(&self, attributes: impl IntoIterator<Item = usize>) -> Concept {
+        let extent = self.extent(&attributes.into_iter().collect());
+        Concept {
+            intent: self.intent(&extent),
+            extent,
+        }
+    }
+
+    /// Every formal concept, with the intents in lectic order.
+    ///
+    /// We use Ganter's NextClosure algorithm, which visits the closed sets of attributes in lectic
+    /// order: the order of their characteristic vectors as binary numbers, with attribute 0 as the
+    /// most significant bit. Each closed set is found from the previous one with at most one
+    /// closure per attribute, and without storing the ones found so far.
+    pub fn concepts(&self) -> Vec<Concept> {
+        let m = self.attributes.len();
+        let close = |attributes: &BitSet| self.intent(&self.extent(attributes));
+        let mut intent = close(&BitSet::new());
+        let mut concepts = Vec::new();
+        loop {
+            concepts.push(Concept {
+                extent: self.extent(&intent),
+                intent: intent.clone(),
+            });
+            let next = (0..m).rev().filter(|&i| !intent.contains(i)).find_map(|i| {
+                let mut candidate: BitSet = intent.iter().filter(|&a| a < i).collect();
+                candidate.insert(i);
+                let closed = close(&candidate);
+                // The closure mustn't add anything smaller than i.
+                let unchanged = (0..i).all(|a| closed.contains(a) == intent.contains(a));
+                unchanged.then_some(closed)
+            });
+            match next {
+                Some(next) => intent = next,
+                None => return concepts,
+            }
+        }
+    }
+
+    /// The concept lattice, and the concepts in the same order as the lattice's indices.
+    ///
+    /// Concepts are ordered by inclusion of their extents (or equivalently, reverse inclusion of
+    /// their intents), and are labelled by their extent and intent.
+    pub fn concept_lattice(&self) -> (FiniteLattice, Vec<Concept>) {
+        let concepts = self.concepts();
+        let names = |set: &BitSet, names: &[String]| {
+            let names: Vec<&str> = set.iter().map(|i| names[i].as_str()).collect();
+            format!("{{{}}}", names.join(", "))
+        };
+        let labels = concepts
+            .iter()
+            .map(|c| {
+                format!(
+                    "({}, {})",
+                    names(&c.extent, &self.objects),
+                    names(&c.intent, &self.attributes)
+                )
+            })
+            .collect();
+        let lattice = Poset::from_fn(concepts.len(), |i, j| {
+            concepts[i].extent.leq(&concepts[j].extent)
+        })
+        .and_then(|poset| poset.with_labels(labels))
+        .expect("inclusion of extents is a partial order")
+        .to_lattice()
+        .expect("the concepts of a context form a lattice");
+        (lattice, concepts)
+    }
+}
+
+impl GaloisConnection<BitSet, Dual<BitSet>> for FormalContext {
+    fn lower(&self, objects: &BitSet) -> Dual<BitSet> {
+        Dual(self.intent(objects))
+    }
+
+    fn upper(&self, attributes: &Dual<BitSet>) -> BitSet {
+        self.extent(&attributes.0)
+    }
+}
+
+/// The antitone Galois connection between the ideals of 𝔽*_q*\[*x*₁, …, *xₙ*\] and the sets of
+/// points of 𝔽*_q*ⁿ, which takes an ideal *I* to its variety *V*(*I*) of common zeros, and a set of
+/// points *S* to the ideal *I*(*S*) of the polynomials vanishing on all of them.
+///
+/// The points are numbered as in [`IdealVariety::point`]. Since 𝔽*_q*ⁿ is finite, every set of
+/// points is a variety, so every set is open. By the Nullstellensatz over a finite field, the
+/// closure of an ideal is *I* + (*x*₁^*q* - *x*₁, …, *xₙ*^*q* - *xₙ*).
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::{
+///     latticelike::{boolean::BitSet, galois::{GaloisConnection, IdealVariety}},
+///     ringlike::{finite_field::PrimeField, ideal::Ideal, multivariate::{GRevLex, MPoly}},
+/// };
+///
+/// type F = PrimeField<3>;
+/// type P = MPoly<F, GRevLex>;
+///
+/// let connection = IdealVariety::<F, GRevLex>::new(2);
+/// let (x, y) = (P::var(0), P::var(1));
+/// // x² = 0 is the line x = 0, which is the points (0, 0), (0, 1) and (0, 2).
+/// let ideal = Ideal::new(2, &[&x * &x]);
+/// let line: BitSet = [0, 3, 6].into_iter().collect();
+/// assert_eq!(connection.lower(&ideal).0, line);
+/// assert_eq!(connection.closure(&ideal), Ideal::new(2, &[x, &y.pow(3) - &y]));
+/// ```
+pub struct IdealVariety<F, O> {
+    variables: usize,
+    ring: PhantomData<MPoly<F, O>>,
+}
+
+impl<F: FiniteField, O: MonomialOrder> IdealVariety<F, O> {
+    /// The correspondence for polynomials in `variables` variables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`usize::MAX`] points.
+    pub fn new(variables: usize) -> Self {
+        let points = u32::try_from(variables)
+            .ok()
+            .and_then(|n| F::ORDER.checked_pow(n))
+            .and_then(|count| usize::try_from(count).ok());
+        assert!(points.is_some(), "there are too many points to number");
+        IdealVariety {
+            variables,
+            ring: PhantomData,
+        }
+    }
+
+    /// The number of variables *n*.
+    pub fn variables(&self) -> usize {
+        self.variables
+    }
+
+    /// The number of points, *qⁿ*.
+    pub fn point_count(&self) -> usize {
+        (0..self.variables).fold(1, |count, _| count * F::ORDER as usize)
+    }
+
+    /// The point numbered `index`, whose coordinates have the [`FiniteField::index`]es given by
+    /// the digits of `index` in base *q*, with *x*₁ the least significant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is at least [`IdealVariety::point_count`].
+    pub fn point(&self, index: usize) -> Vec<F> {
+        assert!(index < self.point_count(), "there's no point {index}");
+        let q = F::ORDER as usize;
+        (0..self.variables)
+            .scan(index, |rest, _| {
+                let digit = *rest % q;
+                *rest /= q;
+                Some(F::from_index(digit as u128))
+            })
+            .collect()
+    }
+
+    /// The number of `point`, the inverse of [`IdealVariety::point`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `point` doesn't have *n* coordinates.
+    pub fn index(&self, point: &[F]) -> usize {
+        assert_eq!(
+            point.len(),
+            self.variables,
+            "the point has the wrong dimension"
+        );
+        point
+            .iter()
+            .rev()
+            .fold(0, |index, a| index * F::ORDER as usize + a.index() as usize)
+    }
+}
+
+impl<F: FiniteField, O: MonomialOrder> GaloisConnection<Ideal<F, O>, Dual<BitSet>>
+    for IdealVariety<F, O>
+{
+    fn lower(&self, ideal: &Ideal<F, O>) -> Dual<BitSet> {
+        Dual(
+            (0..self.point_count())
+                .filter(|&i| {
+                    let point = self.point(i);
+                    ideal.basis().iter().all(|g| g.evaluate(&point) == F::ZERO)
+                })
+                .collect(),
+        )
+    }
+
+    /// The intersection of the maximal ideals (*x*₁ - *a*₁, …, *xₙ* - *aₙ*) of the points *a*.
+    fn upper(&self, points: &Dual<BitSet>) -> Ideal<F, O> {
+        let whole = Ideal::new(self.variables, &[MPoly::ONE]);
+        (0..self.point_count())
+            .filter(|&i| points.0.contains(i))
+            .fold(whole, |ideal, i| {
+                let point = self.point(i);
+                let generators: Vec<MPoly<F, O>> = point
+                    .iter()
+                    .enumerate()
+                    .map(|(k, &a)| &MPoly::var(k) - &MPoly::constant(a))
+                    .collect();
+                ideal.intersect(&Ideal::new(self.variables, &generators))
+            })
+    }
+}
+
+/// The antitone Galois connection between the sets of automorphisms of a finite field 𝔽*_q* and
+/// its subsets, which takes automorphisms to the elements they all fix, and elements to the
+/// automorphisms fixing all of them.
+///
+/// If *q* = *pⁿ*, the automorphisms form a cyclic group of order *n*, generated by the Frobenius
+/// map *φ*(*a*) = *aᵖ*, and *φᵏ* is numbered *k*. Elements are numbered by their
+/// [`FiniteField::index`]. The closed sets are exactly the subgroups ⟨*φᵈ*⟩ for *d* | *n*, and the
+/// open sets are their fixed fields, the subfields 𝔽*_p*^*d*, as in the fundamental theorem of
+/// Galois theory.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::{
+///     latticelike::{boolean::BitSet, galois::{GaloisConnection, SubgroupFixedField}},
+///     ringlike::finite_field::GaloisField,
+/// };
+///
+/// let connection = SubgroupFixedField::<GaloisField<2, 4>>::new();
+/// assert_eq!(connection.degree(), 4);
+/// // φ² fixes 𝔽₄, and so does the subgroup {1, φ²} it generates.
+/// let squared: BitSet = [2].into_iter().collect();
+/// let fixed = connection.lower(&squared);
+/// assert_eq!(fixed.0.len(), Some(4));
+/// assert_eq!(connection.closure(&squared), [0, 2].into_iter().collect());
+/// // φ generates the whole group, which only fixes 𝔽₂.
+/// let frobenius: BitSet = [1].into_iter().collect();
+/// assert_eq!(connection.lower(&frobenius).0, [0, 1].into_iter().collect());
+/// ```
+pub struct SubgroupFixedField<F>(PhantomData<F>);
+
+impl<F: FiniteField> SubgroupFixedField<F> {
+    /// The correspondence for 𝔽*_q* = `F`.
+    pub fn new() -> Self {
+        SubgroupFixedField(PhantomData)
+    }
+
+    /// The degree *n* of 𝔽*_q* over its prime field, which is the order of the Galois group.
+    pub fn degree(&self) -> usize {
+        let p = u128::from(F::CHARACTERISTIC);
+        let mut degree = 1;
+        let mut power = p;
+        while power < F::ORDER {
+            power *= p;
+            degree += 1;
+        }
+        degree
+    }
+
+    /// *φᵏ*(*a*) = *a*^(*pᵏ*).
+    pub fn automorphism(&self, k: usize, a: F) -> F {
+        (0..k).fold(a, |b, _| b.power(u128::from(F::CHARACTERISTIC)))
+    }
+}
+
+impl<F: FiniteField> Default for SubgroupFixedField<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: FiniteField> GaloisConnection<BitSet, Dual<BitSet>> for SubgroupFixedField<F> {
+    fn lower(&self, automorphisms: &BitSet) -> Dual<BitSet> {
+        let group: Vec<usize> = (0..self.degree())
+            .filter(|&k| automorphisms.contains(k))
+            .collect();
+        Dual(
+            F::elements()
+                .filter(|&a| group.iter().all(|&k| self.automorphism(k, a) == a))
+                .map(|a| a.index() as usize)
+                .collect(),
+        )
+    }
+
+    fn upper(&self, elements: &Dual<BitSet>) -> BitSet {
+        let elements: Vec<F> = F::elements()
+            .filter(|a| elements.0.contains(a.index() as usize))
+            .collect();
+        (0..self.degree())
+            .filter(|&k| elements.iter().all(|&a| self.automorphism(k, a) == a))
+            .collect()
+    }
+}
+
+impl fmt::Display for FormalContext {
+    /// Prints the context as a cross table.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .objects
+            .iter()
+            .map(|g| g.chars().count())
+            .max()
+            .unwrap_or(0);
+        writeln!(f, "{:width$} | {}", "", self.attributes.join(" | "))?;
+        for (g, object) in self.objects.iter().enumerate() {
+            write!(f, "{object:width$}")?;
+            for (m, attribute) in self.attributes.iter().enumerate() {
+                let cross = if self.has(g, m) { "×" } else { "" };
+                write!(f, " | {cross:^w$}", w = attribute.chars().count())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
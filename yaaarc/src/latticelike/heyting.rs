@@ -0,0 +1,618 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Concrete Heyting algebras.
+//!
+//! Heyting algebras are to intuitionistic logic what Boolean algebras are to classical logic: an
+//! intuitionistic formula is provable iff it evaluates to ⊤ in every Heyting algebra under every
+//! valuation. In fact, it's enough to check every *finite* Heyting algebra, so the ones here are
+//! good for finding counterexamples with [`crate::latticelike::expr::BoolExpr::counterexample_in`].
+//!
+//! We provide:
+//!
+//! * [`Chain`], the totally ordered set 0 < 1 < … < *N* - 1. With *N* = 2, this is the Boolean
+//!   algebra of truth values, and with *N* > 2, it already refutes the law of the excluded middle.
+//! * [`OpenSet`], the open sets of a [`FiniteTopology`]. Implication is *U* → *V* = int(*U*ᶜ ∪
+//!   *V*), where int is the interior.
+//! * [`DownSet`], the down-sets of a finite [`Poset`], as created by [`DownSets`]. These are the
+//!   open sets of the Alexandrov topology, and correspond to Kripke models of intuitionistic logic
+//!   whose frame is the dual poset.
+//!
+//! Every finite distributive lattice is a Heyting algebra, so [`DownSets`] covers all of them up to
+//! isomorphism, by Birkhoff's representation theorem.
+
+use std::{error::Error, fmt, rc::Rc};
+
+use crate::{
+    grouplike::{Band, CommutativeMagma, Magma, Semigroup, Semilattice, UnitalMagma},
+    latticelike::{
+        boolean::{distributive_lattice_markers, BitSet},
+        poset::Poset,
+        BooleanAlgebra, BoundedLattice, ComplementedLattice, DistributiveLattice, HeytingAlgebra,
+        JoinSemilattice, Lattice, MeetSemilattice, ModularLattice,
+    },
+    operators::{BinaryOperator, Join, Meet},
+};
+
+/// The chain 0 < 1 < … < *N* - 1, where *N* must be at least 1.
+///
+/// Join is the maximum, meet is the minimum, and *x* → *y* is ⊤ if *x* ≤ *y* and *y* otherwise.
+/// This is the algebra of Gödel's many-valued logic.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::latticelike::{heyting::Chain, BoundedLattice, HeytingAlgebra, JoinSemilattice};
+///
+/// let half = Chain::<3>::new(1);
+///
+/// // ¬½ = ⊥, so ½ ∨ ¬½ = ½ and the law of the excluded middle fails.
+/// assert_eq!(half.pseudo_complement(), Chain::BOTTOM);
+/// assert_eq!(half.join(half.pseudo_complement()), half);
+/// // But ¬¬(½ ∨ ¬½) = ⊤.
+/// let excluded_middle = half.join(half.pseudo_complement());
+/// assert_eq!(excluded_middle.pseudo_complement().pseudo_complement(), Chain::TOP);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Chain<const N: usize>(usize);
+
+impl<const N: usize> Chain<N> {
+    /// The element *i*.
+    ///
+    /// # Panics
+    ///
+    /// Panics if *i* ≥ *N*.
+    pub fn new(i: usize) -> Self {
+        assert!(i < N, "{i} is not in a chain of length {N}");
+        Chain(i)
+    }
+
+    /// The position of the element in the chain.
+    pub fn value(&self) -> usize {
+        self.0
+    }
+
+    /// All the elements, from ⊥ to ⊤.
+    pub fn elements() -> Vec<Self> {
+        (0..N).map(Chain).collect()
+    }
+}
+
+impl<const N: usize> fmt::Display for Chain<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const N: usize> BinaryOperator<Join> for Chain<N> {
+    fn op(&self, rhs: Self) -> Self {
+        Chain(self.0.max(rhs.0))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        self.0 = self.0.max(rhs.0);
+    }
+}
+
+impl<const N: usize> BinaryOperator<Meet> for Chain<N> {
+    fn op(&self, rhs: Self) -> Self {
+        Chain(self.0.min(rhs.0))
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        self.0 = self.0.min(rhs.0);
+    }
+}
+
+impl<const N: usize> UnitalMagma<Join> for Chain<N> {
+    const IDENTITY: Self = Chain::BOTTOM;
+}
+impl<const N: usize> UnitalMagma<Meet> for Chain<N> {
+    const IDENTITY: Self = Chain::TOP;
+}
+
+impl<const N: usize> BoundedLattice for Chain<N> {
+    const BOTTOM: Self = Chain(0);
+    const TOP: Self = Chain(N - 1);
+}
+
+impl<const N: usize> HeytingAlgebra for Chain<N> {
+    fn implies(&self, rhs: Self) -> Self {
+        if self.0 <= rhs.0 {
+            Chain::TOP
+        } else {
+            rhs
+        }
+    }
+}
+
+distributive_lattice_markers!([const N: usize] Chain<N>);
+
+/// The two-element chain is the only one with complements, and it's isomorphic to [`bool`].
+impl ComplementedLattice for Chain<2> {
+    fn complement(&self) -> Self {
+        Chain(1 - self.0)
+    }
+}
+
+impl BooleanAlgebra for Chain<2> {}
+
+/// The reasons a [`FiniteTopology`] can fail to be constructed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TopologyError {
+    /// A set contains a point which doesn't exist.
+    OutOfRange(usize),
+    /// The empty set isn't open.
+    MissingEmptySet,
+    /// The whole space isn't open.
+    MissingWholeSpace,
+    /// The union of the open sets with these indices isn't open.
+    NotClosedUnderUnion(usize, usize),
+    /// The intersection of the open sets with these indices isn't open.
+    NotClosedUnderIntersection(usize, usize),
+}
+
+impl fmt::Display for TopologyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TopologyError::OutOfRange(x) => write!(f, "point {x} is out of range"),
+            TopologyError::MissingEmptySet => write!(f, "the empty set must be open"),
+            TopologyError::MissingWholeSpace => write!(f, "the whole space must be open"),
+            TopologyError::NotClosedUnderUnion(i, j) => {
+                write!(f, "the union of open sets {i} and {j} is not open")
+            }
+            TopologyError::NotClosedUnderIntersection(i, j) => {
+                write!(f, "the intersection of open sets {i} and {j} is not open")
+            }
+        }
+    }
+}
+
+impl Error for TopologyError {}
+
+/// Where the elements of a Heyting algebra of subsets live.
+trait Space {
+    /// The whole space, which is the top of the algebra.
+    fn universe(&self) -> BitSet;
+
+    /// The relative pseudo-complement of two subsets of the universe.
+    fn implies(&self, x: &BitSet, y: &BitSet) -> BitSet;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Topology {
+    points: usize,
+    /// The open sets, ordered by size and then by their points.
+    opens: Vec<BitSet>,
+}
+
+impl Space for Topology {
+    fn universe(&self) -> BitSet {
+        (0..self.points).collect()
+    }
+
+    fn implies(&self, x: &BitSet, y: &BitSet) -> BitSet {
+        // The union of every open W with W ∩ x ⊆ y.
+        let outside = x.meet(y.complement());
+        self.opens
+            .iter()
+            .filter(|open| open.meet(outside.clone()).is_empty())
+            .fold(BitSet::new(), |union, open| union.join(open.clone()))
+    }
+}
+
+impl Space for Poset {
+    fn universe(&self) -> BitSet {
+        (0..self.len()).collect()
+    }
+
+    fn implies(&self, x: &BitSet, y: &BitSet) -> BitSet {
+        // Every p such that everything below p which is in x is also in y.
+        (0..self.len())
+            .filter(|&p| {
+                (0..self.len()).all(|q| !self.leq(q, p) || !x.contains(q) || y.contains(q))
+            })
+            .collect()
+    }
+}
+
+/// A topology on the points 0, …, *n* - 1.
+///
+/// # Example
+///
+/// The Sierpiński space has two points, where {1} is open but {0} isn't.
+///
+/// ```rust
+/// use yaaarc::latticelike::{heyting::FiniteTopology, HeytingAlgebra, JoinSemilattice};
+///
+/// let sierpinski = FiniteTopology::new(2, vec![vec![], vec![1], vec![0, 1]]).unwrap();
+/// let u = sierpinski.open([1]).unwrap();
+///
+/// // The interior of {0} is empty, so ¬{1} = ∅.
+/// assert!(u.pseudo_complement().is_empty());
+/// assert_eq!(u.join(u.pseudo_complement()).to_string(), "{1}");
+/// assert_eq!(sierpinski.interior([0]).to_string(), "{}");
+/// assert_eq!(sierpinski.open_sets().len(), 3);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FiniteTopology {
+    topology: Rc<Topology>,
+}
+
+impl FiniteTopology {
+    fn from_family(points: usize, opens: Vec<BitSet>) -> Self {
+        let mut opens = opens;
+        opens.sort_by_key(|open| (open.len(), open.iter().collect::<Vec<_>>()));
+        opens.dedup();
+        FiniteTopology {
+            topology: Rc::new(Topology { points, opens }),
+        }
+    }
+
+    fn to_family(points: usize, sets: Vec<Vec<usize>>) -> Result<Vec<BitSet>, TopologyError> {
+        sets.into_iter()
+            .map(|set| match set.iter().find(|&&x| x >= points) {
+                Some(&x) => Err(TopologyError::OutOfRange(x)),
+                None => Ok(set.into_iter().collect()),
+            })
+            .collect()
+    }
+
+    /// Creates the topology on `points` points with the given open sets, checking the axioms.
+    pub fn new(points: usize, opens: Vec<Vec<usize>>) -> Result<Self, TopologyError> {
+        let mut opens = FiniteTopology::to_family(points, opens)?;
+        opens.dedup();
+        let universe: BitSet = (0..points).collect();
+        if !opens.contains(&BitSet::new()) {
+            return Err(TopologyError::MissingEmptySet);
+        }
+        if !opens.contains(&universe) {
+            return Err(TopologyError::MissingWholeSpace);
+        }
+        for (i, x) in opens.iter().enumerate() {
+            for (j, y) in opens.iter().enumerate().skip(i + 1) {
+                if !opens.contains(&x.join(y.clone())) {
+                    return Err(TopologyError::NotClosedUnderUnion(i, j));
+                }
+                if !opens.contains(&x.meet(y.clone())) {
+                    return Err(TopologyError::NotClosedUnderIntersection(i, j));
+                }
+            }
+        }
+        Ok(FiniteTopology::from_family(points, opens))
+    }
+
+    /// Creates the coarsest topology on `points` points in which the given sets are open, in
+    /// other words, the topology with them as a subbasis.
+    pub fn generated_by(points: usize, subbasis: Vec<Vec<usize>>) -> Result<Self, TopologyError> {
+        let mut opens = FiniteTopology::to_family(points, subbasis)?;
+        opens.push(BitSet::new());
+        opens.push((0..points).collect());
+        // Close up under pairwise unions and intersections, until nothing new appears.
+        let mut i = 0;
+        while i < opens.len() {
+            for j in 0..i {
+                for new in [
+                    opens[i].join(opens[j].clone()),
+                    opens[i].meet(opens[j].clone()),
+                ] {
+                    if !opens.contains(&new) {
+                        opens.push(new);
+                    }
+                }
+            }
+            i += 1;
+        }
+        Ok(FiniteTopology::from_family(points, opens))
+    }
+
+    /// The discrete topology, where every set is open. Its open sets form a Boolean algebra.
+    pub fn discrete(points: usize) -> Self {
+        let singletons = (0..points).map(|x| vec![x]).collect();
+        FiniteTopology::generated_by(points, singletons).expect("the points are in range")
+    }
+
+    /// The number of points.
+    pub fn points(&self) -> usize {
+        self.topology.points
+    }
+
+    fn element(&self, points: BitSet) -> OpenSet {
+        OpenSet {
+            space: Some(Rc::clone(&self.topology)),
+            points,
+        }
+    }
+
+    /// All the open sets, ordered by size.
+    pub fn open_sets(&self) -> Vec<OpenSet> {
+        self.topology
+            .opens
+            .iter()
+            .map(|open| self.element(open.clone()))
+            .collect()
+    }
+
+    /// The given set, if it's open.
+    pub fn open(&self, points: impl IntoIterator<Item = usize>) -> Option<OpenSet> {
+        let points: BitSet = points.into_iter().collect();
+        self.topology
+            .opens
+            .contains(&points)
+            .then(|| self.element(points))
+    }
+
+    /// The interior of the given set, the largest open set inside it.
+    pub fn interior(&self, points: impl IntoIterator<Item = usize>) -> OpenSet {
+        let points: BitSet = points.into_iter().collect();
+        self.element(self.topology.implies(&points.complement(), &BitSet::new()))
+    }
+
+    /// The points of an open set, resolving the constants to this space.
+    pub fn points_of(&self, open: &OpenSet) -> Vec<usize> {
+        open.points.meet(self.topology.universe()).iter().collect()
+    }
+}
+
+/// The down-sets of a finite [`Poset`], the sets *D* where *x* ∈ *D* and *y* ≤ *x* imply *y* ∈
+/// *D*.
+///
+/// # Example
+///
+/// The down-sets of a three-element "V", where 0 and 1 are both below 2.
+///
+/// ```rust
+/// use yaaarc::latticelike::{heyting::DownSets, poset::Poset, HeytingAlgebra};
+///
+/// let v = Poset::from_relation(3, [(0, 2), (1, 2)]).unwrap();
+/// let down_sets = DownSets::new(v);
+///
+/// assert_eq!(down_sets.all().len(), 5);
+/// let left = down_sets.principal(0);
+/// assert_eq!(left.pseudo_complement(), down_sets.principal(1));
+/// assert_eq!(down_sets.principal(2).implies(left.clone()), left);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DownSets {
+    poset: Rc<Poset>,
+}
+
+impl DownSets {
+    /// The down-sets of `poset`.
+    pub fn new(poset: Poset) -> Self {
+        DownSets {
+            poset: Rc::new(poset),
+        }
+    }
+
+    /// The underlying poset.
+    pub fn poset(&self) -> &Poset {
+        &self.poset
+    }
+
+    fn element(&self, points: BitSet) -> DownSet {
+        DownSet {
+            space: Some(Rc::clone(&self.poset)),
+            points,
+        }
+    }
+
+    /// The smallest down-set containing the given elements.
+    pub fn generated(&self, elements: impl IntoIterator<Item = usize>) -> DownSet {
+        let elements: Vec<usize> = elements.into_iter().collect();
+        self.element(
+            (0..self.poset.len())
+                .filter(|&y| elements.iter().any(|&x| self.poset.leq(y, x)))
+                .collect(),
+        )
+    }
+
+    /// The principal down-set ↓*x*, everything below *x*.
+    pub fn principal(&self, x: usize) -> DownSet {
+        self.generated([x])
+    }
+
+    /// Every down-set, ordered by size.
+    pub fn all(&self) -> Vec<DownSet> {
+        // Every down-set is generated by its maximal elements, which form an antichain.
+        let mut sets = vec![BitSet::new()];
+        let mut i = 0;
+        while i < sets.len() {
+            for x in 0..self.poset.len() {
+                let new = sets[i].join(self.principal(x).points);
+                if !sets.contains(&new) {
+                    sets.push(new);
+                }
+            }
+            i += 1;
+        }
+        sets.sort_by_key(|set| (set.len(), set.iter().collect::<Vec<_>>()));
+        sets.into_iter().map(|set| self.element(set)).collect()
+    }
+
+    /// The elements of a down-set, resolving the constants to this poset.
+    pub fn points_of(&self, down_set: &DownSet) -> Vec<usize> {
+        down_set.points.meet(self.poset.universe()).iter().collect()
+    }
+}
+
+/// Implements [`HeytingAlgebra`] for a subset of a [`Space`], given as a struct with fields
+/// `space: Option<Rc<_>>` and `points: BitSet`.
+///
+/// The constants have no space. Their points are ∅ for ⊥ and all of ℕ for ⊤, so intersecting
+/// with the universe of a space gives the right set.
+macro_rules! subset_heyting_algebra {
+    ($ty:ident, $space:ty) => {
+        impl $ty {
+            /// The space of whichever operand has one.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the operands belong to different spaces.
+            fn shared_space<'a>(&'a self, rhs: &'a Self) -> Option<&'a Rc<$space>> {
+                match (&self.space, &rhs.space) {
+                    (Some(a), Some(b)) => {
+                        assert!(Rc::ptr_eq(a, b), "the sets belong to different spaces");
+                        Some(a)
+                    }
+                    (Some(a), None) | (None, Some(a)) => Some(a),
+                    (None, None) => None,
+                }
+            }
+
+            fn combine(&self, rhs: &Self, f: impl Fn(&BitSet, &BitSet) -> BitSet) -> Self {
+                let space = self.shared_space(rhs).cloned();
+                let points = match &space {
+                    Some(space) => f(&self.points, &rhs.points).meet(space.universe()),
+                    None => f(&self.points, &rhs.points),
+                };
+                $ty { space, points }
+            }
+
+            /// Whether `x` is in the set.
+            pub fn contains(&self, x: usize) -> bool {
+                self.points.contains(x)
+                    && self
+                        .space
+                        .as_ref()
+                        .is_none_or(|space| space.universe().contains(x))
+            }
+
+            /// Whether the set is empty.
+            pub fn is_empty(&self) -> bool {
+                match &self.space {
+                    Some(space) => self.points.meet(space.universe()).is_empty(),
+                    None => self.points.is_empty(),
+                }
+            }
+        }
+
+        impl PartialEq for $ty {
+            fn eq(&self, other: &Self) -> bool {
+                match self.shared_space(other) {
+                    Some(space) => {
+                        self.points.meet(space.universe()) == other.points.meet(space.universe())
+                    }
+                    None => self.points == other.points,
+                }
+            }
+        }
+
+        impl Eq for $ty {}
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match &self.space {
+                    Some(space) => write!(f, "{}", self.points.meet(space.universe())),
+                    None if self.points.is_empty() => write!(f, "⊥"),
+                    None => write!(f, "⊤"),
+                }
+            }
+        }
+
+        impl fmt::Debug for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({self})", stringify!($ty))
+            }
+        }
+
+        impl BinaryOperator<Join> for $ty {
+            fn op(&self, rhs: Self) -> Self {
+                self.combine(&rhs, |x, y| x.join(y.clone()))
+            }
+            fn op_assign(&mut self, rhs: Self) {
+                *self = JoinSemilattice::join(self, rhs);
+            }
+        }
+
+        impl BinaryOperator<Meet> for $ty {
+            fn op(&self, rhs: Self) -> Self {
+                self.combine(&rhs, |x, y| x.meet(y.clone()))
+            }
+            fn op_assign(&mut self, rhs: Self) {
+                *self = MeetSemilattice::meet(self, rhs);
+            }
+        }
+
+        impl UnitalMagma<Join> for $ty {
+            const IDENTITY: Self = $ty::BOTTOM;
+        }
+        impl UnitalMagma<Meet> for $ty {
+            const IDENTITY: Self = $ty::TOP;
+        }
+
+        impl BoundedLattice for $ty {
+            const BOTTOM: Self = $ty {
+                space: None,
+                points: BitSet::BOTTOM,
+            };
+            const TOP: Self = $ty {
+                space: None,
+                points: BitSet::TOP,
+            };
+        }
+
+        impl HeytingAlgebra for $ty {
+            fn implies(&self, rhs: Self) -> Self {
+                match self.shared_space(&rhs).cloned() {
+                    Some(space) => {
+                        let universe = space.universe();
+                        let (x, y) = (self.points.meet(universe.clone()), rhs.points.meet(universe));
+                        $ty {
+                            points: space.implies(&x, &y),
+                            space: Some(space),
+                        }
+                    }
+                    // Between ∅ and ℕ, the Boolean implication is right.
+                    None => $ty {
+                        space: None,
+                        points: HeytingAlgebra::implies(&self.points, rhs.points.clone()),
+                    },
+                }
+            }
+        }
+
+        distributive_lattice_markers!([] $ty);
+    };
+}
+
+/// An open set of a [`FiniteTopology`].
+///
+/// The constants [`BoundedLattice::BOTTOM`] and [`BoundedLattice::TOP`] don't belong to any space,
+/// and stand for ∅ and the whole space of whichever open set they're combined with.
+///
+/// # Panics
+///
+/// Combining open sets of different spaces panics.
+#[derive(Clone)]
+pub struct OpenSet {
+    space: Option<Rc<Topology>>,
+    points: BitSet,
+}
+
+subset_heyting_algebra!(OpenSet, Topology);
+
+/// A down-set of a finite [`Poset`], as created by [`DownSets`].
+///
+/// The constants [`BoundedLattice::BOTTOM`] and [`BoundedLattice::TOP`] don't belong to any poset,
+/// and stand for ∅ and the whole poset of whichever down-set they're combined with.
+///
+/// # Panics
+///
+/// Combining down-sets of different posets panics.
+#[derive(Clone)]
+pub struct DownSet {
+    space: Option<Rc<Poset>>,
+    points: BitSet,
+}
+
+subset_heyting_algebra!(DownSet, Poset);
@@ -0,0 +1,775 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Finite partially ordered sets, and the lattices they give rise to.
+//!
+//! A [`Poset`] has elements 0, …, *n* - 1, each with a label for display. It stores the whole
+//! order relation, so comparisons are constant time, and computes its Hasse diagram (the covering
+//! relation) on demand.
+//!
+//! If every pair of elements has a least upper bound and a greatest lower bound, the poset is a
+//! lattice, and [`Poset::to_lattice`] gives a [`FiniteLattice`] whose elements implement the
+//! [`crate::latticelike`] traits. Every poset also embeds into a smallest complete lattice, its
+//! [`Poset::dedekind_macneille`] completion.
+
+use std::{collections::BTreeSet, error::Error, fmt, rc::Rc};
+
+use crate::{
+    grouplike::{Band, CommutativeMagma, Magma, Semigroup, Semilattice, UnitalMagma},
+    latticelike::{boolean::BitSet, BoundedLattice, JoinSemilattice, Lattice, MeetSemilattice},
+    operators::{BinaryOperator, Join, Meet},
+};
+
+/// The reasons a [`Poset`] can fail to be constructed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PosetError {
+    /// A pair in the relation names an element which doesn't exist.
+    OutOfRange(usize),
+    /// The relation isn't antisymmetric: after taking the transitive closure, *x* ≤ *y* and
+    /// *y* ≤ *x* for these distinct elements.
+    NotAntisymmetric { x: usize, y: usize },
+    /// The number of labels doesn't match the number of elements.
+    WrongLabelCount,
+}
+
+impl fmt::Display for PosetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PosetError::OutOfRange(x) => write!(f, "element {x} is out of range"),
+            PosetError::NotAntisymmetric { x, y } => {
+                write!(f, "the relation is not antisymmetric on ({x}, {y})")
+            }
+            PosetError::WrongLabelCount => {
+                write!(
+                    f,
+                    "the number of labels doesn't match the number of elements"
+                )
+            }
+        }
+    }
+}
+
+impl Error for PosetError {}
+
+/// A finite partially ordered set.
+///
+/// # Example
+///
+/// The divisors of 12, ordered by divisibility.
+///
+/// ```rust
+/// use yaaarc::latticelike::poset::Poset;
+///
+/// let divisors = [1, 2, 3, 4, 6, 12];
+/// let poset = Poset::from_fn(6, |i, j| divisors[j] % divisors[i] == 0)
+///     .unwrap()
+///     .with_labels(divisors.iter().map(ToString::to_string).collect())
+///     .unwrap();
+///
+/// assert_eq!(
+///     poset.hasse_diagram(),
+///     vec![(0, 1), (0, 2), (1, 3), (1, 4), (2, 4), (3, 5), (4, 5)],
+/// );
+/// assert_eq!(poset.least_upper_bound(&[3, 2]), Some(5));
+/// assert_eq!(poset.greatest_lower_bound(&[3, 4]), Some(1));
+///
+/// let lattice = poset.to_lattice().unwrap();
+/// assert!(lattice.is_distributive());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Poset {
+    /// `leq[x][y]` is whether *x* ≤ *y*.
+    leq: Vec<Vec<bool>>,
+    labels: Vec<String>,
+}
+
+impl Poset {
+    /// Creates the poset generated by a relation on *n* elements, meaning the smallest partial
+    /// order containing it. This is its reflexive transitive closure, found with Warshall's
+    /// algorithm.
+    pub fn from_relation(
+        n: usize,
+        relation: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Result<Self, PosetError> {
+        let mut leq = vec![vec![false; n]; n];
+        for (x, row) in leq.iter_mut().enumerate() {
+            row[x] = true;
+        }
+        for (x, y) in relation {
+            if x >= n || y >= n {
+                return Err(PosetError::OutOfRange(x.max(y)));
+            }
+            leq[x][y] = true;
+        }
+        for k in 0..n {
+            let through = leq[k].clone();
+            for row in leq.iter_mut().filter(|row| row[k]) {
+                for (entry, &above) in row.iter_mut().zip(&through) {
+                    *entry |= above;
+                }
+            }
+        }
+        Poset::from_matrix(leq)
+    }
+
+    /// Creates the poset on *n* elements where *x* ≤ *y* iff `leq(x, y)`. The relation is closed
+    /// up, just like in [`Poset::from_relation`], so it's enough to give the covering pairs.
+    pub fn from_fn(n: usize, leq: impl Fn(usize, usize) -> bool) -> Result<Self, PosetError> {
+        let pairs: Vec<(usize, usize)> = (0..n)
+            .flat_map(|x| (0..n).map(move |y| (x, y)))
+            .filter(|&(x, y)| leq(x, y))
+            .collect();
+        Poset::from_relation(n, pairs)
+    }
+
+    fn from_matrix(leq: Vec<Vec<bool>>) -> Result<Self, PosetError> {
+        let n = leq.len();
+        let cycle = (0..n)
+            .flat_map(|x| (x + 1..n).map(move |y| (x, y)))
+            .find(|&(x, y)| leq[x][y] && leq[y][x]);
+        if let Some((x, y)) = cycle {
+            return Err(PosetError::NotAntisymmetric { x, y });
+        }
+        Ok(Poset {
+            leq,
+            labels: (0..n).map(|x| x.to_string()).collect(),
+        })
+    }
+
+    /// Replaces the labels, which default to the numbers of the elements.
+    pub fn with_labels(mut self, labels: Vec<String>) -> Result<Self, PosetError> {
+        if labels.len() != self.len() {
+            return Err(PosetError::WrongLabelCount);
+        }
+        self.labels = labels;
+        Ok(self)
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.leq.len()
+    }
+
+    /// Whether the poset has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.leq.is_empty()
+    }
+
+    /// The labels of the elements.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Whether *x* ≤ *y*.
+    pub fn leq(&self, x: usize, y: usize) -> bool {
+        self.leq[x][y]
+    }
+
+    /// Whether *x* < *y*.
+    pub fn lt(&self, x: usize, y: usize) -> bool {
+        x != y && self.leq[x][y]
+    }
+
+    /// Whether *x* ≤ *y* or *y* ≤ *x*.
+    pub fn comparable(&self, x: usize, y: usize) -> bool {
+        self.leq[x][y] || self.leq[y][x]
+    }
+
+    /// Whether *y* covers *x*, meaning *x* < *y* with nothing in between.
+    pub fn covers(&self, x: usize, y: usize) -> bool {
+        self.lt(x, y) && !(0..self.len()).any(|z| self.lt(x, z) && self.lt(z, y))
+    }
+
+    /// Every pair (*x*, *y*) with *x* < *y*, in lexicographic order. This is the transitive
+    /// closure of the relation the poset was made from, minus the reflexive pairs.
+    pub fn relation(&self) -> Vec<(usize, usize)> {
+        let n = self.len();
+        (0..n)
+            .flat_map(|x| (0..n).map(move |y| (x, y)))
+            .filter(|&(x, y)| self.lt(x, y))
+            .collect()
+    }
+
+    /// The Hasse diagram: every pair (*x*, *y*) where *y* covers *x*, in lexicographic order. This
+    /// is the transitive reduction of the order.
+    pub fn hasse_diagram(&self) -> Vec<(usize, usize)> {
+        self.relation()
+            .into_iter()
+            .filter(|&(x, y)| self.covers(x, y))
+            .collect()
+    }
+
+    /// The elements with nothing below them.
+    pub fn minimal_elements(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&x| !(0..self.len()).any(|y| self.lt(y, x)))
+            .collect()
+    }
+
+    /// The elements with nothing above them.
+    pub fn maximal_elements(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&x| !(0..self.len()).any(|y| self.lt(x, y)))
+            .collect()
+    }
+
+    /// The elements above every element of `subset`.
+    pub fn upper_bounds(&self, subset: &[usize]) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&y| subset.iter().all(|&x| self.leq[x][y]))
+            .collect()
+    }
+
+    /// The elements below every element of `subset`.
+    pub fn lower_bounds(&self, subset: &[usize]) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&y| subset.iter().all(|&x| self.leq[y][x]))
+            .collect()
+    }
+
+    /// The least element of `candidates`, if there is one.
+    fn least(&self, candidates: &[usize]) -> Option<usize> {
+        candidates
+            .iter()
+            .copied()
+            .find(|&x| candidates.iter().all(|&y| self.leq[x][y]))
+    }
+
+    /// The greatest element of `candidates`, if there is one.
+    fn greatest(&self, candidates: &[usize]) -> Option<usize> {
+        candidates
+            .iter()
+            .copied()
+            .find(|&x| candidates.iter().all(|&y| self.leq[y][x]))
+    }
+
+    /// The least upper bound (supremum) of `subset`, if it exists.
+    pub fn least_upper_bound(&self, subset: &[usize]) -> Option<usize> {
+        self.least(&self.upper_bounds(subset))
+    }
+
+    /// The greatest lower bound (infimum) of `subset`, if it exists.
+    pub fn greatest_lower_bound(&self, subset: &[usize]) -> Option<usize> {
+        self.greatest(&self.lower_bounds(subset))
+    }
+
+    /// The least element, if there is one.
+    pub fn bottom(&self) -> Option<usize> {
+        self.greatest_lower_bound(&(0..self.len()).collect::<Vec<_>>())
+    }
+
+    /// The greatest element, if there is one.
+    pub fn top(&self) -> Option<usize> {
+        self.least_upper_bound(&(0..self.len()).collect::<Vec<_>>())
+    }
+
+    /// The dual poset, where the order is reversed.
+    pub fn dual(&self) -> Poset {
+        let n = self.len();
+        Poset {
+            leq: (0..n)
+                .map(|x| (0..n).map(|y| self.leq[y][x]).collect())
+                .collect(),
+            labels: self.labels.clone(),
+        }
+    }
+
+    /// The join and meet tables, if every pair has a join and a meet.
+    fn lattice_tables(&self) -> Option<(Table, Table)> {
+        let n = self.len();
+        let mut join = vec![vec![0; n]; n];
+        let mut meet = vec![vec![0; n]; n];
+        for x in 0..n {
+            for y in x..n {
+                let j = self.least_upper_bound(&[x, y])?;
+                let m = self.greatest_lower_bound(&[x, y])?;
+                (join[x][y], join[y][x]) = (j, j);
+                (meet[x][y], meet[y][x]) = (m, m);
+            }
+        }
+        Some((join, meet))
+    }
+
+    /// Whether the poset is a lattice, meaning it's nonempty and every pair of elements has a join
+    /// and a meet.
+    pub fn is_lattice(&self) -> bool {
+        !self.is_empty() && self.lattice_tables().is_some()
+    }
+
+    /// The poset as a lattice, or `None` if it isn't one.
+    pub fn to_lattice(&self) -> Option<FiniteLattice> {
+        if self.is_empty() {
+            return None;
+        }
+        let (join, meet) = self.lattice_tables()?;
+        Some(FiniteLattice {
+            tables: Rc::new(Tables {
+                bottom: self.bottom()?,
+                top: self.top()?,
+                poset: self.clone(),
+                join,
+                meet,
+            }),
+        })
+    }
+
+    /// The Dedekind–MacNeille completion, the smallest complete lattice the poset embeds into.
+    /// Returns the lattice and the index of the image of each element.
+    ///
+    /// The elements of the completion are the *cuts*: the sets *A* of elements with *A* =
+    /// *L*(*U*(*A*)), where *U* and *L* take the upper and lower bounds. The cuts are exactly the
+    /// intersections of principal down-sets ↓*x* (with the empty intersection being the whole
+    /// poset), ordered by inclusion, and each *x* maps to ↓*x*. The labels of the completion are
+    /// those of the poset for the principal cuts, and list the cut otherwise.
+    ///
+    /// # Example
+    ///
+    /// Completing the four-element "bowtie" adds a bottom, a top, and one element in the middle.
+    ///
+    /// ```rust
+    /// use yaaarc::latticelike::poset::Poset;
+    ///
+    /// let bowtie = Poset::from_relation(4, [(0, 2), (0, 3), (1, 2), (1, 3)]).unwrap();
+    /// assert!(!bowtie.is_lattice());
+    ///
+    /// let (completion, embedding) = bowtie.dedekind_macneille();
+    /// assert_eq!(completion.len(), 7);
+    /// let poset = completion.poset();
+    /// for (x, y) in bowtie.relation() {
+    ///     assert!(poset.lt(embedding[x], embedding[y]));
+    /// }
+    /// ```
+    pub fn dedekind_macneille(&self) -> (FiniteLattice, Vec<usize>) {
+        let n = self.len();
+        let down_set = |y: usize| -> BitSet { (0..n).filter(|&x| self.leq[x][y]).collect() };
+        let mut cuts: BTreeSet<Vec<usize>> = BTreeSet::from([(0..n).collect()]);
+        for y in 0..n {
+            let down = down_set(y);
+            let new: Vec<Vec<usize>> = cuts
+                .iter()
+                .map(|cut| cut.iter().copied().filter(|&x| down.contains(x)).collect())
+                .collect();
+            cuts.extend(new);
+        }
+        let cuts: Vec<Vec<usize>> = cuts.into_iter().collect();
+        let contains = |a: &[usize], b: &[usize]| b.iter().all(|x| a.binary_search(x).is_ok());
+        let embedding: Vec<usize> = (0..n)
+            .map(|y| {
+                let down: Vec<usize> = down_set(y).iter().collect();
+                cuts.binary_search(&down)
+                    .expect("principal down-sets are cuts")
+            })
+            .collect();
+        let labels = cuts
+            .iter()
+            .enumerate()
+            .map(|(i, cut)| match embedding.iter().position(|&e| e == i) {
+                Some(y) => self.labels[y].clone(),
+                None if cut.is_empty() => String::from("∅"),
+                None => {
+                    let elements: Vec<&str> =
+                        cut.iter().map(|&x| self.labels[x].as_str()).collect();
+                    format!("{{{}}}", elements.join(", "))
+                }
+            })
+            .collect();
+        let completion = Poset::from_fn(cuts.len(), |i, j| contains(&cuts[j], &cuts[i]))
+            .expect("inclusion is a partial order")
+            .with_labels(labels)
+            .expect("there is one label per cut");
+        let lattice = completion
+            .to_lattice()
+            .expect("the cuts of a poset form a complete lattice");
+        (lattice, embedding)
+    }
+
+    /// The Hasse diagram in the Graphviz DOT language, drawn with smaller elements lower down.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n    rankdir=BT;\n    edge [arrowhead=none];\n");
+        for (x, label) in self.labels.iter().enumerate() {
+            dot.push_str(&format!("    {x} [label={label:?}];\n"));
+        }
+        for (x, y) in self.hasse_diagram() {
+            dot.push_str(&format!("    {x} -> {y};\n"));
+        }
+        dot.push('}');
+        dot.push('\n');
+        dot
+    }
+}
+
+/// An operation table, indexed by the two operands.
+type Table = Vec<Vec<usize>>;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Tables {
+    poset: Poset,
+    join: Table,
+    meet: Table,
+    bottom: usize,
+    top: usize,
+}
+
+/// A finite lattice, given by its join and meet tables.
+///
+/// The elements are [`LatticeElement`]s, which implement the [`crate::latticelike`] traits up to
+/// [`BoundedLattice`]. Whether the lattice is modular or distributive is only known at runtime, so
+/// those are checked by [`FiniteLattice::is_modular`] and [`FiniteLattice::is_distributive`]
+/// instead.
+///
+/// # Example
+///
+/// The diamond *M*₃ is modular but not distributive.
+///
+/// ```rust
+/// use yaaarc::latticelike::{poset::Poset, BoundedLattice, JoinSemilattice, MeetSemilattice};
+///
+/// let m3 = Poset::from_relation(5, [(0, 1), (0, 2), (0, 3), (1, 4), (2, 4), (3, 4)])
+///     .unwrap()
+///     .to_lattice()
+///     .unwrap();
+/// let [a, b, c] = [1, 2, 3].map(|i| m3.element(i));
+///
+/// assert_eq!(a.join(b.clone()), m3.top());
+/// assert_eq!(b.meet(c.clone()), m3.bottom());
+/// assert_eq!(a.meet(b.join(c)), a);
+/// assert_eq!(m3.bottom(), yaaarc::latticelike::poset::LatticeElement::BOTTOM);
+///
+/// assert!(m3.is_modular());
+/// assert_eq!(m3.find_m3(), Some([0, 1, 2, 3, 4]));
+/// assert!(!m3.is_distributive());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FiniteLattice {
+    tables: Rc<Tables>,
+}
+
+/// Where a [`LatticeElement`] is. The constants don't belong to any particular lattice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Position {
+    Bottom,
+    Top,
+    At(usize),
+}
+
+/// An element of a [`FiniteLattice`].
+///
+/// The constants [`BoundedLattice::BOTTOM`] and [`BoundedLattice::TOP`] don't belong to any
+/// lattice, and stand for the bottom and top of whichever lattice they're combined with.
+///
+/// # Panics
+///
+/// Combining elements of different lattices panics.
+#[derive(Clone)]
+pub struct LatticeElement {
+    lattice: Option<Rc<Tables>>,
+    position: Position,
+}
+
+impl FiniteLattice {
+    /// The underlying poset.
+    pub fn poset(&self) -> &Poset {
+        &self.tables.poset
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.tables.poset.len()
+    }
+
+    /// Always `false`, as lattices are nonempty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The element with index *i*.
+    ///
+    /// # Panics
+    ///
+    /// Panics if *i* is out of range.
+    pub fn element(&self, i: usize) -> LatticeElement {
+        assert!(i < self.len(), "element {i} is out of range");
+        LatticeElement {
+            lattice: Some(Rc::clone(&self.tables)),
+            position: Position::At(i),
+        }
+    }
+
+    /// All the elements, in order of their indices.
+    pub fn elements(&self) -> Vec<LatticeElement> {
+        (0..self.len()).map(|i| self.element(i)).collect()
+    }
+
+    /// The index of an element, resolving the constants to this lattice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element belongs to a different lattice.
+    pub fn index_of(&self, x: &LatticeElement) -> usize {
+        if let Some(lattice) = &x.lattice {
+            assert!(
+                Rc::ptr_eq(lattice, &self.tables),
+                "the element belongs to a different lattice"
+            );
+        }
+        self.tables.resolve(x.position)
+    }
+
+    /// The least element.
+    pub fn bottom(&self) -> LatticeElement {
+        self.element(self.tables.bottom)
+    }
+
+    /// The greatest element.
+    pub fn top(&self) -> LatticeElement {
+        self.element(self.tables.top)
+    }
+
+    /// The index of the join of the elements with indices *x* and *y*.
+    pub fn join_index(&self, x: usize, y: usize) -> usize {
+        self.tables.join[x][y]
+    }
+
+    /// The index of the meet of the elements with indices *x* and *y*.
+    pub fn meet_index(&self, x: usize, y: usize) -> usize {
+        self.tables.meet[x][y]
+    }
+
+    /// The complements of the element with index *x*, meaning the *y* with *x* ∨ *y* = ⊤ and
+    /// *x* ∧ *y* = ⊥.
+    pub fn complements(&self, x: usize) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&y| {
+                self.tables.join[x][y] == self.tables.top
+                    && self.tables.meet[x][y] == self.tables.bottom
+            })
+            .collect()
+    }
+
+    /// Whether every element has a complement.
+    pub fn is_complemented(&self) -> bool {
+        (0..self.len()).all(|x| !self.complements(x).is_empty())
+    }
+
+    /// A sublattice isomorphic to the pentagon *N*₅, as the indices [0, *a*, *c*, *b*, 1] with
+    /// 0 < *a* < *c* < 1 and 0 < *b* < 1, if there is one.
+    ///
+    /// By Dedekind's theorem, a lattice is modular iff it has no such sublattice.
+    pub fn find_n5(&self) -> Option<[usize; 5]> {
+        let Tables {
+            poset, join, meet, ..
+        } = self.tables.as_ref();
+        let n = self.len();
+        for a in 0..n {
+            for c in (0..n).filter(|&c| poset.lt(a, c)) {
+                for b in 0..n {
+                    if join[a][b] == join[c][b] && meet[a][b] == meet[c][b] {
+                        return Some([meet[a][b], a, c, b, join[a][b]]);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// A sublattice isomorphic to the diamond *M*₃, as the indices [0, *a*, *b*, *c*, 1], if there
+    /// is one.
+    ///
+    /// By Birkhoff's theorem, a lattice is distributive iff it has no sublattice isomorphic to
+    /// *N*₅ or *M*₃.
+    pub fn find_m3(&self) -> Option<[usize; 5]> {
+        let Tables { join, meet, .. } = self.tables.as_ref();
+        let n = self.len();
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    let (j, m) = (join[a][b], meet[a][b]);
+                    if [join[a][c], join[b][c]] == [j, j] && [meet[a][c], meet[b][c]] == [m, m] {
+                        return Some([m, a, b, c, j]);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether the lattice satisfies the modular law.
+    pub fn is_modular(&self) -> bool {
+        self.find_n5().is_none()
+    }
+
+    /// Whether join and meet distribute over each other.
+    pub fn is_distributive(&self) -> bool {
+        self.is_modular() && self.find_m3().is_none()
+    }
+}
+
+impl Tables {
+    fn resolve(&self, position: Position) -> usize {
+        match position {
+            Position::Bottom => self.bottom,
+            Position::Top => self.top,
+            Position::At(i) => i,
+        }
+    }
+}
+
+impl LatticeElement {
+    /// Combines two elements with a table, or with `constants` if neither belongs to a lattice.
+    fn combine(
+        &self,
+        rhs: &Self,
+        table: impl Fn(&Tables) -> &Table,
+        constants: impl Fn(Position, Position) -> Position,
+    ) -> Self {
+        let lattice = match (&self.lattice, &rhs.lattice) {
+            (Some(a), Some(b)) => {
+                assert!(
+                    Rc::ptr_eq(a, b),
+                    "the elements belong to different lattices"
+                );
+                a
+            }
+            (Some(a), None) | (None, Some(a)) => a,
+            (None, None) => {
+                return LatticeElement {
+                    lattice: None,
+                    position: constants(self.position, rhs.position),
+                }
+            }
+        };
+        let (x, y) = (
+            lattice.resolve(self.position),
+            lattice.resolve(rhs.position),
+        );
+        LatticeElement {
+            lattice: Some(Rc::clone(lattice)),
+            position: Position::At(table(lattice)[x][y]),
+        }
+    }
+}
+
+impl PartialEq for LatticeElement {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.lattice, &other.lattice) {
+            (Some(a), Some(b)) => {
+                Rc::ptr_eq(a, b) && a.resolve(self.position) == a.resolve(other.position)
+            }
+            (Some(a), None) | (None, Some(a)) => {
+                a.resolve(self.position) == a.resolve(other.position)
+            }
+            (None, None) => self.position == other.position,
+        }
+    }
+}
+
+impl Eq for LatticeElement {}
+
+impl fmt::Debug for LatticeElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.lattice, self.position) {
+            (_, Position::Bottom) => write!(f, "LatticeElement(⊥)"),
+            (_, Position::Top) => write!(f, "LatticeElement(⊤)"),
+            (Some(lattice), Position::At(i)) => {
+                write!(f, "LatticeElement({})", lattice.poset.labels[i])
+            }
+            (None, Position::At(i)) => write!(f, "LatticeElement({i})"),
+        }
+    }
+}
+
+impl fmt::Display for LatticeElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.lattice {
+            Some(lattice) => write!(
+                f,
+                "{}",
+                lattice.poset.labels[lattice.resolve(self.position)]
+            ),
+            None if self.position == Position::Bottom => write!(f, "⊥"),
+            None => write!(f, "⊤"),
+        }
+    }
+}
+
+impl BinaryOperator<Join> for LatticeElement {
+    fn op(&self, rhs: Self) -> Self {
+        self.combine(
+            &rhs,
+            |tables| &tables.join,
+            |x, y| {
+                if x == Position::Top || y == Position::Top {
+                    Position::Top
+                } else {
+                    Position::Bottom
+                }
+            },
+        )
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.join(rhs);
+    }
+}
+
+impl BinaryOperator<Meet> for LatticeElement {
+    fn op(&self, rhs: Self) -> Self {
+        self.combine(
+            &rhs,
+            |tables| &tables.meet,
+            |x, y| {
+                if x == Position::Bottom || y == Position::Bottom {
+                    Position::Bottom
+                } else {
+                    Position::Top
+                }
+            },
+        )
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.meet(rhs);
+    }
+}
+
+impl Magma<Join> for LatticeElement {}
+impl Semigroup<Join> for LatticeElement {}
+impl Band<Join> for LatticeElement {}
+impl CommutativeMagma<Join> for LatticeElement {}
+impl Semilattice<Join> for LatticeElement {}
+impl UnitalMagma<Join> for LatticeElement {
+    const IDENTITY: Self = LatticeElement::BOTTOM;
+}
+impl JoinSemilattice for LatticeElement {}
+
+impl Magma<Meet> for LatticeElement {}
+impl Semigroup<Meet> for LatticeElement {}
+impl Band<Meet> for LatticeElement {}
+impl CommutativeMagma<Meet> for LatticeElement {}
+impl Semilattice<Meet> for LatticeElement {}
+impl UnitalMagma<Meet> for LatticeElement {
+    const IDENTITY: Self = LatticeElement::TOP;
+}
+impl MeetSemilattice for LatticeElement {}
+
+impl Lattice for LatticeElement {}
+
+impl BoundedLattice for LatticeElement {
+    const BOTTOM: Self = LatticeElement {
+        lattice: None,
+        position: Position::Bottom,
+    };
+    const TOP: Self = LatticeElement {
+        lattice: None,
+        position: Position::Top,
+    };
+}
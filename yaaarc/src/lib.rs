@@ -22,6 +22,7 @@ limitations under the License. */
 //! between sets and types doesn't matter here. A type which can implement a ring will still model a
 //! set.
 
+mod arithmetic;
 pub mod grouplike;
 pub mod latticelike;
 pub mod operators;
@@ -43,9 +43,9 @@ pub trait UnaryOperator<Output> {
 /// // We don't care about what type we give to BinaryOperator, since we only want one binary
 /// // operator on NewString.
 /// impl BinaryOperator<()> for NewString {
-/// 	fn op(&self, rhs: Self) -> Self {
-/// 		NewString(format!("{}{}", self.0, rhs.0))
-/// 	}
+///     fn op(&self, rhs: Self) -> Self {
+///         NewString(format!("{}{}", self.0, rhs.0))
+///     }
 ///     fn op_assign(&mut self, rhs: Self) {
 ///         self.0.push_str(rhs.0.as_str());
 ///     }
@@ -61,3 +61,11 @@ pub struct Plus;
 
 /// A unit struct representing a multiplicative operation, primarily used for [`crate::ringlike::Ring`].
 pub struct Times;
+
+/// A unit struct representing the join (least upper bound) operation, primarily used for
+/// [`crate::latticelike::Lattice`].
+pub struct Join;
+
+/// A unit struct representing the meet (greatest lower bound) operation, primarily used for
+/// [`crate::latticelike::Lattice`].
+pub struct Meet;
@@ -21,9 +21,23 @@ limitations under the License. */
 //!
 //! We don't give semiring or near-ring implementations because these vary from author to author.
 
+pub mod crt;
+pub mod cyclotomic;
+pub mod factorisation;
+pub mod finite_field;
+pub mod groebner;
+pub mod ideal;
+pub mod implicitisation;
+pub mod integer;
+pub mod multivariate;
+pub mod polynomial;
+pub mod rational;
+pub mod solve;
+
 use crate::{
-    grouplike::{AbelianGroup, CommutativeMagma, Group, Monoid, Quasigroup},
-    operators::{Plus, Times},
+    grouplike::{AbelianGroup, Band, CommutativeMagma, Group, Monoid, Quasigroup},
+    operators::{BinaryOperator, Plus, Times},
+    ringlike::factorisation::Factorisation,
 };
 
 /// A ring.
@@ -61,7 +75,7 @@ use crate::{
 /// * (M1) Multiplication is associative, (*xy*)*z* = *x*(*yz*).
 /// * (M2) Multiplication has an identity called 1 (one), ∃ 1 ∈ *R* s.t. *x*1 = a = 1*x*.
 /// * (D)  Multiplication is distributive over addition, *x*(*y* + *z*) = *xy* + *xz* and (*y* +
-///        *z*)*x* = *yx* + *zx*.
+///   *z*)*x* = *yx* + *zx*.
 ///
 /// We also introduce functions to compute the inverse of a ring element, if it exists. We call an
 /// invertible element a *unit* which form the *group of units*; it's a group under the
@@ -179,7 +193,23 @@ pub trait Ring: AbelianGroup<Plus> + Monoid<Times> + Sized {
     /// A convenience function to call the additive inverse function (i.e.
     /// [`Quasigroup<Plus>::inverse`]).
     fn inverse_add(&self) -> Self {
-        <Self as Quasigroup<Plus>>::inverse(&self)
+        <Self as Quasigroup<Plus>>::inverse(self)
+    }
+
+    /// A convenience function to call the addition operator (i.e. [`BinaryOperator<Plus>::op`]).
+    fn add(&self, rhs: Self) -> Self {
+        <Self as BinaryOperator<Plus>>::op(self, rhs)
+    }
+
+    /// A convenience function to subtract `rhs`, i.e. to add its additive inverse.
+    fn sub(&self, rhs: Self) -> Self {
+        self.add(rhs.inverse_add())
+    }
+
+    /// A convenience function to call the multiplication operator (i.e.
+    /// [`BinaryOperator<Times>::op`]).
+    fn mul(&self, rhs: Self) -> Self {
+        <Self as BinaryOperator<Times>>::op(self, rhs)
     }
 
     /// Checks whether the given element is a unit.
@@ -316,7 +346,31 @@ pub trait DivisionRing: Ring + Group<Times> {
 ///
 /// impl CommutativeRing for Mod4 {}
 /// ```
-pub trait CommutativeRing: Ring + CommutativeMagma<Times> {}
+pub trait CommutativeRing: Ring + CommutativeMagma<Times> {
+    /// Multiplies the polynomials whose coefficients, lowest degree first, are `a` and `b`. This is
+    /// how [`Poly`](polynomial::Poly) multiplies, so everything built on its `*` uses it too.
+    ///
+    /// By default, it uses Karatsuba's method, which works over every commutative ring. Rings
+    /// which can do better choose between [`Poly::mul_toom3`](polynomial::Poly::mul_toom3),
+    /// [`Poly::mul_ntt`](polynomial::Poly::mul_ntt) and
+    /// [`Poly::mul_multimodular`](polynomial::Poly::mul_multimodular) by the size of the
+    /// polynomials.
+    fn mul_polynomials(a: &[Self], b: &[Self]) -> Vec<Self>
+    where
+        Self: Clone,
+    {
+        polynomial::karatsuba(a, b)
+    }
+}
+
+/// A Boolean ring, a ring where every element is idempotent under multiplication.
+///
+/// Formally, for all *x* ∈ *R*, we have *x*² = *x*. Such a ring is automatically commutative, and
+/// every element is its own additive inverse, since *x* + *x* = (*x* + *x*)² = *x* + *x* + *x* + *x*.
+///
+/// Boolean rings are the same thing as Boolean algebras, just written differently. See
+/// [`crate::latticelike::boolean`] for the conversions between the two.
+pub trait BooleanRing: CommutativeRing + Band<Times> {}
 
 /// An integral domain.
 ///
@@ -341,8 +395,53 @@ pub trait IntegralDomain: CommutativeRing {
 
 /// A unique factorisation domain.
 ///
-/// Every element has a unique prime factorisation (up to the order and taking associates).
-pub trait UniqueFactorisationDomain: IntegralDomain {}
+/// Every element has a unique prime factorisation (up to the order and taking associates), and we
+/// can compute it.
+pub trait UniqueFactorisationDomain: IntegralDomain {
+    /// Factorises a nonzero element into a unit and a product of irreducible elements, each with
+    /// its multiplicity. See [`Factorisation`] for how the factors are normalised.
+    ///
+    /// # Panics
+    ///
+    /// Implementations panic if `self` is zero, which has no factorisation.
+    fn factor(&self) -> Factorisation<Self>;
+
+    /// Whether `self` is irreducible, i.e. it's not zero or a unit, and whenever `self` = *ab*,
+    /// either *a* or *b* is a unit.
+    fn is_irreducible(&self) -> bool {
+        *self != Self::ZERO && matches!(self.factor().factors(), [(_, 1)])
+    }
+
+    /// Whether `self` is prime, i.e. it's not zero or a unit, and whenever `self` divides *ab*, it
+    /// divides *a* or *b*. In a unique factorisation domain, this is the same as being
+    /// irreducible.
+    fn is_prime(&self) -> bool {
+        self.is_irreducible()
+    }
+
+    /// The square-free part, or radical, the product of the distinct irreducible factors of
+    /// `self`. Like the factors, it's only defined up to a unit, and the square-free part of a unit
+    /// is one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::UniqueFactorisationDomain;
+    ///
+    /// assert_eq!((-360_i32).squarefree_part(), 30);
+    /// assert!(7_i64.is_prime());
+    /// assert!(!1_i64.is_irreducible());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    fn squarefree_part(&self) -> Self {
+        self.factor()
+            .into_iter()
+            .fold(Self::ONE, |acc, (p, _)| acc.mul(p))
+    }
+}
 
 pub trait PrincipalIdealDomain: UniqueFactorisationDomain {}
 
@@ -352,8 +451,94 @@ pub trait GCDDomain: PrincipalIdealDomain {
     fn gcd(&self, b: Self) -> Self;
 }
 
+/// A Euclidean domain, an integral domain where we can divide with remainder.
+///
+/// There's a valuation *v* from the nonzero elements to the natural numbers, such that for all *a*
+/// and nonzero *b*, there are *q* and *r* with *a* = *qb* + *r* and either *r* = 0 or *v*(*r*) <
+/// *v*(*b*). Repeating this gives Euclid's algorithm, so every Euclidean domain is a
+/// [`GCDDomain`], and in fact a principal ideal domain.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::ringlike::EuclideanDomain;
+///
+/// assert_eq!((-7_i32).div_rem(3), (-3, 2));
+/// // -2 × 3 + 1 × 7 = 1, so -2 ≡ 5 is the inverse of 3 modulo 7.
+/// let (g, s, t) = 3_i64.extended_gcd(7);
+/// assert_eq!((g, s, t), (1, -2, 1));
+/// assert_eq!(s.rem(7), 5);
+/// ```
 pub trait EuclideanDomain: PrincipalIdealDomain {
+    /// The valuation *v*, which remainders make smaller.
     fn valuation(&self) -> usize;
+
+    /// Division with remainder, which returns *q* and *r* with `self` = *q* `rhs` + *r*, where
+    /// either *r* = 0 or *v*(*r*) < *v*(`rhs`).
+    ///
+    /// # Panics
+    ///
+    /// Implementations panic if `rhs` is zero.
+    fn div_rem(&self, rhs: Self) -> (Self, Self);
+
+    /// The quotient *q* from [`EuclideanDomain::div_rem`].
+    fn quo(&self, rhs: Self) -> Self {
+        self.div_rem(rhs).0
+    }
+
+    /// The remainder *r* from [`EuclideanDomain::div_rem`].
+    fn rem(&self, rhs: Self) -> Self {
+        self.div_rem(rhs).1
+    }
+
+    /// The extended Euclidean algorithm, which returns a greatest common divisor *g* of `self` and
+    /// `b`, along with Bézout coefficients *s* and *t* with *s* `self` + *t* `b` = *g*.
+    ///
+    /// The gcd is only defined up to a unit, and implementations may override this to pick a
+    /// particular one, such as a nonnegative integer or a monic polynomial.
+    fn extended_gcd(&self, b: Self) -> (Self, Self, Self)
+    where
+        Self: Clone,
+    {
+        let (mut r0, mut r1) = (self.clone(), b);
+        let (mut s0, mut s1) = (Self::ONE, Self::ZERO);
+        let (mut t0, mut t1) = (Self::ZERO, Self::ONE);
+        while r1 != Self::ZERO {
+            let (q, r) = r0.div_rem(r1.clone());
+            let s = s0.sub(q.mul(s1.clone()));
+            let t = t0.sub(q.mul(t1.clone()));
+            (r0, r1) = (r1, r);
+            (s0, s1) = (s1, s);
+            (t0, t1) = (t1, t);
+        }
+        (r0, s0, t0)
+    }
+
+    /// Euclid's algorithm, which returns a greatest common divisor of `self` and `b` without the
+    /// Bézout coefficients of [`EuclideanDomain::extended_gcd`]. This is what [`GCDDomain::gcd`]
+    /// uses.
+    ///
+    /// Implementations may override this to pick a particular gcd, which should be the same one
+    /// [`EuclideanDomain::extended_gcd`] picks, or to use a faster algorithm.
+    fn euclid(&self, b: Self) -> Self
+    where
+        Self: Clone,
+    {
+        let (mut r0, mut r1) = (self.clone(), b);
+        while r1 != Self::ZERO {
+            let r = r0.rem(r1.clone());
+            (r0, r1) = (r1, r);
+        }
+        r0
+    }
+}
+
+/// Euclid's algorithm gives every Euclidean domain greatest common divisors.
+impl<T: EuclideanDomain + Clone> GCDDomain for T {
+    /// The gcd from [`EuclideanDomain::euclid`].
+    fn gcd(&self, b: Self) -> Self {
+        self.euclid(b)
+    }
 }
 
 /// A field.
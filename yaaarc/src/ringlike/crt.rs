@@ -0,0 +1,237 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! The Chinese remainder theorem, in any Euclidean domain.
+//!
+//! If *m*₁, …, *mₖ* are pairwise coprime, then the system of congruences *x* ≡ *aᵢ* (mod *mᵢ*) has
+//! a solution, which is unique modulo *m*₁ ⋯ *mₖ*. In other words, *R*/(*m*₁ ⋯ *mₖ*) ≅ *R*/(*m*₁) ×
+//! ⋯ × *R*/(*mₖ*). If the moduli aren't coprime, there's still a solution, unique modulo the lcm,
+//! exactly when the congruences agree modulo each gcd *gᵢⱼ* = gcd(*mᵢ*, *mⱼ*).
+//!
+//! Over a field *K*, the remainder of a polynomial modulo *x* - *c* is its value at *c*, so
+//! solving congruences in *K*\[*x*\] is the same as interpolating. See [`Poly::interpolate`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::crt::{crt, CrtError};
+//!
+//! // Sunzi's original problem: x ≡ 2 (mod 3), x ≡ 3 (mod 5) and x ≡ 2 (mod 7).
+//! assert_eq!(crt(&[(2_i64, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+//! // 4 and 6 aren't coprime, but these congruences agree modulo 2, so there's a solution mod 12.
+//! assert_eq!(crt(&[(3_i64, 4), (1, 6)]), Ok((7, 12)));
+//! assert_eq!(crt(&[(0_i64, 4), (1, 6)]), Err(CrtError::Incompatible { index: 1 }));
+//! ```
+
+use std::{error::Error, fmt};
+
+use crate::ringlike::{
+    factorisation::FactorisableField, polynomial::Poly, EuclideanDomain, GCDDomain,
+};
+
+/// The reasons a system of congruences can fail to have a solution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CrtError {
+    /// The modulus at this index is zero.
+    ZeroModulus { index: usize },
+    /// The congruence at this index contradicts the ones before it.
+    Incompatible { index: usize },
+    /// These two moduli aren't coprime, which a [`CrtBasis`] needs.
+    NotCoprime { first: usize, second: usize },
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrtError::ZeroModulus { index } => write!(f, "modulus {index} is zero"),
+            CrtError::Incompatible { index } => {
+                write!(f, "congruence {index} contradicts the ones before it")
+            }
+            CrtError::NotCoprime { first, second } => {
+                write!(f, "moduli {first} and {second} aren't coprime")
+            }
+        }
+    }
+}
+
+impl Error for CrtError {}
+
+/// Solves the congruences *x* ≡ `residue` (mod `modulus`) for each pair in `congruences`.
+///
+/// This returns *x* along with the modulus it's unique up to, the lcm of the moduli, with *x*
+/// reduced by it. With no congruences at all, that's 0 modulo 1. The moduli don't need to be
+/// coprime.
+///
+/// # Errors
+///
+/// Returns [`CrtError::ZeroModulus`] if a modulus is zero, and [`CrtError::Incompatible`] at the
+/// first congruence which can't be satisfied together with the ones before it.
+pub fn crt<R: EuclideanDomain + Clone>(congruences: &[(R, R)]) -> Result<(R, R), CrtError> {
+    let mut solution = (R::ZERO, R::ONE);
+    for (index, (residue, modulus)) in congruences.iter().enumerate() {
+        if *modulus == R::ZERO {
+            return Err(CrtError::ZeroModulus { index });
+        }
+        let (x, m) = solution;
+        // With sm + tn = g, x + sm(a - x)/g is a solution whenever g divides a - x.
+        let (g, s, _) = m.extended_gcd(modulus.clone());
+        let (q, r) = residue.sub(x.clone()).div_rem(g.clone());
+        if r != R::ZERO {
+            return Err(CrtError::Incompatible { index });
+        }
+        let n = modulus.quo(g);
+        let lcm = m.mul(n.clone());
+        // Reducing the multiplier modulo n first keeps the numbers small.
+        let y = x.add(m.mul(s.mul(q).rem(n))).rem(lcm.clone());
+        solution = (y, lcm);
+    }
+    Ok(solution)
+}
+
+/// Pairwise coprime moduli *m*₁, …, *mₖ*, with what we need to solve any system of congruences
+/// modulo them quickly.
+///
+/// For each *i*, we store *Mᵢ* = *M*/*mᵢ*, where *M* = *m*₁ ⋯ *mₖ*, and the inverse *cᵢ* of *Mᵢ*
+/// modulo *mᵢ*. Then the solution to *x* ≡ *aᵢ* (mod *mᵢ*) is the sum of (*aᵢcᵢ* mod *mᵢ*) *Mᵢ*,
+/// modulo *M*, which avoids running Euclid's algorithm again. This is what multimodular algorithms
+/// do after computing the same thing modulo several primes.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::ringlike::crt::CrtBasis;
+///
+/// let basis = CrtBasis::new(&[3_i64, 5, 7]).unwrap();
+/// assert_eq!(*basis.modulus(), 105);
+/// assert_eq!(basis.reconstruct(&[2, 3, 2]), 23);
+/// assert_eq!(basis.reconstruct(&[1, 1, 1]), 1);
+/// assert_eq!(basis.residues(&52), vec![1, 2, 3]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrtBasis<R> {
+    moduli: Vec<R>,
+    modulus: R,
+    cofactors: Vec<R>,
+    inverses: Vec<R>,
+}
+
+impl<R: EuclideanDomain + Clone> CrtBasis<R> {
+    /// Precomputes the basis for the given moduli.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CrtError::ZeroModulus`] if a modulus is zero, or [`CrtError::NotCoprime`] for the
+    /// first pair of moduli which aren't coprime.
+    pub fn new(moduli: &[R]) -> Result<Self, CrtError> {
+        if let Some(index) = moduli.iter().position(|m| *m == R::ZERO) {
+            return Err(CrtError::ZeroModulus { index });
+        }
+        for (first, a) in moduli.iter().enumerate() {
+            for (second, b) in moduli.iter().enumerate().skip(first + 1) {
+                if !a.gcd(b.clone()).is_unit() {
+                    return Err(CrtError::NotCoprime { first, second });
+                }
+            }
+        }
+        let modulus = moduli.iter().fold(R::ONE, |acc, m| acc.mul(m.clone()));
+        let cofactors: Vec<R> = moduli.iter().map(|m| modulus.quo(m.clone())).collect();
+        let inverses = cofactors
+            .iter()
+            .zip(moduli)
+            .map(|(c, m)| {
+                // sc + tm = g for a unit g, so s/g is the inverse of c modulo m.
+                let (g, s, _) = c.extended_gcd(m.clone());
+                let unit = g.inverse_mul().expect("the moduli are coprime");
+                s.mul(unit).rem(m.clone())
+            })
+            .collect();
+        Ok(CrtBasis {
+            moduli: moduli.to_vec(),
+            modulus,
+            cofactors,
+            inverses,
+        })
+    }
+
+    /// The moduli *m*₁, …, *mₖ*.
+    pub fn moduli(&self) -> &[R] {
+        &self.moduli
+    }
+
+    /// Their product *M*, which solutions are unique modulo.
+    pub fn modulus(&self) -> &R {
+        &self.modulus
+    }
+
+    /// The solution, reduced modulo *M*, to *x* ≡ `residues[i]` (mod *mᵢ*) for every *i*.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't exactly one residue for each modulus.
+    pub fn reconstruct(&self, residues: &[R]) -> R {
+        assert_eq!(
+            residues.len(),
+            self.moduli.len(),
+            "there must be one residue for each modulus"
+        );
+        residues
+            .iter()
+            .zip(&self.moduli)
+            .zip(self.cofactors.iter().zip(&self.inverses))
+            .fold(R::ZERO, |acc, ((a, m), (cofactor, inverse))| {
+                let term = a.mul(inverse.clone()).rem(m.clone()).mul(cofactor.clone());
+                acc.add(term).rem(self.modulus.clone())
+            })
+    }
+
+    /// The residues of `x` modulo each *mᵢ*, the inverse of [`CrtBasis::reconstruct`].
+    pub fn residues(&self, x: &R) -> Vec<R> {
+        self.moduli.iter().map(|m| x.rem(m.clone())).collect()
+    }
+}
+
+impl<R: FactorisableField> Poly<R> {
+    /// The polynomial of least degree through the given points (*cᵢ*, *yᵢ*), found by solving
+    /// *f* ≡ *yᵢ* (mod *x* - *cᵢ*) with the Chinese remainder theorem. This is Lagrange
+    /// interpolation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::{crt::CrtError, polynomial::Poly, rational::Rational};
+    ///
+    /// let q = |n: i128| Rational::from(n);
+    /// let points = [(q(0), q(1)), (q(1), q(2)), (q(2), q(5))];
+    /// assert_eq!(Poly::interpolate(&points), Ok(Poly::new(vec![q(1), q(0), q(1)])));
+    /// // A repeated point is fine, but two different values at the same point aren't.
+    /// let points = [(q(3), q(1)), (q(3), q(1)), (q(3), q(2))];
+    /// assert_eq!(Poly::interpolate(&points), Err(CrtError::Incompatible { index: 2 }));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CrtError::Incompatible`] if two points have the same *c* but different *y*.
+    pub fn interpolate(points: &[(R, R)]) -> Result<Self, CrtError> {
+        let congruences: Vec<(Self, Self)> = points
+            .iter()
+            .map(|(c, y)| {
+                (
+                    Poly::constant(y.clone()),
+                    Poly::new(vec![c.inverse_add(), R::ONE]),
+                )
+            })
+            .collect();
+        crt(&congruences).map(|(f, _)| f)
+    }
+}
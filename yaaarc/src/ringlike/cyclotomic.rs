@@ -0,0 +1,588 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Cyclotomic fields, ℚ(ζ*ₙ*).
+//!
+//! A cyclotomic field is what we get by adjoining a primitive *n*-th root of unity ζ*ₙ* to the
+//! rationals. The number *n* is called the *conductor*. These fields are exactly what's needed to
+//! write down the character values of a finite group, which are sums of roots of unity.
+//!
+//! Rather than fixing *n* at the type level, every [`Cyclotomic`] remembers its own conductor.
+//! When two numbers with different conductors meet, they are both embedded into ℚ(ζ*ₘ*), where *m*
+//! is the least common multiple of the conductors, using ζ*ₙ* = ζ*ₘ*^(*m*/*n*). This means that
+//! all cyclotomic numbers live in one big field, so we can implement [`Field`] for them.
+
+use std::fmt;
+
+use crate::{
+    arithmetic::{gcd, lcm},
+    grouplike::{
+        AbelianGroup, CommutativeMagma, Group, Magma, Monoid, Quasigroup, Semigroup, UnitalMagma,
+    },
+    operators::{BinaryOperator, Plus, Times},
+    ringlike::{
+        factorisation::Factorisation, polynomial, rational::Rational, CommutativeRing,
+        DivisionRing, EuclideanDomain, Field, IntegralDomain, PrincipalIdealDomain, Ring,
+        UniqueFactorisationDomain,
+    },
+};
+
+/// An element of a cyclotomic field.
+///
+/// Internally, the number is written as *c*₀ + *c*₁ζ*ₙ* + ⋯ + *c*ₖζ*ₙ*^*k* with rational
+/// coefficients, where *k* < φ(*n*). The powers 1, ζ*ₙ*, …, ζ*ₙ*^(φ(*n*) - 1) form a basis of
+/// ℚ(ζ*ₙ*) over ℚ, so this representation is unique for a given conductor.
+///
+/// # Example
+///
+/// The cube roots of unity sum to zero.
+///
+/// ```rust
+/// use yaaarc::ringlike::{cyclotomic::Cyclotomic, rational::Rational};
+///
+/// let omega = Cyclotomic::zeta(3);
+/// let sum = Cyclotomic::from(Rational::from(1)) + omega.clone() + omega.pow(2);
+///
+/// assert_eq!(sum, Cyclotomic::from(Rational::from(0)));
+/// // Complex conjugation sends ζ₃ to ζ₃² = -1 - ζ₃.
+/// assert_eq!(omega.conjugate(), -Cyclotomic::from(Rational::from(1)) - omega);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cyclotomic {
+    conductor: usize,
+    /// The rational part, i.e. the coefficient of ζ⁰.
+    constant: Rational,
+    /// `coefficients[i]` is the coefficient of ζ^(i + 1). Trailing zeros are trimmed.
+    coefficients: Vec<Rational>,
+}
+
+/// The Möbius function.
+fn mobius(mut n: usize) -> i8 {
+    let mut result = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        result = -result;
+    }
+    result
+}
+
+/// The coefficients of the *n*-th cyclotomic polynomial Φ*ₙ*, lowest degree first.
+///
+/// We use Φ*ₙ*(*x*) = ∏ (*x*^*d* - 1)^μ(*n*/*d*), where the product ranges over the divisors *d*
+/// of *n*. Every division is exact, and since Φ*ₙ* is monic it has integer coefficients.
+pub(crate) fn cyclotomic_polynomial(n: usize) -> Vec<i128> {
+    assert!(n > 0, "there is no 0-th cyclotomic polynomial");
+    let divisors: Vec<usize> = (1..=n).filter(|d| n.is_multiple_of(*d)).collect();
+    let mut poly = vec![1];
+    for &d in divisors.iter().filter(|&&d| mobius(n / d) == 1) {
+        // Multiply by x^d - 1.
+        let mut product = vec![0; poly.len() + d];
+        for (i, &c) in poly.iter().enumerate() {
+            product[i + d] += c;
+            product[i] -= c;
+        }
+        poly = product;
+    }
+    for &d in divisors.iter().filter(|&&d| mobius(n / d) == -1) {
+        // Divide by x^d - 1. Working from the top, q[i] = p[i + d] + q[i + d].
+        let mut quotient = vec![0; poly.len() - d];
+        for i in (0..quotient.len()).rev() {
+            quotient[i] = poly[i + d] + quotient.get(i + d).copied().unwrap_or(0);
+        }
+        poly = quotient;
+    }
+    poly
+}
+
+impl Cyclotomic {
+    /// Creates the number Σ *cᵢ* ζ*ₙ*^*i*, where the *cᵢ* are given by `coefficients`.
+    ///
+    /// Any number of coefficients may be given; exponents are taken modulo *n* and the result is
+    /// then reduced to the canonical basis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `conductor` is zero.
+    pub fn new(conductor: usize, coefficients: Vec<Rational>) -> Self {
+        assert!(
+            conductor > 0,
+            "the conductor of a cyclotomic number must be positive"
+        );
+        let mut dense = vec![Rational::ZERO; conductor];
+        for (i, c) in coefficients.into_iter().enumerate() {
+            dense[i % conductor] += c;
+        }
+        Cyclotomic::from_dense(conductor, dense)
+    }
+
+    /// The primitive *n*-th root of unity ζ*ₙ* = *e*^(2π*i*/*n*).
+    pub fn zeta(n: usize) -> Self {
+        Cyclotomic::new(n, vec![Rational::ZERO, Rational::ONE])
+    }
+
+    /// The conductor *n* of the field ℚ(ζ*ₙ*) this number is currently written in.
+    ///
+    /// This isn't necessarily the smallest such field, though rational numbers always have
+    /// conductor 1.
+    pub fn conductor(&self) -> usize {
+        self.conductor
+    }
+
+    /// Whether this number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.constant.is_zero() && self.coefficients.is_empty()
+    }
+
+    /// Returns the number as a [`Rational`], if it is one.
+    pub fn to_rational(&self) -> Option<Rational> {
+        if self.coefficients.is_empty() {
+            Some(self.constant)
+        } else {
+            None
+        }
+    }
+
+    /// The coefficients of 1, ζ*ₙ*, ζ*ₙ*², … in the canonical basis of ℚ(ζ*ₙ*), where *n* is
+    /// [`Cyclotomic::conductor`]. Trailing zero coefficients are omitted.
+    pub fn coefficients(&self) -> Vec<Rational> {
+        if self.is_zero() {
+            Vec::new()
+        } else {
+            self.to_dense()
+        }
+    }
+
+    /// The complex conjugate, which sends ζ*ₙ* to ζ*ₙ*⁻¹.
+    pub fn conjugate(&self) -> Self {
+        self.galois_conjugate(self.conductor - 1)
+    }
+
+    /// Applies the Galois automorphism σ*ₖ* of ℚ(ζ*ₙ*), which sends ζ*ₙ* to ζ*ₙ*^*k*.
+    ///
+    /// # Panics
+    ///
+    /// Panics if *k* is not coprime to the conductor *n*, since then σ*ₖ* is not an automorphism.
+    pub fn galois_conjugate(&self, k: usize) -> Self {
+        let n = self.conductor;
+        assert!(gcd(k, n) == 1, "σ_{k} is not an automorphism of ℚ(ζ_{n})");
+        let mut dense = vec![Rational::ZERO; n];
+        for (i, c) in self.to_dense().into_iter().enumerate() {
+            dense[(i * k) % n] += c;
+        }
+        Cyclotomic::from_dense(n, dense)
+    }
+
+    /// Raises `self` to the power `exponent` by repeated squaring.
+    pub fn pow(&self, mut exponent: u64) -> Self {
+        let mut result = Cyclotomic::ONE;
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// The multiplicative inverse, or [`None`] if `self` is zero.
+    ///
+    /// The product of all Galois conjugates of *x* is its norm *N*(*x*), which is rational. So we
+    /// can compute *x*⁻¹ as the product of all the *other* conjugates divided by *N*(*x*).
+    pub fn recip(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        if let Some(q) = self.to_rational() {
+            return q.recip().map(Cyclotomic::from);
+        }
+        let n = self.conductor;
+        let others = (2..n)
+            .filter(|&k| gcd(k, n) == 1)
+            .fold(Cyclotomic::ONE, |acc, k| &acc * &self.galois_conjugate(k));
+        let norm = (self * &others)
+            .to_rational()
+            .expect("the norm of a cyclotomic number is rational");
+        Some(&others * &Cyclotomic::from(norm.recip()?))
+    }
+
+    /// The coefficients of ζ⁰, ζ¹, … as one vector.
+    fn to_dense(&self) -> Vec<Rational> {
+        let mut dense = Vec::with_capacity(self.coefficients.len() + 1);
+        dense.push(self.constant);
+        dense.extend_from_slice(&self.coefficients);
+        dense
+    }
+
+    /// Writes `self` as a (not necessarily reduced) vector of coefficients of ζ*ₘ*⁰, …, ζ*ₘ*^(*m*
+    /// - 1), where *m* is a multiple of the conductor.
+    fn lift(&self, m: usize) -> Vec<Rational> {
+        let step = m / self.conductor;
+        let mut dense = vec![Rational::ZERO; m];
+        for (i, c) in self.to_dense().into_iter().enumerate() {
+            dense[i * step] = c;
+        }
+        dense
+    }
+
+    /// Reduces Σ `dense[i]` ζ*ₙ*^*i* modulo Φ*ₙ*, producing the canonical representation.
+    fn from_dense(conductor: usize, mut dense: Vec<Rational>) -> Self {
+        let phi = cyclotomic_polynomial(conductor);
+        let degree = phi.len() - 1;
+        for i in (degree..dense.len()).rev() {
+            let c = dense[i];
+            if c.is_zero() {
+                continue;
+            }
+            for (j, &p) in phi.iter().enumerate() {
+                dense[i - degree + j] -= c * Rational::from(p);
+            }
+        }
+        dense.truncate(degree);
+        while dense.last().is_some_and(Rational::is_zero) {
+            dense.pop();
+        }
+        if dense.len() <= 1 {
+            return Cyclotomic::from(dense.first().copied().unwrap_or(Rational::ZERO));
+        }
+        let constant = dense.remove(0);
+        Cyclotomic {
+            conductor,
+            constant,
+            coefficients: dense,
+        }
+    }
+
+    fn plus(&self, rhs: &Self) -> Self {
+        let m = lcm(self.conductor, rhs.conductor);
+        let dense = self
+            .lift(m)
+            .into_iter()
+            .zip(rhs.lift(m))
+            .map(|(a, b)| a + b)
+            .collect();
+        Cyclotomic::from_dense(m, dense)
+    }
+
+    fn times(&self, rhs: &Self) -> Self {
+        let m = lcm(self.conductor, rhs.conductor);
+        let (a, b) = (self.lift(m), rhs.lift(m));
+        let mut dense = vec![Rational::ZERO; m];
+        for (i, x) in a.iter().enumerate().filter(|(_, x)| !x.is_zero()) {
+            for (j, y) in b.iter().enumerate().filter(|(_, y)| !y.is_zero()) {
+                dense[(i + j) % m] += *x * *y;
+            }
+        }
+        Cyclotomic::from_dense(m, dense)
+    }
+
+    fn negated(&self) -> Self {
+        Cyclotomic {
+            conductor: self.conductor,
+            constant: -self.constant,
+            coefficients: self.coefficients.iter().map(|&c| -c).collect(),
+        }
+    }
+}
+
+impl Default for Cyclotomic {
+    fn default() -> Self {
+        Cyclotomic::ZERO
+    }
+}
+
+impl From<Rational> for Cyclotomic {
+    fn from(q: Rational) -> Self {
+        Cyclotomic {
+            conductor: 1,
+            constant: q,
+            coefficients: Vec::new(),
+        }
+    }
+}
+
+impl From<i128> for Cyclotomic {
+    fn from(n: i128) -> Self {
+        Cyclotomic::from(Rational::from(n))
+    }
+}
+
+impl From<i64> for Cyclotomic {
+    fn from(n: i64) -> Self {
+        Cyclotomic::from(Rational::from(n))
+    }
+}
+
+impl From<i32> for Cyclotomic {
+    fn from(n: i32) -> Self {
+        Cyclotomic::from(Rational::from(n))
+    }
+}
+
+impl PartialEq for Cyclotomic {
+    fn eq(&self, other: &Self) -> bool {
+        if self.conductor == other.conductor {
+            self.constant == other.constant && self.coefficients == other.coefficients
+        } else {
+            (self - other).is_zero()
+        }
+    }
+}
+
+impl Eq for Cyclotomic {}
+
+impl fmt::Display for Cyclotomic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut first = true;
+        for (i, c) in self.to_dense().into_iter().enumerate() {
+            if c.is_zero() {
+                continue;
+            }
+            let magnitude = c.abs();
+            match (first, c < Rational::ZERO) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            first = false;
+            match (i, magnitude == Rational::ONE) {
+                (0, _) => write!(f, "{magnitude}")?,
+                (_, true) => write!(f, "ζ{}", self.conductor)?,
+                (_, false) => write!(f, "{magnitude}*ζ{}", self.conductor)?,
+            }
+            if i > 1 {
+                write!(f, "^{i}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::ops::Add<&Cyclotomic> for &Cyclotomic {
+    type Output = Cyclotomic;
+
+    fn add(self, rhs: &Cyclotomic) -> Self::Output {
+        self.plus(rhs)
+    }
+}
+
+impl std::ops::Add for Cyclotomic {
+    type Output = Cyclotomic;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.plus(&rhs)
+    }
+}
+
+impl std::ops::Sub<&Cyclotomic> for &Cyclotomic {
+    type Output = Cyclotomic;
+
+    fn sub(self, rhs: &Cyclotomic) -> Self::Output {
+        self.plus(&rhs.negated())
+    }
+}
+
+impl std::ops::Sub for Cyclotomic {
+    type Output = Cyclotomic;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.plus(&rhs.negated())
+    }
+}
+
+impl std::ops::Mul<&Cyclotomic> for &Cyclotomic {
+    type Output = Cyclotomic;
+
+    fn mul(self, rhs: &Cyclotomic) -> Self::Output {
+        self.times(rhs)
+    }
+}
+
+impl std::ops::Mul for Cyclotomic {
+    type Output = Cyclotomic;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.times(&rhs)
+    }
+}
+
+impl std::ops::Div for Cyclotomic {
+    type Output = Cyclotomic;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        self.times(&rhs.recip().expect("division by zero"))
+    }
+}
+
+impl std::ops::Neg for &Cyclotomic {
+    type Output = Cyclotomic;
+
+    fn neg(self) -> Self::Output {
+        self.negated()
+    }
+}
+
+impl std::ops::Neg for Cyclotomic {
+    type Output = Cyclotomic;
+
+    fn neg(self) -> Self::Output {
+        self.negated()
+    }
+}
+
+impl std::iter::Sum for Cyclotomic {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Cyclotomic::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl BinaryOperator<Plus> for Cyclotomic {
+    fn op(&self, rhs: Self) -> Self {
+        self.plus(&rhs)
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.plus(&rhs);
+    }
+}
+
+impl BinaryOperator<Times> for Cyclotomic {
+    fn op(&self, rhs: Self) -> Self {
+        self.times(&rhs)
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.times(&rhs);
+    }
+}
+
+impl Magma<Plus> for Cyclotomic {}
+impl Semigroup<Plus> for Cyclotomic {}
+impl UnitalMagma<Plus> for Cyclotomic {
+    const IDENTITY: Self = Cyclotomic::ZERO;
+}
+impl Quasigroup<Plus> for Cyclotomic {
+    fn inverse(&self) -> Self {
+        self.negated()
+    }
+}
+impl CommutativeMagma<Plus> for Cyclotomic {}
+impl AbelianGroup<Plus> for Cyclotomic {}
+
+impl Magma<Times> for Cyclotomic {}
+impl Semigroup<Times> for Cyclotomic {}
+impl UnitalMagma<Times> for Cyclotomic {
+    const IDENTITY: Self = Cyclotomic::ONE;
+}
+/// # Panics
+///
+/// [`Quasigroup::inverse`] panics if called on zero.
+impl Quasigroup<Times> for Cyclotomic {
+    fn inverse(&self) -> Self {
+        self.recip().expect("zero has no multiplicative inverse")
+    }
+}
+impl CommutativeMagma<Times> for Cyclotomic {}
+impl Monoid<Times> for Cyclotomic {}
+impl Group<Times> for Cyclotomic {}
+
+impl Ring for Cyclotomic {
+    const ZERO: Self = Cyclotomic {
+        conductor: 1,
+        constant: Rational::ZERO,
+        coefficients: Vec::new(),
+    };
+    const ONE: Self = Cyclotomic {
+        conductor: 1,
+        constant: Rational::ONE,
+        coefficients: Vec::new(),
+    };
+
+    fn left_inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+
+    fn right_inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+
+    fn inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+}
+
+impl CommutativeRing for Cyclotomic {
+    /// Uses Toom-3, or Karatsuba in characteristic 2 or 3.
+    fn mul_polynomials(a: &[Self], b: &[Self]) -> Vec<Self> {
+        polynomial::field_product(a, b)
+    }
+}
+
+impl DivisionRing for Cyclotomic {
+    fn div_right(&self, rhs: Self) -> Self {
+        self.clone() / rhs
+    }
+
+    fn div_left(&self, rhs: Self) -> Self {
+        self.clone() / rhs
+    }
+}
+
+impl IntegralDomain for Cyclotomic {
+    fn associates(&self, rhs: Self) -> bool {
+        self.is_zero() == rhs.is_zero()
+    }
+}
+
+impl UniqueFactorisationDomain for Cyclotomic {
+    /// Every nonzero element is a unit, so it has no irreducible factors.
+    fn factor(&self) -> Factorisation<Self> {
+        assert!(!self.is_zero(), "zero has no factorisation");
+        Factorisation::new(self.clone(), Vec::new())
+    }
+}
+impl PrincipalIdealDomain for Cyclotomic {}
+
+impl EuclideanDomain for Cyclotomic {
+    fn valuation(&self) -> usize {
+        0
+    }
+
+    /// Division is exact in a field, so the remainder is always zero.
+    fn div_rem(&self, rhs: Self) -> (Self, Self) {
+        (self.clone() / rhs, Self::ZERO)
+    }
+}
+
+impl Field for Cyclotomic {
+    fn div(&self, rhs: Self) -> Self {
+        self.div_right(rhs)
+    }
+}
@@ -0,0 +1,1177 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Factorising polynomials, and the [`Factorisation`] type.
+//!
+//! Over a finite field 𝔽*_q*, every nonzero polynomial is a unit times a product of monic
+//! irreducible polynomials, and we can actually find them. The usual pipeline has three stages:
+//!
+//! 1. square-free factorisation, which splits off repeated factors using the derivative;
+//! 2. distinct-degree factorisation, which groups the irreducible factors by degree, since the
+//!    irreducible factors of *x*^(*qᵈ*) - *x* are exactly those whose degree divides *d*;
+//! 3. equal-degree factorisation, which splits a product of irreducibles of the same degree with
+//!    the randomised method of Cantor and Zassenhaus.
+//!
+//! Berlekamp's algorithm is an alternative to the last two stages. It uses linear algebra over
+//! 𝔽*_q* instead of randomness, but it tries every element of the field, so it's only sensible for
+//! small fields.
+//!
+//! Over ℤ and ℚ, we reduce to the finite field case with the Berlekamp–Zassenhaus algorithm. We
+//! factorise a square-free polynomial modulo a small prime *p*, lift the factors to factors modulo
+//! a large power *pᵏ* with Hensel's lemma, and then try products of subsets of the lifted factors
+//! until we find the true factors over ℤ. The last step can take exponential time in the worst
+//! case, which van Hoeij's lattice reduction method avoids, but it's fast for almost every
+//! polynomial in practice. As with [`super::rational`], the arithmetic is in [`i128`], whichever
+//! integer type the coefficients have, and we panic rather than wrap around if the coefficients
+//! (or *pᵏ*) get too big.
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{finite_field::PrimeField, polynomial::Poly, UniqueFactorisationDomain};
+//!
+//! type F5 = PrimeField<5>;
+//! let poly = |c: &[i128]| Poly::new(c.iter().map(|&n| F5::new(n)).collect());
+//!
+//! // x⁵ - x is the product of x - a over every a in 𝔽₅.
+//! let factors = poly(&[0, -1, 0, 0, 0, 1]).factor();
+//! assert_eq!(factors.factors().len(), 5);
+//! assert!(factors.factors().iter().all(|(f, m)| f.degree() == Some(1) && *m == 1));
+//!
+//! // 2(x² + 2)²(x + 1) over 𝔽₅, where x² + 2 is irreducible since -2 isn't a square mod 5.
+//! let f = &poly(&[2, 2]) * &poly(&[2, 0, 1]).pow(2);
+//! let factors = f.factor();
+//! assert_eq!(factors.unit(), &poly(&[2]));
+//! assert_eq!(factors.factors(), [(poly(&[1, 1]), 1), (poly(&[2, 0, 1]), 2)]);
+//! assert_eq!(f.factor_berlekamp(), factors);
+//! assert_eq!(factors.product(), f);
+//! assert!(poly(&[2, 0, 1]).is_irreducible());
+//! assert_eq!(f.squarefree_part(), &poly(&[1, 1]) * &poly(&[2, 0, 1]));
+//!
+//! // 6(x⁸ - 1) over the integers. Here x⁴ + 1 is irreducible, even though it factorises modulo
+//! // every prime, so lifting from a prime isn't enough on its own.
+//! let g = Poly::new(vec![-6_i128, 0, 0, 0, 0, 0, 0, 0, 6]);
+//! let factors = g.factor();
+//! assert_eq!(factors.unit(), &Poly::constant(1));
+//! assert_eq!(
+//!     factors.factors(),
+//!     [
+//!         (Poly::constant(2), 1),
+//!         (Poly::constant(3), 1),
+//!         (Poly::new(vec![-1, 1]), 1),
+//!         (Poly::new(vec![1, 1]), 1),
+//!         (Poly::new(vec![1, 0, 1]), 1),
+//!         (Poly::new(vec![1, 0, 0, 0, 1]), 1),
+//!     ]
+//! );
+//! let h = &Poly::new(vec![4_i128, 2]).pow(3) * &Poly::new(vec![1, 0, 1]);
+//! assert_eq!(h.squarefree_part(), Poly::new(vec![2, 1, 2, 1]).scale(&2));
+//!
+//! // Every prime below 100 divides the discriminant of x² - 2 · 3 · 5 ⋯ 97, so it isn't
+//! // square-free modulo any of them, but it's still irreducible.
+//! let primorial = Poly::new(vec![-2305567963945518424753102147331756070_i128, 0, 1]);
+//! assert!(primorial.is_irreducible());
+//! assert_eq!(primorial.factor().factors(), [(primorial.clone(), 1)]);
+//!
+//! // The narrower integer types work the same way.
+//! let k = Poly::new(vec![-1_i32, 0, 0, 1]);
+//! assert_eq!(
+//!     k.factor().factors(),
+//!     [(Poly::new(vec![-1, 1]), 1), (Poly::new(vec![1, 1, 1]), 1)]
+//! );
+//! ```
+
+use std::cmp::Ordering;
+
+use crate::{
+    arithmetic::{inverse_mod, mul_mod},
+    ringlike::{
+        finite_field::{FiniteField, PrimeField},
+        polynomial::Poly,
+        rational::Rational,
+        CommutativeRing, Field, GCDDomain, Ring, UniqueFactorisationDomain,
+    },
+};
+
+/// A factorisation *u* *p*₁^*e*₁ ⋯ *pₖ*^*eₖ* of a nonzero element of a unique factorisation
+/// domain, into a unit *u* and irreducible elements *pᵢ* with multiplicities *eᵢ* > 0.
+///
+/// Irreducible elements are only unique up to associates, so each implementation of
+/// [`UniqueFactorisationDomain::factor`] picks one representative of each: positive primes in ℤ,
+/// and monic polynomials over a field, for example. The factors are pairwise non-associate and
+/// sorted.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::ringlike::UniqueFactorisationDomain;
+///
+/// let factors = (-360_i64).factor();
+/// assert_eq!(*factors.unit(), -1);
+/// assert_eq!(factors.factors(), [(2, 3), (3, 2), (5, 1)]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Factorisation<R> {
+    unit: R,
+    factors: Vec<(R, usize)>,
+}
+
+impl<R> Factorisation<R> {
+    /// The factorisation `unit` times the product of the `factors` to the power of their
+    /// multiplicities. The caller is responsible for them actually being a unit and irreducible.
+    pub fn new(unit: R, factors: Vec<(R, usize)>) -> Self {
+        Factorisation { unit, factors }
+    }
+
+    /// The unit *u*.
+    pub fn unit(&self) -> &R {
+        &self.unit
+    }
+
+    /// The irreducible factors with their multiplicities.
+    pub fn factors(&self) -> &[(R, usize)] {
+        &self.factors
+    }
+
+    /// The unit and the factors.
+    pub fn into_parts(self) -> (R, Vec<(R, usize)>) {
+        (self.unit, self.factors)
+    }
+
+    /// Iterates over the factors with their multiplicities.
+    pub fn iter(&self) -> std::slice::Iter<'_, (R, usize)> {
+        self.factors.iter()
+    }
+}
+
+impl<R: Ring + Clone> Factorisation<R> {
+    /// Multiplies the factorisation back out, giving the element it came from.
+    pub fn product(&self) -> R {
+        self.factors.iter().fold(self.unit.clone(), |acc, (p, e)| {
+            (0..*e).fold(acc, |acc, _| acc.mul(p.clone()))
+        })
+    }
+}
+
+impl<R: UniqueFactorisationDomain + Clone + PartialEq> Factorisation<R> {
+    /// Puts a factorisation built by hand into the form [`UniqueFactorisationDomain::factor`]
+    /// gives, without changing its product.
+    ///
+    /// The unit and each factor are factorised again, so units are absorbed into the unit,
+    /// anything reducible is split up, and associates are replaced by the same representative and
+    /// merged. The factors are then in order of first appearance.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::factorisation::Factorisation;
+    ///
+    /// // 2 × (-3)² × 6 × (-1) = -108 = -(2² × 3³)
+    /// let factors = Factorisation::new(2_i32, vec![(-3, 2), (6, 1), (-1, 1)]).normalise();
+    /// assert_eq!(factors, Factorisation::new(-1, vec![(2, 2), (3, 3)]));
+    /// assert_eq!(factors.product(), -108);
+    /// ```
+    pub fn normalise(self) -> Self {
+        let mut unit = R::ONE;
+        let mut factors: Vec<(R, usize)> = Vec::new();
+        for (element, e) in std::iter::once((self.unit, 1)).chain(self.factors) {
+            let (u, parts) = element.factor().into_parts();
+            for _ in 0..e {
+                unit = unit.mul(u.clone());
+            }
+            for (p, k) in parts {
+                match factors.iter_mut().find(|(q, _)| *q == p) {
+                    Some((_, m)) => *m += k * e,
+                    None => factors.push((p, k * e)),
+                }
+            }
+        }
+        Factorisation::new(unit, factors)
+    }
+}
+
+impl<R> IntoIterator for Factorisation<R> {
+    type Item = (R, usize);
+    type IntoIter = std::vec::IntoIter<(R, usize)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.factors.into_iter()
+    }
+}
+
+impl<'a, R> IntoIterator for &'a Factorisation<R> {
+    type Item = &'a (R, usize);
+    type IntoIter = std::slice::Iter<'a, (R, usize)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.factors.iter()
+    }
+}
+
+/// A field *K* over which we can factorise univariate polynomials.
+///
+/// There are fields where no algorithm can factorise polynomials, so we can't do this for every
+/// field. *K*\[*x*\] is always a Euclidean domain, but we only implement
+/// [`UniqueFactorisationDomain`] for it, and the traits which build on it, if *K* implements this
+/// trait. Every [`FiniteField`] does, as does [`Rational`].
+pub trait FactorisableField: Field + Clone + PartialEq {
+    /// Factorises a nonzero polynomial into its leading coefficient (as a constant) and monic
+    /// irreducible factors, sorted by degree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` is zero.
+    fn factor_polynomial(f: &Poly<Self>) -> Factorisation<Poly<Self>>;
+
+    /// Whether `f` is irreducible. By default this factorises `f`, but there may be faster tests.
+    fn is_irreducible_polynomial(f: &Poly<Self>) -> bool {
+        !f.is_zero() && {
+            let factors = Self::factor_polynomial(f);
+            matches!(factors.factors(), [(_, 1)])
+        }
+    }
+
+    /// The *p*-th root of `a`, where *p* is the characteristic. It exists because finite fields
+    /// are perfect.
+    ///
+    /// # Panics
+    ///
+    /// Panics in characteristic zero.
+    fn pth_root(a: &Self) -> Self;
+}
+
+impl<F: FiniteField> FactorisableField for F {
+    fn factor_polynomial(f: &Poly<F>) -> Factorisation<Poly<F>> {
+        f.factor_with(Poly::cantor_zassenhaus)
+    }
+
+    /// Uses [Rabin's test](Poly::rabin_test), which doesn't need to factorise `f`.
+    fn is_irreducible_polynomial(f: &Poly<F>) -> bool {
+        f.rabin_test()
+    }
+
+    /// The inverse of the Frobenius map *a* ↦ *aᵖ* on 𝔽*_q* is *a* ↦ *a*^(*q*/*p*).
+    fn pth_root(a: &F) -> F {
+        a.power(F::ORDER / u128::from(F::CHARACTERISTIC))
+    }
+}
+
+impl FactorisableField for Rational {
+    /// Clears denominators and factorises over ℤ. By Gauss's lemma, the irreducible factors over ℤ
+    /// of positive degree are irreducible over ℚ too.
+    fn factor_polynomial(f: &Poly<Rational>) -> Factorisation<Poly<Rational>> {
+        assert!(!f.is_zero(), "zero has no factorisation");
+        let denominator = f.coefficients().iter().fold(1_i128, |acc, c| {
+            let d = c.denominator();
+            checked(acc.checked_mul(d / acc.gcd(d)))
+        });
+        let integral = Poly::new(
+            f.coefficients()
+                .iter()
+                .map(|c| checked(c.numerator().checked_mul(denominator / c.denominator())))
+                .collect(),
+        );
+        let factors = factor_integer_polynomial(&integral)
+            .into_parts()
+            .1
+            .into_iter()
+            .filter(|(g, _)| g.degree() != Some(0))
+            .map(|(g, m)| {
+                let g = Poly::new(g.coefficients().into_iter().map(Rational::from).collect());
+                (g.monic(), m)
+            })
+            .collect();
+        Factorisation::new(Poly::constant(f.leading_coefficient()), factors)
+    }
+
+    fn pth_root(_: &Rational) -> Rational {
+        panic!("ℚ has characteristic zero")
+    }
+}
+
+/// Unwraps the result of checked arithmetic.
+fn checked<T>(x: Option<T>) -> T {
+    x.expect("integer arithmetic overflowed")
+}
+
+/// A small deterministic pseudorandom generator (SplitMix64), so that factorisations are
+/// reproducible.
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly random element of `F`, up to a negligible bias.
+    fn element<F: FiniteField>(&mut self) -> F {
+        let n = (u128::from(self.next()) << 64) | u128::from(self.next());
+        F::from_index(n % F::ORDER)
+    }
+}
+
+/// Orders polynomials by degree, and then by their coefficients from the top down, each ordered by
+/// `key`.
+fn compare<R: CommutativeRing + Clone + PartialEq, K: Ord>(
+    a: &Poly<R>,
+    b: &Poly<R>,
+    key: impl Fn(&R) -> K,
+) -> Ordering {
+    a.degree().cmp(&b.degree()).then_with(|| {
+        let keys = |f: &Poly<R>| f.coefficients().iter().rev().map(&key).collect::<Vec<_>>();
+        keys(a).cmp(&keys(b))
+    })
+}
+
+/// The prime factors of `n`, by trial division.
+fn prime_factors(mut n: usize) -> Vec<usize> {
+    let mut factors = Vec::new();
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            factors.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+/// A basis of the null space of `matrix`, found by Gaussian elimination.
+fn null_space<F: Field + Clone + PartialEq>(
+    mut matrix: Vec<Vec<F>>,
+    columns: usize,
+) -> Vec<Vec<F>> {
+    let mut pivots = Vec::new();
+    let mut row = 0;
+    for column in 0..columns {
+        let Some(pivot) = (row..matrix.len()).find(|&i| matrix[i][column] != F::ZERO) else {
+            continue;
+        };
+        matrix.swap(row, pivot);
+        let unit = F::ONE.div(matrix[row][column].clone());
+        for entry in &mut matrix[row] {
+            *entry = entry.mul(unit.clone());
+        }
+        for i in 0..matrix.len() {
+            if i != row && matrix[i][column] != F::ZERO {
+                let factor = matrix[i][column].clone();
+                let pivot_row = matrix[row].clone();
+                for (entry, p) in matrix[i].iter_mut().zip(pivot_row) {
+                    *entry = entry.sub(factor.mul(p));
+                }
+            }
+        }
+        pivots.push(column);
+        row += 1;
+    }
+    (0..columns)
+        .filter(|column| !pivots.contains(column))
+        .map(|free| {
+            let mut vector = vec![F::ZERO; columns];
+            vector[free] = F::ONE;
+            for (i, &pivot) in pivots.iter().enumerate() {
+                vector[pivot] = matrix[i][free].inverse_add();
+            }
+            vector
+        })
+        .collect()
+}
+
+impl<F: FiniteField> Poly<F> {
+    /// Whether no irreducible polynomial divides `self` more than once. Over a finite field, this
+    /// happens exactly when `self` is coprime to its derivative.
+    pub fn is_squarefree(&self) -> bool {
+        !self.is_zero() && self.gcd(self.derivative()).degree() == Some(0)
+    }
+
+    /// The *p*-th root of a polynomial whose derivative is zero, where *p* is the characteristic.
+    /// Such a polynomial only has terms *cxᵏᵖ*, and the Frobenius map *a* ↦ *aᵖ* is invertible on
+    /// 𝔽*_q*, with inverse *a* ↦ *a*^(*q*/*p*).
+    fn pth_root(&self) -> Self {
+        let p = F::CHARACTERISTIC as usize;
+        Poly::new(
+            self.coefficients()
+                .into_iter()
+                .step_by(p)
+                .map(|c| F::pth_root(&c))
+                .collect(),
+        )
+    }
+
+    /// Writes the monic part of `self` as *a*₁*a*₂² ⋯, where the *aᵢ* are square-free and pairwise
+    /// coprime. This returns the *aᵢ* which aren't 1 with their multiplicities *i*, in increasing
+    /// order of multiplicity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    pub fn squarefree_factorisation(&self) -> Vec<(Self, usize)> {
+        assert!(!self.is_zero(), "zero has no factorisation");
+        let mut factors = Vec::new();
+        let f = self.monic();
+        let mut c = f.gcd(f.derivative());
+        let mut w = f.div_rem(&c).0;
+        let mut i = 1;
+        while w.degree() != Some(0) {
+            let y = w.gcd(c.clone());
+            let factor = w.div_rem(&y).0;
+            if factor.degree() != Some(0) {
+                factors.push((factor, i));
+            }
+            c = c.div_rem(&y).0;
+            w = y;
+            i += 1;
+        }
+        if c.degree() != Some(0) {
+            // What's left only has factors whose multiplicity is a multiple of p.
+            let p = F::CHARACTERISTIC as usize;
+            factors.extend(
+                c.pth_root()
+                    .squarefree_factorisation()
+                    .into_iter()
+                    .map(|(factor, m)| (factor, m * p)),
+            );
+        }
+        factors.sort_by_key(|(_, m)| *m);
+        factors
+    }
+
+    /// Splits a monic square-free polynomial into the products of its irreducible factors of each
+    /// degree. This returns each product which isn't 1 with the degree *d* of its factors, in
+    /// increasing order of *d*.
+    ///
+    /// This uses the fact that *x*^(*qᵈ*) - *x* is the product of every monic irreducible
+    /// polynomial whose degree divides *d*.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    pub fn distinct_degree_factorisation(&self) -> Vec<(Self, usize)> {
+        assert!(!self.is_zero(), "zero has no factorisation");
+        let mut factors = Vec::new();
+        let mut f = self.monic();
+        let mut h = Poly::x().div_rem(&f).1;
+        let mut d = 1;
+        while f.degree().is_some_and(|n| n >= 2 * d) {
+            h = h.pow_mod(F::ORDER, &f);
+            let g = f.gcd(&h - &Poly::x());
+            if g.degree() != Some(0) {
+                f = f.div_rem(&g).0;
+                h = h.div_rem(&f).1;
+                factors.push((g, d));
+            }
+            d += 1;
+        }
+        if let Some(n) = f.degree().filter(|&n| n > 0) {
+            factors.push((f, n));
+        }
+        factors
+    }
+
+    /// Splits a monic square-free polynomial whose irreducible factors all have degree `degree`
+    /// into those factors, with the algorithm of Cantor and Zassenhaus. The factors are monic and
+    /// sorted.
+    ///
+    /// This picks random polynomials *a* and takes gcds with *a*^((*qᵈ* - 1)/2) - 1, which is
+    /// divisible by about half of the factors. In characteristic 2, it uses the trace
+    /// *a* + *a*² + ⋯ + *a*^(*qᵈ*/2) instead. The randomness is seeded, so the result is
+    /// reproducible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero or `degree` doesn't divide its degree. If the irreducible factors
+    /// don't all have degree `degree`, this may not return.
+    pub fn equal_degree_factorisation(&self, degree: usize) -> Vec<Self> {
+        let f = self.monic();
+        let n = f.degree().expect("zero has no factorisation");
+        assert!(
+            degree > 0 && n.is_multiple_of(degree),
+            "the degree of every factor must divide the degree"
+        );
+        let mut factors = vec![f.clone()];
+        let mut random = SplitMix(0x5EED);
+        while factors.len() < n / degree {
+            let a = Poly::new((0..n).map(|_| random.element()).collect());
+            let g = if F::CHARACTERISTIC == 2 {
+                let mut power = a.div_rem(&f).1;
+                let mut trace = power.clone();
+                for _ in 1..(F::ORDER.trailing_zeros() as usize) * degree {
+                    power = (&power * &power).div_rem(&f).1;
+                    trace = &trace + &power;
+                }
+                trace
+            } else {
+                // (qᵈ - 1)/2 = (q - 1)/2 ⋅ (1 + q + ⋯ + qᵈ⁻¹), which might not fit in a u128.
+                let mut power = a.pow_mod((F::ORDER - 1) / 2, &f);
+                let mut product = power.clone();
+                for _ in 1..degree {
+                    power = power.pow_mod(F::ORDER, &f);
+                    product = (&product * &power).div_rem(&f).1;
+                }
+                &product - &Poly::ONE
+            };
+            factors = factors
+                .into_iter()
+                .flat_map(|u| {
+                    if u.degree() == Some(degree) {
+                        return vec![u];
+                    }
+                    let v = u.gcd(g.clone());
+                    match v.degree() {
+                        Some(d) if d > 0 && Some(d) < u.degree() => {
+                            let w = u.div_rem(&v).0;
+                            vec![v, w]
+                        }
+                        _ => vec![u],
+                    }
+                })
+                .collect();
+        }
+        factors.sort_by(|a, b| compare(a, b, F::index));
+        factors
+    }
+
+    /// Rabin's irreducibility test, which [`UniqueFactorisationDomain::is_irreducible`] uses over
+    /// finite fields. A polynomial *f* of degree *n* > 0 is irreducible exactly when *f* divides
+    /// *x*^(*qⁿ*) - *x* but is coprime to *x*^(*q*^(*n*/*r*)) - *x* for every prime *r* dividing
+    /// *n*.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::{
+    ///     finite_field::PrimeField, polynomial::Poly, UniqueFactorisationDomain,
+    /// };
+    ///
+    /// type F2 = PrimeField<2>;
+    /// let poly = |c: &[i128]| Poly::new(c.iter().map(|&n| F2::new(n)).collect());
+    ///
+    /// // x⁴ + x + 1 is irreducible over 𝔽₂, but x⁴ + x² + 1 = (x² + x + 1)² isn't.
+    /// assert!(poly(&[1, 1, 0, 0, 1]).rabin_test());
+    /// assert!(!poly(&[1, 0, 1, 0, 1]).is_irreducible());
+    /// // Units aren't irreducible.
+    /// assert!(!poly(&[1]).rabin_test());
+    /// ```
+    pub fn rabin_test(&self) -> bool {
+        let Some(n) = self.degree().filter(|&n| n > 0) else {
+            return false;
+        };
+        let f = self.monic();
+        // frobenius[k] is x^(qᵏ) mod f.
+        let mut frobenius = vec![Poly::x().div_rem(&f).1];
+        for k in 0..n {
+            let next = frobenius[k].pow_mod(F::ORDER, &f);
+            frobenius.push(next);
+        }
+        frobenius[n] == frobenius[0]
+            && prime_factors(n)
+                .into_iter()
+                .all(|r| f.gcd(&frobenius[n / r] - &Poly::x()).degree() == Some(0))
+    }
+
+    /// The irreducible factors of a monic square-free polynomial, by distinct-degree and then
+    /// equal-degree factorisation.
+    fn cantor_zassenhaus(&self) -> Vec<Self> {
+        self.distinct_degree_factorisation()
+            .into_iter()
+            .flat_map(|(g, d)| g.equal_degree_factorisation(d))
+            .collect()
+    }
+
+    /// Factorises `self` like [`UniqueFactorisationDomain::factor`], but with Berlekamp's algorithm
+    /// in place of the distinct-degree and equal-degree stages.
+    ///
+    /// For a square-free *f*, the polynomials *v* of smaller degree with *v^q* ≡ *v* (mod *f*)
+    /// form a vector space whose dimension is the number of irreducible factors of *f*. We find a
+    /// basis by linear algebra, and then gcd(*f*, *v* - *s*) for *s* in 𝔽*_q* splits *f*. This
+    /// takes time proportional to *q*, so it's only practical over small fields.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    pub fn factor_berlekamp(&self) -> Factorisation<Self> {
+        self.factor_with(Poly::berlekamp)
+    }
+
+    /// The irreducible factors of a monic square-free polynomial, by Berlekamp's algorithm.
+    fn berlekamp(&self) -> Vec<Self> {
+        let n = self.degree().expect("zero has no factorisation");
+        // The rows of Q are x^(iq) mod f. We want v with vQ = v, i.e. the null space of (Q - I)ᵀ.
+        let xq = Poly::x().pow_mod(F::ORDER, self);
+        let mut rows = Vec::with_capacity(n);
+        let mut power = Poly::ONE;
+        for _ in 0..n {
+            rows.push(power.clone());
+            power = (&power * &xq).div_rem(self).1;
+        }
+        let matrix = (0..n)
+            .map(|j| {
+                (0..n)
+                    .map(|i| {
+                        let entry = rows[i].coefficient(j);
+                        if i == j {
+                            entry.sub(F::ONE)
+                        } else {
+                            entry
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let basis = null_space(matrix, n);
+        let count = basis.len();
+        let mut factors = vec![self.clone()];
+        for v in basis.into_iter().map(Poly::new) {
+            if factors.len() == count {
+                break;
+            }
+            if v.degree().is_none_or(|d| d == 0) {
+                continue;
+            }
+            factors = factors
+                .into_iter()
+                .flat_map(|u| {
+                    if u.degree() == Some(1) {
+                        return vec![u];
+                    }
+                    let mut parts = Vec::new();
+                    let mut rest = u;
+                    for s in F::elements() {
+                        let g = rest.gcd(&v - &Poly::constant(s));
+                        if g.degree().is_some_and(|d| d > 0) {
+                            rest = rest.div_rem(&g).0;
+                            parts.push(g);
+                        }
+                        if rest.degree() == Some(0) {
+                            break;
+                        }
+                    }
+                    if rest.degree() != Some(0) {
+                        parts.push(rest);
+                    }
+                    parts
+                })
+                .collect();
+        }
+        factors
+    }
+
+    /// Factorises `self` into a unit and monic irreducibles, given a way to split a monic
+    /// square-free polynomial into irreducibles.
+    fn factor_with(&self, split: impl Fn(&Self) -> Vec<Self>) -> Factorisation<Self> {
+        assert!(!self.is_zero(), "zero has no factorisation");
+        let mut factors: Vec<(Self, usize)> = self
+            .squarefree_factorisation()
+            .into_iter()
+            .flat_map(|(f, m)| split(&f).into_iter().map(move |g| (g, m)))
+            .collect();
+        factors.sort_by(|(a, _), (b, _)| compare(a, b, F::index));
+        Factorisation::new(Poly::constant(self.leading_coefficient()), factors)
+    }
+}
+
+/// A function which works modulo a particular prime, monomorphised for each prime we try.
+type Modular<T> = fn(&Poly<i128>) -> T;
+
+/// Counts the factors modulo a prime, and factorises by lifting from that prime.
+type Strategy = (Modular<Option<usize>>, Modular<Vec<Poly<i128>>>);
+
+/// The primes we try to factorise modulo, with the functions which do it.
+const MODULAR: [Strategy; 25] = [
+    (modular_count::<2>, zassenhaus::<2>),
+    (modular_count::<3>, zassenhaus::<3>),
+    (modular_count::<5>, zassenhaus::<5>),
+    (modular_count::<7>, zassenhaus::<7>),
+    (modular_count::<11>, zassenhaus::<11>),
+    (modular_count::<13>, zassenhaus::<13>),
+    (modular_count::<17>, zassenhaus::<17>),
+    (modular_count::<19>, zassenhaus::<19>),
+    (modular_count::<23>, zassenhaus::<23>),
+    (modular_count::<29>, zassenhaus::<29>),
+    (modular_count::<31>, zassenhaus::<31>),
+    (modular_count::<37>, zassenhaus::<37>),
+    (modular_count::<41>, zassenhaus::<41>),
+    (modular_count::<43>, zassenhaus::<43>),
+    (modular_count::<47>, zassenhaus::<47>),
+    (modular_count::<53>, zassenhaus::<53>),
+    (modular_count::<59>, zassenhaus::<59>),
+    (modular_count::<61>, zassenhaus::<61>),
+    (modular_count::<67>, zassenhaus::<67>),
+    (modular_count::<71>, zassenhaus::<71>),
+    (modular_count::<73>, zassenhaus::<73>),
+    (modular_count::<79>, zassenhaus::<79>),
+    (modular_count::<83>, zassenhaus::<83>),
+    (modular_count::<89>, zassenhaus::<89>),
+    (modular_count::<97>, zassenhaus::<97>),
+];
+
+/// Larger primes to fall back on if every prime in [`MODULAR`] divides the leading coefficient or
+/// the discriminant, as they do for *x*² - 2 · 3 · 5 ⋯ 97. They're not much bigger than they need
+/// to be, so that the powers we lift to can still get close to the bound.
+const FALLBACK: [Strategy; 12] = [
+    (modular_count::<65521>, zassenhaus::<65521>),
+    (modular_count::<65519>, zassenhaus::<65519>),
+    (modular_count::<65497>, zassenhaus::<65497>),
+    (modular_count::<65479>, zassenhaus::<65479>),
+    (modular_count::<65449>, zassenhaus::<65449>),
+    (modular_count::<65447>, zassenhaus::<65447>),
+    (modular_count::<65437>, zassenhaus::<65437>),
+    (modular_count::<65423>, zassenhaus::<65423>),
+    (modular_count::<65419>, zassenhaus::<65419>),
+    (modular_count::<65413>, zassenhaus::<65413>),
+    (modular_count::<65407>, zassenhaus::<65407>),
+    (modular_count::<65393>, zassenhaus::<65393>),
+];
+
+/// How many suitable primes we compare before settling on the one with the fewest factors.
+const PRIMES_TO_TRY: usize = 5;
+
+/// Factorises a nonzero polynomial over ℤ. See [`UniqueFactorisationDomain::factor`].
+pub(crate) fn factor_integer_polynomial(f: &Poly<i128>) -> Factorisation<Poly<i128>> {
+    assert!(!f.is_zero(), "zero has no factorisation");
+    let unit = f.leading_coefficient().signum();
+    let mut factors: Vec<(Poly<i128>, usize)> = f
+        .content()
+        .factor()
+        .into_parts()
+        .1
+        .into_iter()
+        .map(|(p, e)| (Poly::constant(p), e))
+        .collect();
+    if f.degree() != Some(0) {
+        for (g, m) in squarefree_decomposition(&normalised(f)) {
+            factors.extend(factor_squarefree(&g).into_iter().map(|h| (h, m)));
+        }
+    }
+    factors.sort_by(|(a, _), (b, _)| compare(a, b, |&c| c));
+    Factorisation::new(Poly::constant(unit), factors)
+}
+
+/// The square-free part of a nonzero polynomial over ℤ. See
+/// [`UniqueFactorisationDomain::squarefree_part`].
+pub(crate) fn squarefree_part_integer_polynomial(f: &Poly<i128>) -> Poly<i128> {
+    assert!(!f.is_zero(), "zero has no factorisation");
+    let content = Poly::constant(f.content().squarefree_part());
+    if f.degree() == Some(0) {
+        return content;
+    }
+    squarefree_decomposition(&normalised(f))
+        .into_iter()
+        .fold(content, |acc, (g, _)| &acc * &g)
+}
+
+/// The primitive part with a positive leading coefficient.
+fn normalised(f: &Poly<i128>) -> Poly<i128> {
+    let g = f.primitive_part();
+    if g.leading_coefficient() < 0 {
+        -g
+    } else {
+        g
+    }
+}
+
+/// `f` / `g` in ℤ\[*x*\], or [`None`] if `g` doesn't divide `f` or the division overflows.
+fn divide_exact(f: &Poly<i128>, g: &Poly<i128>) -> Option<Poly<i128>> {
+    let n = g.degree()?;
+    let Some(m) = f.degree() else {
+        return Some(Poly::ZERO);
+    };
+    let divisor = g.coefficients();
+    let lead = divisor[n];
+    let mut remainder = f.coefficients();
+    let mut quotient = vec![0; m.checked_sub(n)? + 1];
+    for i in (0..=m - n).rev() {
+        let c = remainder[i + n];
+        if c.checked_rem(lead)? != 0 {
+            return None;
+        }
+        quotient[i] = c.checked_div(lead)?;
+        for (r, &d) in remainder[i..].iter_mut().zip(&divisor) {
+            *r = r.checked_sub(quotient[i].checked_mul(d)?)?;
+        }
+    }
+    remainder
+        .iter()
+        .all(|&c| c == 0)
+        .then(|| Poly::new(quotient))
+}
+
+/// `f` / `g` in ℤ\[*x*\], where we know that `g` divides `f`.
+fn divide(f: &Poly<i128>, g: &Poly<i128>) -> Poly<i128> {
+    checked(divide_exact(f, g))
+}
+
+/// Large primes for computing gcds in ℤ\[*x*\], with functions which compute the monic gcd of
+/// the images modulo each one.
+const GCD_PRIMES: [(u64, Modular2<Option<Vec<u64>>>); 8] = [
+    (4611686018427387847, gcd_image::<4611686018427387847>),
+    (4611686018427387817, gcd_image::<4611686018427387817>),
+    (4611686018427387787, gcd_image::<4611686018427387787>),
+    (4611686018427387761, gcd_image::<4611686018427387761>),
+    (4611686018427387751, gcd_image::<4611686018427387751>),
+    (4611686018427387737, gcd_image::<4611686018427387737>),
+    (4611686018427387733, gcd_image::<4611686018427387733>),
+    (4611686018427387709, gcd_image::<4611686018427387709>),
+];
+
+/// A function of two polynomials which works modulo a particular prime.
+type Modular2<T> = fn(&Poly<i128>, &Poly<i128>) -> T;
+
+/// The monic gcd of the images of `f` and `g` modulo `P`, or [`None`] if `P` divides either
+/// leading coefficient.
+fn gcd_image<const P: u64>(f: &Poly<i128>, g: &Poly<i128>) -> Option<Vec<u64>> {
+    let (a, b) = (reduce_unchecked::<P>(f), reduce_unchecked::<P>(g));
+    (a.degree() == f.degree() && b.degree() == g.degree()).then(|| {
+        a.gcd(b)
+            .coefficients()
+            .iter()
+            .map(PrimeField::value)
+            .collect()
+    })
+}
+
+/// The gcd of the primitive parts, as a primitive polynomial with a positive leading coefficient.
+///
+/// Euclid's algorithm over ℤ makes the coefficients grow far too quickly for [`i128`]s, so we use
+/// the modular method instead. The gcd divides `f`, so Mignotte's bound limits its coefficients.
+/// We compute the gcd modulo large primes, combine the images by the Chinese remainder theorem
+/// until the modulus exceeds twice the bound, and check the result by division. A prime is
+/// unlucky if the gcd modulo it has too large a degree, but only finitely many primes are.
+fn primitive_gcd(f: &Poly<i128>, g: &Poly<i128>) -> Poly<i128> {
+    let (f, g) = (normalised(f), normalised(g));
+    if g.is_zero() {
+        return f;
+    }
+    if f.is_zero() {
+        return g;
+    }
+    let lead = f.leading_coefficient().gcd(g.leading_coefficient());
+    let smaller = if f.degree() <= g.degree() { &f } else { &g };
+    let bound = checked(mignotte_bound(smaller).checked_mul(lead.unsigned_abs()));
+    // The images of lead × gcd of the smallest degree so far, and their modulus.
+    let mut images: Option<(Vec<u128>, u128)> = None;
+    for (p, image) in GCD_PRIMES {
+        let Some(monic) = image(&f, &g) else {
+            continue;
+        };
+        let p = u128::from(p);
+        let scaled: Vec<u128> = monic
+            .iter()
+            .map(|&c| mul_mod(u128::from(c), lead as u128 % p, p))
+            .collect();
+        images = match &images {
+            Some((residues, _)) if residues.len() < scaled.len() => continue,
+            Some((residues, modulus)) if residues.len() == scaled.len() => {
+                let product = checked(modulus.checked_mul(p));
+                Some((crt(residues, *modulus, &scaled, p), product))
+            }
+            _ => Some((scaled, p)),
+        };
+        let (residues, modulus) = images.as_ref().expect("just set");
+        if *modulus > bound && *modulus <= i128::MAX as u128 {
+            let candidate = normalised(&symmetric(residues, *modulus));
+            if divide_exact(&f, &candidate).is_some() && divide_exact(&g, &candidate).is_some() {
+                return candidate;
+            }
+        }
+    }
+    panic!("integer arithmetic overflowed");
+}
+
+/// Twice Mignotte's bound 2ⁿ ‖`f`‖₁ on the coefficients of any factor of `f`, so that a factor is
+/// determined by its residues modulo anything larger.
+fn mignotte_bound(f: &Poly<i128>) -> u128 {
+    let n = f.degree().expect("f is nonzero");
+    let norm = f
+        .coefficients()
+        .iter()
+        .fold(0_u128, |acc, c| checked(acc.checked_add(c.unsigned_abs())));
+    checked(
+        2_u128
+            .checked_pow(u32::try_from(n + 1).unwrap_or(u32::MAX))
+            .and_then(|b| b.checked_mul(norm)),
+    )
+}
+
+/// Combines `a` modulo `m` and `b` modulo a prime `p` into residues modulo *mp*, coefficientwise.
+fn crt(a: &[u128], m: u128, b: &[u128], p: u128) -> Vec<u128> {
+    let inverse = inverse_mod(m % p, p);
+    a.iter()
+        .zip(b)
+        .map(|(&x, &y)| {
+            let difference = (y + p - x % p) % p;
+            x + m * mul_mod(difference, inverse, p)
+        })
+        .collect()
+}
+
+/// Yun's square-free decomposition of a primitive polynomial of positive degree, which returns
+/// primitive, square-free and pairwise coprime *aᵢ* with `f` = *a*₁*a*₂² ⋯, omitting the *aᵢ* which
+/// are 1. Unlike over a finite field, we don't need *p*-th roots in characteristic 0.
+fn squarefree_decomposition(f: &Poly<i128>) -> Vec<(Poly<i128>, usize)> {
+    let derivative = f.derivative();
+    let b = primitive_gcd(f, &derivative);
+    let mut c = divide(f, &b);
+    let mut d = &divide(&derivative, &b) - &c.derivative();
+    let mut factors = Vec::new();
+    let mut i = 1;
+    while c.degree() != Some(0) {
+        let a = primitive_gcd(&c, &d);
+        c = divide(&c, &a);
+        d = &divide(&d, &a) - &c.derivative();
+        if a.degree() != Some(0) {
+            factors.push((a, i));
+        }
+        i += 1;
+    }
+    factors
+}
+
+/// The irreducible factors of a primitive square-free polynomial with a positive leading
+/// coefficient.
+fn factor_squarefree(f: &Poly<i128>) -> Vec<Poly<i128>> {
+    if f.degree() == Some(1) {
+        return vec![f.clone()];
+    }
+    let mut best: Option<(usize, Modular<Vec<Poly<i128>>>)> = None;
+    let mut tried = 0;
+    for (count, lift) in MODULAR.into_iter().chain(FALLBACK) {
+        let Some(count) = count(f) else {
+            continue;
+        };
+        if count == 1 {
+            return vec![f.clone()];
+        }
+        if best.is_none_or(|(fewest, _)| count < fewest) {
+            best = Some((count, lift));
+        }
+        tried += 1;
+        if tried == PRIMES_TO_TRY {
+            break;
+        }
+    }
+    let (_, lift) =
+        best.expect("every prime we try divides the leading coefficient or the discriminant");
+    lift(f)
+}
+
+/// The image of `f` in 𝔽*ₚ*\[*x*\].
+fn reduce_unchecked<const P: u64>(f: &Poly<i128>) -> Poly<PrimeField<P>> {
+    Poly::new(f.coefficients().into_iter().map(PrimeField::new).collect())
+}
+
+/// The image of `f` in 𝔽*ₚ*\[*x*\], if it has the same degree and is still square-free.
+fn reduce<const P: u64>(f: &Poly<i128>) -> Option<Poly<PrimeField<P>>> {
+    let reduced = reduce_unchecked(f);
+    (reduced.degree() == f.degree() && reduced.is_squarefree()).then_some(reduced)
+}
+
+/// How many irreducible factors `f` has modulo `P`, if `P` is suitable.
+fn modular_count<const P: u64>(f: &Poly<i128>) -> Option<usize> {
+    reduce::<P>(f).map(|reduced| reduced.monic().cantor_zassenhaus().len())
+}
+
+/// The irreducible factors over ℤ of a primitive square-free polynomial, by factorising modulo
+/// `P`, Hensel lifting and recombining.
+fn zassenhaus<const P: u64>(f: &Poly<i128>) -> Vec<Poly<i128>> {
+    let reduced = reduce::<P>(f).expect("the prime was checked to be suitable");
+    let modular = reduced.monic().cantor_zassenhaus();
+
+    // Every factor of lc(f) g with g | f has coefficients bounded by |lc(f)| times Mignotte's
+    // bound, so modulo pᵏ beyond that, we can tell positive and negative coefficients apart.
+    let bound = checked(mignotte_bound(f).checked_mul(f.leading_coefficient().unsigned_abs()));
+    let p = u128::from(P);
+    let (mut modulus, mut k) = (p, 1);
+    while modulus <= bound {
+        modulus = checked(modulus.checked_mul(p));
+        k += 1;
+    }
+    assert!(
+        modulus <= i128::MAX as u128,
+        "integer arithmetic overflowed"
+    );
+
+    let target = f
+        .coefficients()
+        .iter()
+        .map(|&c| c.rem_euclid(modulus as i128) as u128)
+        .collect();
+    let mut lifted = hensel_lift::<P>(target, &modular, k, modulus);
+
+    // Try products of s lifted factors, for s = 1, 2, …, until no more factors can be split off.
+    // We only need s up to half of what's left, since the complement is also a product.
+    let mut remaining = f.clone();
+    let mut factors = Vec::new();
+    let mut s = 1;
+    'search: while 2 * s <= lifted.len() {
+        let mut subset: Vec<usize> = (0..s).collect();
+        loop {
+            let lead = remaining.leading_coefficient() as u128 % modulus;
+            let product = subset.iter().fold(vec![lead], |acc, &i| {
+                multiply_mod(&acc, &lifted[i], modulus)
+            });
+            let candidate = normalised(&symmetric(&product, modulus));
+            if let Some(quotient) = divide_exact(&remaining, &candidate) {
+                factors.push(candidate);
+                remaining = quotient;
+                for &i in subset.iter().rev() {
+                    lifted.remove(i);
+                }
+                continue 'search;
+            }
+            if !next_subset(&mut subset, lifted.len()) {
+                break;
+            }
+        }
+        s += 1;
+    }
+    factors.push(remaining);
+    factors
+}
+
+/// Moves `subset` to the next subset of {0, …, `n` - 1} of the same size in lexicographic order,
+/// returning false if it was the last one.
+fn next_subset(subset: &mut [usize], n: usize) -> bool {
+    let s = subset.len();
+    let Some(i) = (0..s).rev().find(|&i| subset[i] < n - s + i) else {
+        return false;
+    };
+    subset[i] += 1;
+    for j in i + 1..s {
+        subset[j] = subset[j - 1] + 1;
+    }
+    true
+}
+
+/// Coefficients modulo `modulus` as integers between -`modulus`/2 and `modulus`/2.
+fn symmetric(coefficients: &[u128], modulus: u128) -> Poly<i128> {
+    Poly::new(
+        coefficients
+            .iter()
+            .map(|&c| {
+                if c > modulus / 2 {
+                    -((modulus - c) as i128)
+                } else {
+                    c as i128
+                }
+            })
+            .collect(),
+    )
+}
+
+/// The product of two polynomials with coefficients modulo `modulus`.
+fn multiply_mod(a: &[u128], b: &[u128], modulus: u128) -> Vec<u128> {
+    let mut product = vec![0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (&y, z) in b.iter().zip(&mut product[i..]) {
+            *z = (*z + mul_mod(x, y, modulus)) % modulus;
+        }
+    }
+    product
+}
+
+fn to_field<const P: u64>(coefficients: &[u128]) -> Poly<PrimeField<P>> {
+    Poly::new(
+        coefficients
+            .iter()
+            .map(|&c| PrimeField::new((c % u128::from(P)) as i128))
+            .collect(),
+    )
+}
+
+fn from_field<const P: u64>(f: &Poly<PrimeField<P>>) -> Vec<u128> {
+    f.coefficients()
+        .iter()
+        .map(|c| u128::from(c.value()))
+        .collect()
+}
+
+/// Lifts the factorisation `target` ≡ lc ⋅ *g*₁ ⋯ *gᵣ* (mod *p*) into monic irreducibles `factors`
+/// to monic factors modulo `modulus` = *pᵏ*, splitting off one factor at a time.
+fn hensel_lift<const P: u64>(
+    target: Vec<u128>,
+    factors: &[Poly<PrimeField<P>>],
+    k: u32,
+    modulus: u128,
+) -> Vec<Vec<u128>> {
+    let lead = *target.last().expect("the target is nonzero");
+    let [first, rest @ ..] = factors else {
+        return Vec::new();
+    };
+    if rest.is_empty() {
+        let inverse = inverse_mod(lead, modulus);
+        return vec![target
+            .iter()
+            .map(|&c| mul_mod(c, inverse, modulus))
+            .collect()];
+    }
+    let rest_product = rest.iter().fold(
+        Poly::constant(PrimeField::new((lead % u128::from(P)) as i128)),
+        |acc, g| &acc * g,
+    );
+    let (g, h) = hensel_step::<P>(&target, first, &rest_product, k, modulus);
+    let mut lifted = vec![g];
+    lifted.extend(hensel_lift::<P>(h, rest, k, modulus));
+    lifted
+}
+
+/// Lifts `target` ≡ *gh* (mod *p*), with *g* monic and coprime to *h*, to `target` ≡ *GH* (mod
+/// *pᵏ*) with *G* ≡ *g* and *H* ≡ *h* (mod *p*), *G* monic and *H* having the same leading
+/// coefficient as `target`. This is the linear version, gaining one power of *p* at a time.
+fn hensel_step<const P: u64>(
+    target: &[u128],
+    g: &Poly<PrimeField<P>>,
+    h: &Poly<PrimeField<P>>,
+    k: u32,
+    modulus: u128,
+) -> (Vec<u128>, Vec<u128>) {
+    let (_, s, t) = g.extended_gcd(h);
+    let (mut big_g, mut big_h) = (from_field(g), from_field(h));
+    *big_h.last_mut().expect("h is nonzero") = *target.last().expect("the target is nonzero");
+    let p = u128::from(P);
+    let mut power = p;
+    for _ in 1..k {
+        // With e = (target - GH)/pʲ, we want ΔG, ΔH with ΔG h + ΔH g ≡ e (mod p). Since
+        // sg + th = 1, we can take ΔG = te mod g and ΔH = se + (te div g) h.
+        let next = power * p;
+        let product = multiply_mod(&big_g, &big_h, next);
+        let error: Vec<u128> = (0..target.len())
+            .map(|i| {
+                let c = product.get(i).copied().unwrap_or(0);
+                ((target[i] % next + next - c) % next) / power
+            })
+            .collect();
+        let e = to_field::<P>(&error);
+        let (q, r) = (&t * &e).div_rem(g);
+        let dh = &(&s * &e) + &(&q * h);
+        for (c, d) in big_g.iter_mut().zip(from_field(&r)) {
+            *c = (*c + power * d) % modulus;
+        }
+        for (c, d) in big_h.iter_mut().zip(from_field(&dh)) {
+            *c = (*c + power * d) % modulus;
+        }
+        power = next;
+    }
+    (big_g, big_h)
+}
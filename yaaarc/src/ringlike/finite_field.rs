@@ -0,0 +1,882 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Finite fields.
+//!
+//! For every prime *p*, the integers modulo *p* form a field 𝔽*ₚ* = ℤ/*p*ℤ. Here the prime is a
+//! const generic, so 𝔽₇ and 𝔽₁₁ are different types and can't be mixed up by accident.
+//!
+//! More generally, there's exactly one field with *q* = *pⁿ* elements up to isomorphism, written
+//! 𝔽*_q* or GF(*q*). We build it as 𝔽*ₚ*\[*x*\]/(*m*) for a monic irreducible polynomial *m* of
+//! degree *n*; see [`GaloisField`].
+
+use std::{cell::RefCell, collections::HashMap, fmt};
+
+use crate::{
+    arithmetic,
+    grouplike::{
+        AbelianGroup, CommutativeMagma, Group, Magma, Monoid, Quasigroup, Semigroup, UnitalMagma,
+    },
+    operators::{BinaryOperator, Plus, Times},
+    ringlike::{
+        factorisation::Factorisation,
+        polynomial::{self, Poly},
+        CommutativeRing, DivisionRing, EuclideanDomain, Field, IntegralDomain,
+        PrincipalIdealDomain, Ring, UniqueFactorisationDomain,
+    },
+};
+
+/// A finite field 𝔽*_q*.
+///
+/// The elements are numbered 0, 1, …, *q* - 1 by [`FiniteField::from_index`] and
+/// [`FiniteField::index`], with 0 and 1 getting the numbers 0 and 1. This lets algorithms like
+/// polynomial factorisation enumerate or pick elements.
+pub trait FiniteField: Field + Copy + PartialEq {
+    /// The characteristic *p*, the prime with *p* ⋅ 1 = 0.
+    const CHARACTERISTIC: u64;
+
+    /// The number of elements *q*, a power of the characteristic.
+    const ORDER: u128;
+
+    /// The element numbered `index`.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `index` is at least [`FiniteField::ORDER`].
+    fn from_index(index: u128) -> Self;
+
+    /// The number of this element, the inverse of [`FiniteField::from_index`].
+    fn index(&self) -> u128;
+
+    /// Every element, in order of [`FiniteField::index`].
+    fn elements() -> impl Iterator<Item = Self> {
+        (0..Self::ORDER).map(Self::from_index)
+    }
+
+    /// Raises `self` to the power `exponent` by repeated squaring.
+    fn power(&self, mut exponent: u128) -> Self {
+        let mut result = Self::ONE;
+        let mut base = *self;
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.mul(base);
+            }
+            base = base.mul(base);
+            exponent /= 2;
+        }
+        result
+    }
+}
+
+/// Whether `n` is prime, using the Miller–Rabin test with the first twenty primes as bases. This
+/// is known to be deterministic for all 64-bit integers.
+pub const fn is_prime(n: u64) -> bool {
+    arithmetic::is_prime(n as u128)
+}
+
+/// An element of the prime field 𝔽*ₚ*, the integers modulo a prime *p* = `P`.
+///
+/// Creating an element with [`PrimeField::new`] checks at compile time that `P` is prime.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::ringlike::{finite_field::PrimeField, Field};
+///
+/// type F7 = PrimeField<7>;
+///
+/// let three = F7::new(3);
+/// assert_eq!(three * F7::new(5), F7::new(1));
+/// assert_eq!(three.div(F7::new(5)), F7::new(2));
+/// assert_eq!(F7::new(-1), F7::new(6));
+/// // Fermat's little theorem.
+/// assert_eq!(three.pow(6), F7::new(1));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PrimeField<const P: u64>(u64);
+
+impl<const P: u64> PrimeField<P> {
+    const ASSERT_PRIME: () = assert!(is_prime(P), "the modulus of a prime field must be prime");
+
+    /// The residue of `n` modulo *p*.
+    pub const fn new(n: i128) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::ASSERT_PRIME;
+        PrimeField(n.rem_euclid(P as i128) as u64)
+    }
+
+    /// The characteristic *p*.
+    pub const fn characteristic() -> u64 {
+        P
+    }
+
+    /// The representative of this element in 0, 1, …, *p* - 1.
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// Whether this element is zero.
+    pub const fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Raises `self` to the power `exponent` by repeated squaring.
+    pub const fn pow(&self, exponent: u64) -> Self {
+        PrimeField(arithmetic::pow_mod(self.0 as u128, exponent as u128, P as u128) as u64)
+    }
+
+    /// The multiplicative inverse, or [`None`] if `self` is zero. By Fermat's little theorem, this
+    /// is *x*^(*p* - 2).
+    pub const fn recip(&self) -> Option<Self> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(self.pow(P - 2))
+        }
+    }
+
+    /// Every element of the field, in order.
+    pub fn elements() -> impl Iterator<Item = Self> {
+        (0..P).map(PrimeField)
+    }
+
+    /// A primitive `n`-th root of unity, i.e. an element of multiplicative order exactly `n`.
+    ///
+    /// These exist exactly when `n` divides *p* - 1, and otherwise this returns [`None`].
+    /// Number-theoretic transforms of length 2*ᵏ* need a primitive 2*ᵏ*-th root of unity, so
+    /// primes like 998244353 = 119 ⋅ 2²³ + 1 are especially useful.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::finite_field::PrimeField;
+    ///
+    /// type F = PrimeField<998244353>;
+    ///
+    /// let omega = F::root_of_unity(1 << 23).unwrap();
+    /// assert_eq!(omega.pow(1 << 23), F::new(1));
+    /// assert_ne!(omega.pow(1 << 22), F::new(1));
+    /// assert!(F::root_of_unity(1 << 24).is_none());
+    /// ```
+    pub fn root_of_unity(n: u64) -> Option<Self> {
+        if n == 0 || !(P - 1).is_multiple_of(n) {
+            return None;
+        }
+        let mut prime_factors = Vec::new();
+        let mut m = n;
+        let mut q = 2;
+        while q * q <= m {
+            if m.is_multiple_of(q) {
+                prime_factors.push(q);
+                while m.is_multiple_of(q) {
+                    m /= q;
+                }
+            }
+            q += 1;
+        }
+        if m > 1 {
+            prime_factors.push(m);
+        }
+        // x^((p - 1)/n) has order dividing n, and it's exactly n unless some x^((p - 1)/q) = 1.
+        (1..P).map(PrimeField).find_map(|x| {
+            let root = x.pow((P - 1) / n);
+            prime_factors
+                .iter()
+                .all(|&q| root.pow(n / q).0 != 1)
+                .then_some(root)
+        })
+    }
+}
+
+impl<const P: u64> Default for PrimeField<P> {
+    fn default() -> Self {
+        PrimeField(0)
+    }
+}
+
+impl<const P: u64> From<i128> for PrimeField<P> {
+    fn from(n: i128) -> Self {
+        PrimeField::new(n)
+    }
+}
+
+impl<const P: u64> From<i64> for PrimeField<P> {
+    fn from(n: i64) -> Self {
+        PrimeField::new(n.into())
+    }
+}
+
+impl<const P: u64> From<i32> for PrimeField<P> {
+    fn from(n: i32) -> Self {
+        PrimeField::new(n.into())
+    }
+}
+
+impl<const P: u64> fmt::Display for PrimeField<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> std::ops::Add for PrimeField<P> {
+    type Output = PrimeField<P>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        PrimeField(((self.0 as u128 + rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> std::ops::Sub for PrimeField<P> {
+    type Output = PrimeField<P>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const P: u64> std::ops::Mul for PrimeField<P> {
+    type Output = PrimeField<P>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        PrimeField(arithmetic::mul_mod(self.0 as u128, rhs.0 as u128, P as u128) as u64)
+    }
+}
+
+impl<const P: u64> std::ops::Div for PrimeField<P> {
+    type Output = PrimeField<P>;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        let inverse = rhs.recip().expect("division by zero");
+        PrimeField(arithmetic::mul_mod(self.0 as u128, inverse.0 as u128, P as u128) as u64)
+    }
+}
+
+impl<const P: u64> std::ops::Neg for PrimeField<P> {
+    type Output = PrimeField<P>;
+
+    fn neg(self) -> Self::Output {
+        PrimeField((P - self.0) % P)
+    }
+}
+
+impl<const P: u64> std::ops::AddAssign for PrimeField<P> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P: u64> std::ops::SubAssign for PrimeField<P> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P: u64> std::ops::MulAssign for PrimeField<P> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const P: u64> std::ops::DivAssign for PrimeField<P> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const P: u64> std::iter::Sum for PrimeField<P> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(PrimeField(0), |acc, x| acc + x)
+    }
+}
+
+impl<const P: u64> std::iter::Product for PrimeField<P> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(PrimeField(1), |acc, x| acc * x)
+    }
+}
+
+impl<const P: u64> BinaryOperator<Plus> for PrimeField<P> {
+    fn op(&self, rhs: Self) -> Self {
+        *self + rhs
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self += rhs;
+    }
+}
+
+impl<const P: u64> BinaryOperator<Times> for PrimeField<P> {
+    fn op(&self, rhs: Self) -> Self {
+        *self * rhs
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self *= rhs;
+    }
+}
+
+impl<const P: u64> Magma<Plus> for PrimeField<P> {}
+impl<const P: u64> Semigroup<Plus> for PrimeField<P> {}
+impl<const P: u64> UnitalMagma<Plus> for PrimeField<P> {
+    const IDENTITY: Self = PrimeField(0);
+}
+impl<const P: u64> Quasigroup<Plus> for PrimeField<P> {
+    fn inverse(&self) -> Self {
+        -*self
+    }
+}
+impl<const P: u64> CommutativeMagma<Plus> for PrimeField<P> {}
+impl<const P: u64> AbelianGroup<Plus> for PrimeField<P> {}
+
+impl<const P: u64> Magma<Times> for PrimeField<P> {}
+impl<const P: u64> Semigroup<Times> for PrimeField<P> {}
+impl<const P: u64> UnitalMagma<Times> for PrimeField<P> {
+    const IDENTITY: Self = PrimeField(1);
+}
+/// # Panics
+///
+/// [`Quasigroup::inverse`] panics if called on zero.
+impl<const P: u64> Quasigroup<Times> for PrimeField<P> {
+    fn inverse(&self) -> Self {
+        self.recip().expect("zero has no multiplicative inverse")
+    }
+}
+impl<const P: u64> CommutativeMagma<Times> for PrimeField<P> {}
+impl<const P: u64> Monoid<Times> for PrimeField<P> {}
+impl<const P: u64> Group<Times> for PrimeField<P> {}
+
+impl<const P: u64> Ring for PrimeField<P> {
+    const ZERO: Self = PrimeField(0);
+    const ONE: Self = PrimeField(1);
+
+    fn left_inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+
+    fn right_inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+
+    fn inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+}
+
+impl<const P: u64> CommutativeRing for PrimeField<P> {
+    /// Uses the number-theoretic transform for long polynomials if 𝔽*ₚ* has the roots of unity
+    /// it needs, and Toom-3 otherwise, or Karatsuba in characteristic 2 or 3.
+    fn mul_polynomials(a: &[Self], b: &[Self]) -> Vec<Self> {
+        polynomial::prime_field_product(a, b)
+    }
+}
+
+impl<const P: u64> DivisionRing for PrimeField<P> {
+    fn div_right(&self, rhs: Self) -> Self {
+        *self / rhs
+    }
+
+    fn div_left(&self, rhs: Self) -> Self {
+        *self / rhs
+    }
+}
+
+impl<const P: u64> IntegralDomain for PrimeField<P> {
+    fn associates(&self, rhs: Self) -> bool {
+        self.is_zero() == rhs.is_zero()
+    }
+}
+
+impl<const P: u64> UniqueFactorisationDomain for PrimeField<P> {
+    /// Every nonzero element is a unit, so it has no irreducible factors.
+    fn factor(&self) -> Factorisation<Self> {
+        assert!(!self.is_zero(), "zero has no factorisation");
+        Factorisation::new(*self, Vec::new())
+    }
+}
+impl<const P: u64> PrincipalIdealDomain for PrimeField<P> {}
+
+impl<const P: u64> EuclideanDomain for PrimeField<P> {
+    fn valuation(&self) -> usize {
+        0
+    }
+
+    /// Division is exact in a field, so the remainder is always zero.
+    fn div_rem(&self, rhs: Self) -> (Self, Self) {
+        (*self / rhs, Self::ZERO)
+    }
+}
+
+impl<const P: u64> Field for PrimeField<P> {
+    fn div(&self, rhs: Self) -> Self {
+        self.div_right(rhs)
+    }
+}
+
+impl<const P: u64> FiniteField for PrimeField<P> {
+    const CHARACTERISTIC: u64 = P;
+    const ORDER: u128 = P as u128;
+
+    fn from_index(index: u128) -> Self {
+        PrimeField::new(index as i128)
+    }
+
+    fn index(&self) -> u128 {
+        self.0.into()
+    }
+}
+
+thread_local! {
+    /// The moduli of the Galois fields used so far, keyed by characteristic and degree.
+    static MODULI: RefCell<HashMap<(u64, usize), Vec<u64>>> = RefCell::new(HashMap::new());
+}
+
+/// An element of the finite field GF(*pⁿ*) with *p* = `P` and *n* = `N`.
+///
+/// We take the modulus *m* to be the first monic irreducible polynomial of degree *n* over 𝔽*ₚ*,
+/// where *xⁿ* + *c*ₙ₋₁*x*ⁿ⁻¹ + ⋯ + *c*₀ is numbered by the digits *c*₀ + *c*₁*p* + ⋯ in base *p*.
+/// It's found the first time it's needed, with [`Poly::rabin_test`]. Then every element is a
+/// polynomial in the class α of *x* of degree less than *n*, which is what we store. These aren't
+/// the Conway polynomials that some other systems use, so the same α may be written differently
+/// elsewhere.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::ringlike::{
+///     finite_field::{FiniteField, GaloisField, PrimeField},
+///     polynomial::Poly,
+///     Field,
+/// };
+///
+/// type F9 = GaloisField<3, 2>;
+///
+/// // -1 isn't a square mod 3, so 𝔽₉ = 𝔽₃[x]/(x² + 1) and α² = -1.
+/// assert_eq!(F9::modulus(), Poly::new(vec![PrimeField::new(1), PrimeField::new(0), PrimeField::new(1)]));
+/// let alpha = F9::alpha();
+/// assert_eq!(alpha * alpha, -F9::from(1));
+/// assert_eq!(alpha.div(alpha + F9::from(1)), F9::new([PrimeField::new(2), PrimeField::new(2)]));
+/// // The Frobenius map fixes exactly 𝔽₃, and every element satisfies x⁹ = x.
+/// assert!(F9::elements().all(|x| x.power(9) == x));
+/// assert_eq!(F9::elements().filter(|x| x.frobenius() == *x).count(), 3);
+/// assert_eq!(format!("{}", alpha + F9::from(2)), "α + 2");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GaloisField<const P: u64, const N: usize>([PrimeField<P>; N]);
+
+impl<const P: u64, const N: usize> GaloisField<P, N> {
+    const ASSERT_DEGREE: () = assert!(N > 0, "a Galois field must have positive degree");
+
+    /// The element *c*₀ + *c*₁α + ⋯ + *c*ₙ₋₁αⁿ⁻¹, where the *cᵢ* are `coefficients`.
+    pub const fn new(coefficients: [PrimeField<P>; N]) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::ASSERT_DEGREE;
+        GaloisField(coefficients)
+    }
+
+    /// The generator α, the class of *x* in 𝔽*ₚ*\[*x*\]/(*m*). In 𝔽*ₚ* itself, this is just 0.
+    pub fn alpha() -> Self {
+        GaloisField::from_poly(&Poly::x())
+    }
+
+    /// The coefficients of 1, α, …, αⁿ⁻¹.
+    pub const fn coefficients(&self) -> [PrimeField<P>; N] {
+        self.0
+    }
+
+    /// The modulus *m*, a monic irreducible polynomial of degree *n* over 𝔽*ₚ*, with *m*(α) = 0.
+    pub fn modulus() -> Poly<PrimeField<P>> {
+        let mut coefficients: Vec<PrimeField<P>> = Self::reduction().to_vec();
+        coefficients.push(PrimeField(1));
+        Poly::new(coefficients)
+    }
+
+    /// The image of `polynomial` under *x* ↦ α, i.e. its remainder modulo the modulus.
+    pub fn from_poly(polynomial: &Poly<PrimeField<P>>) -> Self {
+        let mut coefficients = [PrimeField(0); N];
+        for (c, r) in coefficients
+            .iter_mut()
+            .zip(polynomial.div_rem(&Self::modulus()).1.coefficients())
+        {
+            *c = r;
+        }
+        GaloisField::new(coefficients)
+    }
+
+    /// This element as a polynomial in α of degree less than *n*.
+    pub fn to_poly(&self) -> Poly<PrimeField<P>> {
+        Poly::new(self.0.to_vec())
+    }
+
+    /// Whether this element is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(PrimeField::is_zero)
+    }
+
+    /// The Frobenius automorphism *x* ↦ *xᵖ*, which generates the Galois group of GF(*pⁿ*) over
+    /// 𝔽*ₚ*.
+    pub fn frobenius(&self) -> Self {
+        self.power(P.into())
+    }
+
+    /// The multiplicative inverse, or [`None`] if `self` is zero. This is *x*^(*q* - 2).
+    pub fn recip(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self.power(Self::ORDER - 2))
+        }
+    }
+
+    /// The multiplicative order, the least *k* > 0 with *xᵏ* = 1, or [`None`] if `self` is zero.
+    pub fn multiplicative_order(&self) -> Option<u128> {
+        if self.is_zero() {
+            return None;
+        }
+        // The order divides q - 1, so remove prime factors from q - 1 while we still get 1.
+        let mut order = Self::ORDER - 1;
+        let mut m = order;
+        let mut p = 2;
+        while p * p <= m {
+            if m.is_multiple_of(p) {
+                while m.is_multiple_of(p) {
+                    m /= p;
+                }
+                while order.is_multiple_of(p) && self.power(order / p) == Self::ONE {
+                    order /= p;
+                }
+            }
+            p += 1;
+        }
+        if m > 1 && self.power(order / m) == Self::ONE {
+            order /= m;
+        }
+        Some(order)
+    }
+
+    /// The first element, in order of [`FiniteField::index`], which generates the multiplicative
+    /// group. The multiplicative group of a finite field is always cyclic, so this exists.
+    pub fn primitive_element() -> Self {
+        Self::elements()
+            .find(|x| x.multiplicative_order() == Some(Self::ORDER - 1))
+            .expect("the multiplicative group of a finite field is cyclic")
+    }
+
+    /// The lower coefficients of the modulus, so that αⁿ = -(*c*₀ + *c*₁α + ⋯).
+    fn reduction() -> [PrimeField<P>; N] {
+        let coefficients = MODULI.with(|moduli| {
+            moduli
+                .borrow_mut()
+                .entry((P, N))
+                .or_insert_with(|| {
+                    (0..PrimeField::<P>::ORDER.pow(N as u32))
+                        .map(|index| {
+                            let mut coefficients: Vec<PrimeField<P>> = (0..N)
+                                .scan(index, |rest, _| {
+                                    let digit = *rest % u128::from(P);
+                                    *rest /= u128::from(P);
+                                    Some(PrimeField::from_index(digit))
+                                })
+                                .collect();
+                            coefficients.push(PrimeField(1));
+                            Poly::new(coefficients)
+                        })
+                        .find(Poly::rabin_test)
+                        .expect("there are irreducible polynomials of every degree")
+                        .coefficients()
+                        .iter()
+                        .take(N)
+                        .map(PrimeField::value)
+                        .collect()
+                })
+                .clone()
+        });
+        std::array::from_fn(|i| PrimeField(coefficients[i]))
+    }
+
+    fn times(&self, rhs: &Self) -> Self {
+        let mut product = vec![PrimeField::<P>(0); 2 * N - 1];
+        for (i, &a) in self.0.iter().enumerate() {
+            for (&b, p) in rhs.0.iter().zip(&mut product[i..]) {
+                *p += a * b;
+            }
+        }
+        let reduction = Self::reduction();
+        for k in (N..2 * N - 1).rev() {
+            let c = product[k];
+            for (p, &r) in product[k - N..k].iter_mut().zip(&reduction) {
+                *p -= c * r;
+            }
+        }
+        GaloisField(std::array::from_fn(|i| product[i]))
+    }
+}
+
+impl<const P: u64, const N: usize> Default for GaloisField<P, N> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const P: u64, const N: usize> From<PrimeField<P>> for GaloisField<P, N> {
+    fn from(c: PrimeField<P>) -> Self {
+        let mut coefficients = [PrimeField(0); N];
+        coefficients[0] = c;
+        GaloisField::new(coefficients)
+    }
+}
+
+impl<const P: u64, const N: usize> From<i128> for GaloisField<P, N> {
+    fn from(n: i128) -> Self {
+        GaloisField::from(PrimeField::new(n))
+    }
+}
+
+impl<const P: u64, const N: usize> From<i64> for GaloisField<P, N> {
+    fn from(n: i64) -> Self {
+        GaloisField::from(PrimeField::new(n.into()))
+    }
+}
+
+impl<const P: u64, const N: usize> From<i32> for GaloisField<P, N> {
+    fn from(n: i32) -> Self {
+        GaloisField::from(PrimeField::new(n.into()))
+    }
+}
+
+impl<const P: u64, const N: usize> fmt::Display for GaloisField<P, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let polynomial = format!("{}", self.to_poly());
+        write!(f, "{}", polynomial.replace('x', "α"))
+    }
+}
+
+impl<const P: u64, const N: usize> std::ops::Add for GaloisField<P, N> {
+    type Output = GaloisField<P, N>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        GaloisField(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<const P: u64, const N: usize> std::ops::Sub for GaloisField<P, N> {
+    type Output = GaloisField<P, N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        GaloisField(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<const P: u64, const N: usize> std::ops::Mul for GaloisField<P, N> {
+    type Output = GaloisField<P, N>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.times(&rhs)
+    }
+}
+
+impl<const P: u64, const N: usize> std::ops::Div for GaloisField<P, N> {
+    type Output = GaloisField<P, N>;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        self.times(&rhs.recip().expect("division by zero"))
+    }
+}
+
+impl<const P: u64, const N: usize> std::ops::Neg for GaloisField<P, N> {
+    type Output = GaloisField<P, N>;
+
+    fn neg(self) -> Self::Output {
+        GaloisField(self.0.map(|c| -c))
+    }
+}
+
+impl<const P: u64, const N: usize> std::ops::AddAssign for GaloisField<P, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P: u64, const N: usize> std::ops::SubAssign for GaloisField<P, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P: u64, const N: usize> std::ops::MulAssign for GaloisField<P, N> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const P: u64, const N: usize> std::ops::DivAssign for GaloisField<P, N> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const P: u64, const N: usize> std::iter::Sum for GaloisField<P, N> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl<const P: u64, const N: usize> std::iter::Product for GaloisField<P, N> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+
+impl<const P: u64, const N: usize> BinaryOperator<Plus> for GaloisField<P, N> {
+    fn op(&self, rhs: Self) -> Self {
+        *self + rhs
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self += rhs;
+    }
+}
+
+impl<const P: u64, const N: usize> BinaryOperator<Times> for GaloisField<P, N> {
+    fn op(&self, rhs: Self) -> Self {
+        *self * rhs
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self *= rhs;
+    }
+}
+
+impl<const P: u64, const N: usize> Magma<Plus> for GaloisField<P, N> {}
+impl<const P: u64, const N: usize> Semigroup<Plus> for GaloisField<P, N> {}
+impl<const P: u64, const N: usize> UnitalMagma<Plus> for GaloisField<P, N> {
+    const IDENTITY: Self = Self::ZERO;
+}
+impl<const P: u64, const N: usize> Quasigroup<Plus> for GaloisField<P, N> {
+    fn inverse(&self) -> Self {
+        -*self
+    }
+}
+impl<const P: u64, const N: usize> CommutativeMagma<Plus> for GaloisField<P, N> {}
+impl<const P: u64, const N: usize> AbelianGroup<Plus> for GaloisField<P, N> {}
+
+impl<const P: u64, const N: usize> Magma<Times> for GaloisField<P, N> {}
+impl<const P: u64, const N: usize> Semigroup<Times> for GaloisField<P, N> {}
+impl<const P: u64, const N: usize> UnitalMagma<Times> for GaloisField<P, N> {
+    const IDENTITY: Self = Self::ONE;
+}
+/// # Panics
+///
+/// [`Quasigroup::inverse`] panics if called on zero.
+impl<const P: u64, const N: usize> Quasigroup<Times> for GaloisField<P, N> {
+    fn inverse(&self) -> Self {
+        self.recip().expect("zero has no multiplicative inverse")
+    }
+}
+impl<const P: u64, const N: usize> CommutativeMagma<Times> for GaloisField<P, N> {}
+impl<const P: u64, const N: usize> Monoid<Times> for GaloisField<P, N> {}
+impl<const P: u64, const N: usize> Group<Times> for GaloisField<P, N> {}
+
+impl<const P: u64, const N: usize> Ring for GaloisField<P, N> {
+    const ZERO: Self = GaloisField([PrimeField(0); N]);
+    const ONE: Self = {
+        let mut coefficients = [PrimeField(0); N];
+        coefficients[0] = PrimeField(1);
+        GaloisField(coefficients)
+    };
+
+    fn left_inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+
+    fn right_inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+
+    fn inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+}
+
+impl<const P: u64, const N: usize> CommutativeRing for GaloisField<P, N> {
+    /// Uses Toom-3, or Karatsuba in characteristic 2 or 3.
+    fn mul_polynomials(a: &[Self], b: &[Self]) -> Vec<Self> {
+        polynomial::field_product(a, b)
+    }
+}
+
+impl<const P: u64, const N: usize> DivisionRing for GaloisField<P, N> {
+    fn div_right(&self, rhs: Self) -> Self {
+        *self / rhs
+    }
+
+    fn div_left(&self, rhs: Self) -> Self {
+        *self / rhs
+    }
+}
+
+impl<const P: u64, const N: usize> IntegralDomain for GaloisField<P, N> {
+    fn associates(&self, rhs: Self) -> bool {
+        self.is_zero() == rhs.is_zero()
+    }
+}
+
+impl<const P: u64, const N: usize> UniqueFactorisationDomain for GaloisField<P, N> {
+    /// Every nonzero element is a unit, so it has no irreducible factors.
+    fn factor(&self) -> Factorisation<Self> {
+        assert!(!self.is_zero(), "zero has no factorisation");
+        Factorisation::new(*self, Vec::new())
+    }
+}
+impl<const P: u64, const N: usize> PrincipalIdealDomain for GaloisField<P, N> {}
+
+impl<const P: u64, const N: usize> EuclideanDomain for GaloisField<P, N> {
+    fn valuation(&self) -> usize {
+        0
+    }
+
+    /// Division is exact in a field, so the remainder is always zero.
+    fn div_rem(&self, rhs: Self) -> (Self, Self) {
+        (*self / rhs, Self::ZERO)
+    }
+}
+
+impl<const P: u64, const N: usize> Field for GaloisField<P, N> {
+    fn div(&self, rhs: Self) -> Self {
+        self.div_right(rhs)
+    }
+}
+
+impl<const P: u64, const N: usize> FiniteField for GaloisField<P, N> {
+    const CHARACTERISTIC: u64 = P;
+    const ORDER: u128 = (P as u128).pow(N as u32);
+
+    /// The element whose coefficients are the base *p* digits of `index`, lowest first.
+    fn from_index(index: u128) -> Self {
+        let mut rest = index;
+        GaloisField::new(std::array::from_fn(|_| {
+            let digit = rest % u128::from(P);
+            rest /= u128::from(P);
+            PrimeField::from_index(digit)
+        }))
+    }
+
+    fn index(&self) -> u128 {
+        self.0
+            .iter()
+            .rev()
+            .fold(0, |acc, c| acc * u128::from(P) + c.index())
+    }
+}
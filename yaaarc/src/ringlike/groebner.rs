@@ -0,0 +1,439 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Gröbner bases of ideals of multivariate polynomial rings over a field.
+//!
+//! Fix a [`MonomialOrder`]. A *Gröbner basis* of an ideal *I* ⊆ *K*\[*x*₁, …, *xₙ*\] is a finite
+//! *G* ⊆ *I* such that the leading monomial of every nonzero element of *I* is divisible by the
+//! leading monomial of some element of *G*. Then *G* generates *I*, and the remainder of any *f*
+//! on division by *G* (see [`MPoly::div_rem_list`]) doesn't depend on how we divide. It's zero
+//! exactly when *f* ∈ *I*, which solves the ideal membership problem.
+//!
+//! Every ideal has a unique *reduced* Gröbner basis, where every element is monic and no term of
+//! an element is divisible by the leading monomial of another. That's what
+//! [`groebner_basis`] returns, so two ideals are equal exactly when their reduced Gröbner bases
+//! are.
+//!
+//! We compute it with Buchberger's algorithm, which adds the remainders of S-polynomials (see
+//! [`MPoly::s_polynomial`]) to the basis until they all reduce to zero. Over ℚ, the coefficients
+//! along the way can be much larger than those in the answer, so
+//! [`modular_groebner_basis`] computes the basis modulo several primes instead and reconstructs
+//! the rational coefficients. The [`f4`] module has Faugère's F4 algorithm, which does many
+//! reductions at once with linear algebra, and the [`signature`] module has the GVW algorithm,
+//! which uses signatures to skip most S-polynomials that would reduce to zero. For
+//! zero-dimensional ideals, the [`fglm`] module converts a basis from one order to another with
+//! linear algebra, which is usually much quicker than computing a lex basis directly, and the
+//! [`walk`] module does the same for any ideal with the Gröbner walk.
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{
+//!     groebner::groebner_basis,
+//!     multivariate::{GrLex, MPoly},
+//!     rational::Rational,
+//! };
+//!
+//! let p = |terms: Vec<(Vec<u32>, i128, i128)>| {
+//!     MPoly::<Rational, GrLex>::new(
+//!         terms.into_iter().map(|(e, a, b)| (e, Rational::new(a, b))).collect(),
+//!     )
+//! };
+//! // x³ - 2xy and x²y - 2y² + x.
+//! let f = p(vec![(vec![3], 1, 1), (vec![1, 1], -2, 1)]);
+//! let g = p(vec![(vec![2, 1], 1, 1), (vec![0, 2], -2, 1), (vec![1], 1, 1)]);
+//! let basis = groebner_basis(&[f, g]);
+//! assert_eq!(
+//!     basis,
+//!     [
+//!         p(vec![(vec![0, 2], 1, 1), (vec![1], -1, 2)]),
+//!         p(vec![(vec![1, 1], 1, 1)]),
+//!         p(vec![(vec![2], 1, 1)]),
+//!     ]
+//! );
+//! // x² + xy is in the ideal, but x isn't.
+//! assert!(p(vec![(vec![2], 1, 1), (vec![1, 1], 1, 1)]).reduce(&basis).is_zero());
+//! assert!(!p(vec![(vec![1], 1, 1)]).reduce(&basis).is_zero());
+//! ```
+
+pub mod f4;
+pub mod fglm;
+pub mod signature;
+pub mod walk;
+
+use crate::ringlike::{
+    crt::crt,
+    finite_field::PrimeField,
+    multivariate::{MPoly, Monomial, MonomialOrder},
+    rational::Rational,
+    Field,
+};
+
+/// The reduced Gröbner basis of the ideal generated by `generators`, sorted by leading monomial
+/// from smallest to largest. The basis of the zero ideal is empty, and that of the whole ring is
+/// just 1.
+///
+/// This is Buchberger's algorithm. We keep a list of pairs of basis elements whose S-polynomials
+/// haven't been reduced yet, and always take the pair with the smallest lcm of leading monomials
+/// next, which is the "normal" selection strategy. Buchberger's two criteria let us skip pairs
+/// which we know would reduce to zero: pairs whose leading monomials are coprime, and pairs
+/// (*i*, *j*) where some other leading monomial divides their lcm and the pairs (*i*, *k*) and
+/// (*j*, *k*) have already been dealt with.
+pub fn groebner_basis<R, O>(generators: &[MPoly<R, O>]) -> Vec<MPoly<R, O>>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    let mut basis: Vec<MPoly<R, O>> = Vec::new();
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    for f in generators.iter().filter(|f| !f.is_zero()) {
+        pairs.extend((0..basis.len()).map(|i| (i, basis.len())));
+        basis.push(f.monic());
+    }
+    let lead = |basis: &[MPoly<R, O>], i: usize| -> Monomial {
+        basis[i].leading_monomial().expect("nonzero").clone()
+    };
+    while !pairs.is_empty() {
+        let lcms: Vec<Monomial> = pairs
+            .iter()
+            .map(|&(i, j)| lead(&basis, i).lcm(&lead(&basis, j)))
+            .collect();
+        let k = (0..pairs.len())
+            .min_by(|&a, &b| O::compare(&lcms[a], &lcms[b]))
+            .expect("there's a pair");
+        let (i, j) = pairs.swap_remove(k);
+        let lcm = &lcms[k];
+        if lead(&basis, i).is_coprime(&lead(&basis, j)) {
+            continue;
+        }
+        let pending = |a: usize, b: usize| pairs.contains(&(a.min(b), a.max(b)));
+        let chain = (0..basis.len()).any(|l| {
+            l != i && l != j && lead(&basis, l).divides(lcm) && !pending(i, l) && !pending(j, l)
+        });
+        if chain {
+            continue;
+        }
+        let h = basis[i].s_polynomial(&basis[j]).reduce(&basis);
+        if !h.is_zero() {
+            pairs.extend((0..basis.len()).map(|l| (l, basis.len())));
+            basis.push(h.monic());
+        }
+    }
+    reduce_basis(&basis)
+}
+
+/// Whether `basis` is a Gröbner basis of the ideal it generates, i.e. whether every S-polynomial
+/// reduces to zero. Pairs with coprime leading monomials always do, so we skip those.
+pub fn is_groebner_basis<R, O>(basis: &[MPoly<R, O>]) -> bool
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    let basis: Vec<MPoly<R, O>> = basis.iter().filter(|f| !f.is_zero()).cloned().collect();
+    (0..basis.len()).all(|j| {
+        (0..j).all(|i| {
+            let (a, b) = (
+                basis[i].leading_monomial().expect("nonzero"),
+                basis[j].leading_monomial().expect("nonzero"),
+            );
+            a.is_coprime(b) || basis[i].s_polynomial(&basis[j]).reduce(&basis).is_zero()
+        })
+    })
+}
+
+/// Turns a Gröbner basis into the reduced Gröbner basis of the same ideal, sorted by leading
+/// monomial from smallest to largest.
+///
+/// First we throw away the elements whose leading monomials are divisible by another's, which
+/// leaves a minimal Gröbner basis. Then we replace each element by its remainder on division by
+/// the others, and make it monic.
+pub fn reduce_basis<R, O>(basis: &[MPoly<R, O>]) -> Vec<MPoly<R, O>>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    let basis: Vec<MPoly<R, O>> = basis.iter().filter(|f| !f.is_zero()).cloned().collect();
+    let lead = |i: usize| basis[i].leading_monomial().expect("nonzero");
+    let minimal: Vec<MPoly<R, O>> = (0..basis.len())
+        .filter(|&i| {
+            !(0..basis.len())
+                .any(|j| j != i && lead(j).divides(lead(i)) && (lead(j) != lead(i) || j < i))
+        })
+        .map(|i| basis[i].clone())
+        .collect();
+    let mut reduced: Vec<MPoly<R, O>> = (0..minimal.len())
+        .map(|i| {
+            let others: Vec<MPoly<R, O>> = minimal
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, g)| g.clone())
+                .collect();
+            minimal[i].reduce(&others).monic()
+        })
+        .collect();
+    reduced.sort_by(|f, g| {
+        O::compare(
+            f.leading_monomial().expect("nonzero"),
+            g.leading_monomial().expect("nonzero"),
+        )
+    });
+    reduced
+}
+
+/// A reduced Gröbner basis modulo a prime, as the terms of each element with their coefficients'
+/// representatives in 0, 1, …, *p* - 1.
+type Image = Vec<Vec<(Monomial, u64)>>;
+
+/// A function computing the image of the reduced Gröbner basis modulo a particular prime.
+type Modular<O> = fn(&[MPoly<Rational, O>]) -> Option<Image>;
+
+/// A function checking whether every element of a basis lies in the ideal generated by some
+/// generators modulo a particular prime.
+type Membership<O> = fn(&[MPoly<Rational, O>], &[MPoly<Rational, O>]) -> Option<bool>;
+
+/// The images of `polynomials` modulo `P`, or [`None`] if `P` divides a denominator.
+fn reduce_modulo<O: MonomialOrder, const P: u64>(
+    polynomials: &[MPoly<Rational, O>],
+) -> Option<Vec<MPoly<PrimeField<P>, O>>> {
+    let p = i128::from(P);
+    polynomials
+        .iter()
+        .map(|f| {
+            (!f.terms().any(|(_, c)| c.denominator() % p == 0)).then(|| {
+                f.map_coefficients(|c| {
+                    PrimeField::<P>::new(c.numerator()) / PrimeField::<P>::new(c.denominator())
+                })
+            })
+        })
+        .collect()
+}
+
+/// The reduced Gröbner basis of the ideal generated by `generators` modulo `P`, or [`None`] if
+/// `P` divides a denominator, so the generators have no image.
+fn modular_image<O: MonomialOrder, const P: u64>(
+    generators: &[MPoly<Rational, O>],
+) -> Option<Image> {
+    Some(
+        groebner_basis(&reduce_modulo::<O, P>(generators)?)
+            .iter()
+            .map(|g| g.terms().map(|(m, c)| (m.clone(), c.value())).collect())
+            .collect(),
+    )
+}
+
+/// Whether every element of `basis` lies in the ideal generated by `generators` modulo `P`, or
+/// [`None`] if `P` divides a denominator of either.
+fn contained_modulo<O: MonomialOrder, const P: u64>(
+    basis: &[MPoly<Rational, O>],
+    generators: &[MPoly<Rational, O>],
+) -> Option<bool> {
+    let ideal = groebner_basis(&reduce_modulo::<O, P>(generators)?);
+    Some(
+        reduce_modulo::<O, P>(basis)?
+            .iter()
+            .all(|g| g.reduce(&ideal).is_zero()),
+    )
+}
+
+/// The images modulo the primes which gave bases with the same monomials.
+struct Images<O> {
+    /// The monomials of each element of the basis.
+    support: Vec<Vec<Monomial>>,
+    /// Every coefficient, in order, combined by the Chinese remainder theorem.
+    residues: Vec<i128>,
+    /// The product of the primes.
+    modulus: i128,
+    /// The primes themselves.
+    primes: Vec<i128>,
+    /// The last basis we reconstructed from these images.
+    candidate: Option<Vec<MPoly<Rational, O>>>,
+}
+
+impl<O: MonomialOrder> Images<O> {
+    /// The basis over ℚ with these images, if rational reconstruction works for every
+    /// coefficient.
+    fn reconstruct(&self) -> Option<Vec<MPoly<Rational, O>>> {
+        let mut residues = self.residues.iter();
+        self.support
+            .iter()
+            .map(|monomials| {
+                let terms: Option<Vec<(Monomial, Rational)>> = monomials
+                    .iter()
+                    .map(|m| {
+                        let r = residues.next().expect("one residue per term");
+                        Rational::reconstruct(*r, self.modulus).map(|c| (m.clone(), c))
+                    })
+                    .collect();
+                terms.map(MPoly::from_terms)
+            })
+            .collect()
+    }
+}
+
+/// The reduced Gröbner basis of the ideal generated by `generators`, computed by the modular
+/// method.
+///
+/// This gives the same answer as [`groebner_basis`], but the coefficients of the polynomials in
+/// the middle of Buchberger's algorithm over ℚ are often enormous, even when those of the input
+/// and output are small, and they can easily overflow an [`i128`]. Instead:
+///
+/// 1. We compute reduced Gröbner bases modulo primes just below 2³¹, skipping those which divide
+///    a denominator of the input.
+/// 2. Some primes are *unlucky*, and the basis modulo them isn't the image of the basis over ℚ.
+///    There are only finitely many, so we group the primes by which monomials appear in their
+///    bases, and trust the biggest group.
+/// 3. We combine the images in that group coefficientwise with the Chinese remainder theorem,
+///    and recover each rational coefficient by [`Rational::reconstruct`].
+/// 4. Once two moduli in a row give the same basis *G* over ℚ, we check over ℚ that it's a
+///    Gröbner basis and that every generator reduces to zero modulo it, so *I* ⊆ ⟨*G*⟩. Checking
+///    *G* ⊆ *I* exactly would need a Gröbner basis of *I* over ℚ, which is what we're trying to
+///    avoid, so instead we check that every element of *G* lies in *I* modulo a prime we haven't
+///    used yet, and return *G* if so.
+///
+/// The answer is therefore probabilistic: it's certainly a Gröbner basis of an ideal containing
+/// *I*, but it's only wrong if the primes we built it from and the one we checked it with were all
+/// unlucky, which for a fixed input happens for only finitely many primes.
+///
+/// Reconstruction is done in [`i128`], and the product of more than four primes just below 2³¹
+/// doesn't fit, so however many of the twelve primes are left, a basis is built from at most four
+/// of them. This bounds the coefficients we can recover to numerators and denominators below about
+/// 2⁶¹.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::ringlike::{
+///     groebner::{groebner_basis, modular_groebner_basis},
+///     multivariate::{Lex, MPoly},
+///     rational::Rational,
+/// };
+///
+/// let p = |terms: Vec<(Vec<u32>, i128, i128)>| {
+///     MPoly::<Rational, Lex>::new(
+///         terms.into_iter().map(|(e, a, b)| (e, Rational::new(a, b))).collect(),
+///     )
+/// };
+/// // Where the circle x² + y² = 1 meets the line x = y.
+/// let circle = p(vec![(vec![2], 1, 1), (vec![0, 2], 1, 1), (vec![], -1, 1)]);
+/// let line = p(vec![(vec![1], 1, 1), (vec![0, 1], -1, 1)]);
+/// let basis = modular_groebner_basis(&[circle.clone(), line.clone()]);
+/// assert_eq!(
+///     basis,
+///     [
+///         p(vec![(vec![0, 2], 1, 1), (vec![], -1, 2)]),
+///         p(vec![(vec![1], 1, 1), (vec![0, 1], -1, 1)]),
+///     ]
+/// );
+/// assert_eq!(basis, groebner_basis(&[circle, line]));
+/// ```
+///
+/// # Panics
+///
+/// Panics if we run out of primes before a candidate passes the checks, as happens when the
+/// coefficients of the basis are too large to reconstruct from the product of four primes, or if
+/// checking the answer overflows.
+pub fn modular_groebner_basis<O: MonomialOrder>(
+    generators: &[MPoly<Rational, O>],
+) -> Vec<MPoly<Rational, O>> {
+    if generators.iter().all(MPoly::is_zero) {
+        return Vec::new();
+    }
+    macro_rules! prime {
+        ($p:literal) => {
+            ($p, modular_image::<O, $p>, contained_modulo::<O, $p>)
+        };
+    }
+    let primes: [(i128, Modular<O>, Membership<O>); 12] = [
+        prime!(2147483647),
+        prime!(2147483629),
+        prime!(2147483587),
+        prime!(2147483579),
+        prime!(2147483563),
+        prime!(2147483549),
+        prime!(2147483543),
+        prime!(2147483497),
+        prime!(2147483489),
+        prime!(2147483477),
+        prime!(2147483423),
+        prime!(2147483399),
+    ];
+    let mut groups: Vec<Images<O>> = Vec::new();
+    for &(p, image, _) in &primes {
+        let Some(image) = image(generators) else {
+            continue;
+        };
+        let support: Vec<Vec<Monomial>> = image
+            .iter()
+            .map(|g| g.iter().map(|(m, _)| m.clone()).collect())
+            .collect();
+        let residues = image.iter().flatten().map(|&(_, c)| i128::from(c));
+        let index = match groups.iter().position(|g| g.support == support) {
+            Some(index) => {
+                let group = &mut groups[index];
+                let Some(modulus) = group.modulus.checked_mul(p) else {
+                    continue;
+                };
+                group.residues = group
+                    .residues
+                    .iter()
+                    .zip(residues)
+                    .map(|(&a, b)| crt(&[(a, group.modulus), (b, p)]).expect("coprime").0)
+                    .collect();
+                group.modulus = modulus;
+                group.primes.push(p);
+                index
+            }
+            None => {
+                groups.push(Images {
+                    support,
+                    residues: residues.collect(),
+                    modulus: p,
+                    primes: vec![p],
+                    candidate: None,
+                });
+                groups.len() - 1
+            }
+        };
+        let most = groups
+            .iter()
+            .map(|g| g.primes.len())
+            .max()
+            .expect("nonempty");
+        let group = &mut groups[index];
+        if group.primes.len() < most {
+            continue;
+        }
+        let candidate = group.reconstruct();
+        match candidate {
+            Some(basis) if group.candidate.as_ref() == Some(&basis) => {
+                // The images only tell us that G ⊆ I modulo the primes they came from, so we
+                // check that modulo another prime too.
+                let fresh = primes
+                    .iter()
+                    .filter(|(q, _, _)| !group.primes.contains(q))
+                    .find_map(|(_, _, contained)| contained(&basis, generators));
+                if is_groebner_basis(&basis)
+                    && generators.iter().all(|f| f.reduce(&basis).is_zero())
+                    && fresh == Some(true)
+                {
+                    return basis;
+                }
+                group.candidate = None;
+            }
+            candidate => group.candidate = candidate,
+        }
+    }
+    panic!("ran out of primes before the reconstructed basis passed the checks");
+}
@@ -0,0 +1,449 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Faugère's F4 algorithm for Gröbner bases.
+//!
+//! Buchberger's algorithm reduces one S-polynomial at a time, and spends most of its time
+//! repeating the same reductions. F4 instead takes every pair with the smallest *sugar* at once
+//! and reduces them all together with linear algebra. Each S-polynomial (*m*/LT(*f*)) *f* -
+//! (*m*/LT(*g*)) *g* is a difference of two rows of a Macaulay-style matrix, whose columns are
+//! monomials sorted by the [`MonomialOrder`]. *Symbolic preprocessing* adds a row *tg* for every
+//! monomial which some *g* in the basis could cancel, so that Gaussian elimination performs all
+//! the reductions at once. The rows of the echelon form with new leading monomials join the
+//! basis.
+//!
+//! The sugar of a generator is its total degree, that of *tf* is the sugar of *f* plus the degree
+//! of *t*, and that of an S-polynomial is the larger sugar of its halves. It's the degree the
+//! polynomial would have if we'd homogenised the generators. For an order which compares degrees
+//! first, it's just the degree of the lcm, but for [`Lex`](crate::ringlike::multivariate::Lex)
+//! and other orders which don't, it stops us from choosing pairs of small degree whose reductions
+//! pull in reducers of enormous degree.
+//!
+//! We use the Gebauer–Möller criteria to decide which pairs to keep, and the result is the same
+//! reduced Gröbner basis as [`super::groebner_basis`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{
+//!     finite_field::PrimeField,
+//!     groebner::{f4, groebner_basis},
+//!     multivariate::{GRevLex, MPoly},
+//! };
+//!
+//! type P = MPoly<PrimeField<32003>, GRevLex>;
+//!
+//! // The cyclic 4-roots problem.
+//! let (a, b, c, d) = (P::var(0), P::var(1), P::var(2), P::var(3));
+//! let generators = [
+//!     &(&(&a + &b) + &c) + &d,
+//!     &(&(&(&a * &b) + &(&b * &c)) + &(&c * &d)) + &(&d * &a),
+//!     &(&(&(&(&a * &b) * &c) + &(&(&b * &c) * &d)) + &(&(&c * &d) * &a)) + &(&(&d * &a) * &b),
+//!     &(&(&(&a * &b) * &c) * &d) - &P::constant(1.into()),
+//! ];
+//! let basis = f4::groebner_basis(&generators);
+//! assert_eq!(basis.len(), 7);
+//! assert_eq!(basis, groebner_basis(&generators));
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, HashSet},
+};
+
+use crate::ringlike::{
+    groebner::reduce_basis,
+    multivariate::{MPoly, Monomial, MonomialOrder},
+    Field,
+};
+
+/// A critical pair of indices into the list of polynomials, with the lcm of their leading
+/// monomials and the sugar of their S-polynomial.
+#[derive(Clone, Debug)]
+struct Pair {
+    first: usize,
+    second: usize,
+    lcm: Monomial,
+    sugar: u32,
+}
+
+/// The polynomials we've found so far with their sugars, and which of them are still in the
+/// basis.
+struct Basis<R, O> {
+    polynomials: Vec<MPoly<R, O>>,
+    sugars: Vec<u32>,
+    active: Vec<usize>,
+}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Basis<R, O> {
+    fn lead(&self, i: usize) -> &Monomial {
+        self.polynomials[i]
+            .leading_monomial()
+            .expect("basis elements are nonzero")
+    }
+
+    /// The sugar of *t* times the `i`-th polynomial, for *t* the quotient of `m` by its leading
+    /// monomial.
+    fn sugar(&self, i: usize, m: &Monomial) -> u32 {
+        self.sugars[i] + m.degree() - self.lead(i).degree()
+    }
+
+    /// Adds `h` with sugar `sugar` to the basis, updating the pairs with the Gebauer–Möller
+    /// criteria.
+    ///
+    /// Of the new pairs (*h*, *g*), we drop those whose lcm is a proper multiple of another new
+    /// pair's lcm, keeping one of each lcm, and then those where the leading monomials are
+    /// coprime. Of the old pairs (*g*₁, *g*₂), we drop those where LM(*h*) divides the lcm, unless
+    /// the lcm of *h* with either one is the same. Finally, any *g* whose leading monomial is a
+    /// multiple of LM(*h*) leaves the basis, though its pairs stay.
+    fn update(&mut self, h: MPoly<R, O>, sugar: u32, pairs: &mut Vec<Pair>) {
+        let index = self.polynomials.len();
+        self.polynomials.push(h);
+        self.sugars.push(sugar);
+        let lead = self.lead(index).clone();
+        let mut candidates: Vec<Pair> = self
+            .active
+            .iter()
+            .map(|&g| {
+                let lcm = self.lead(g).lcm(&lead);
+                Pair {
+                    first: g,
+                    second: index,
+                    sugar: self.sugar(g, &lcm).max(self.sugar(index, &lcm)),
+                    lcm,
+                }
+            })
+            .collect();
+        let mut kept: Vec<Pair> = Vec::new();
+        while let Some(pair) = candidates.pop() {
+            let coprime = self.lead(pair.first).is_coprime(&lead);
+            let beaten = candidates
+                .iter()
+                .chain(&kept)
+                .any(|other| other.lcm.divides(&pair.lcm));
+            if coprime || !beaten {
+                kept.push(pair);
+            }
+        }
+        kept.retain(|pair| !self.lead(pair.first).is_coprime(&lead));
+        pairs.retain(|pair| {
+            !lead.divides(&pair.lcm)
+                || self.lead(pair.first).lcm(&lead) == pair.lcm
+                || self.lead(pair.second).lcm(&lead) == pair.lcm
+        });
+        pairs.extend(kept);
+        let polynomials = &self.polynomials;
+        self.active
+            .retain(|&g| !lead.divides(polynomials[g].leading_monomial().expect("nonzero")));
+        self.active.push(index);
+    }
+
+    /// An active basis element whose leading monomial divides `m`, with the quotient.
+    fn reducer(&self, m: &Monomial) -> Option<(usize, Monomial)> {
+        self.active
+            .iter()
+            .find_map(|&g| m.checked_div(self.lead(g)).map(|t| (g, t)))
+    }
+}
+
+/// A row of the matrix, as the columns of its nonzero entries in increasing order, i.e. from the
+/// largest monomial down, with the entries.
+type Row<R> = Vec<(usize, R)>;
+
+/// Puts `rows` into row echelon form, where every row has a different leading column with a 1
+/// there, and returns the nonzero rows.
+///
+/// Each row is copied into a sparse map from columns to entries, and then each entry with a pivot
+/// row is cancelled, in increasing order of column from the leading one, before the row becomes a
+/// pivot row itself. That way the new rows are also reduced by every pivot which came before them,
+/// and the work for each row only depends on the entries it and its pivots actually have.
+fn echelon<R: Field + Clone + PartialEq>(rows: Vec<Row<R>>) -> Vec<Row<R>> {
+    let mut pivots: HashMap<usize, Row<R>> = HashMap::new();
+    let mut order: Vec<usize> = Vec::new();
+    for row in rows {
+        let mut working: BTreeMap<usize, R> = row.into_iter().collect();
+        let mut lead = None;
+        let mut next = 0;
+        while let Some((c, a)) = working.range(next..).next().map(|(&c, a)| (c, a.clone())) {
+            next = c + 1;
+            match pivots.get(&c) {
+                Some(pivot) => {
+                    for (d, b) in pivot {
+                        let entry = working.entry(*d).or_insert(R::ZERO);
+                        *entry = entry.sub(a.mul(b.clone()));
+                        if *entry == R::ZERO {
+                            working.remove(d);
+                        }
+                    }
+                }
+                None if lead.is_none() => lead = Some(c),
+                None => {}
+            }
+        }
+        let Some(lead) = lead else {
+            continue;
+        };
+        let inverse = working[&lead].inverse_mul().expect("the pivot is nonzero");
+        let reduced = working
+            .into_iter()
+            .map(|(c, a)| (c, a.mul(inverse.clone())))
+            .collect();
+        pivots.insert(lead, reduced);
+        order.push(lead);
+    }
+    order
+        .into_iter()
+        .map(|c| pivots.remove(&c).expect("every pivot is recorded"))
+        .collect()
+}
+
+/// The reduced Gröbner basis of the ideal generated by `generators`, sorted by leading monomial
+/// from smallest to largest, computed with F4. See the [module documentation](self).
+pub fn groebner_basis<R, O>(generators: &[MPoly<R, O>]) -> Vec<MPoly<R, O>>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    let mut basis = Basis {
+        polynomials: Vec::new(),
+        sugars: Vec::new(),
+        active: Vec::new(),
+    };
+    let mut pairs: Vec<Pair> = Vec::new();
+    for f in generators.iter().filter(|f| !f.is_zero()) {
+        let sugar = f.total_degree().expect("nonzero");
+        basis.update(f.monic(), sugar, &mut pairs);
+    }
+    while let Some(sugar) = pairs.iter().map(|pair| pair.sugar).min() {
+        let (selected, rest): (Vec<Pair>, Vec<Pair>) =
+            pairs.into_iter().partition(|pair| pair.sugar == sugar);
+        pairs = rest;
+        // Both halves of each S-polynomial, as multipliers of basis elements.
+        let mut products: Vec<(Monomial, usize)> = Vec::new();
+        for pair in &selected {
+            for g in [pair.first, pair.second] {
+                let t = pair
+                    .lcm
+                    .checked_div(basis.lead(g))
+                    .expect("it divides the lcm");
+                if !products.contains(&(t.clone(), g)) {
+                    products.push((t, g));
+                }
+            }
+        }
+        // Symbolic preprocessing: add a reducer for every monomial we can reduce.
+        let mut rows: Vec<MPoly<R, O>> = products
+            .iter()
+            .map(|(t, g)| basis.polynomials[*g].mul_term(&R::ONE, t))
+            .collect();
+        let mut done: HashSet<Monomial> = rows
+            .iter()
+            .map(|f| f.leading_monomial().expect("nonzero").clone())
+            .collect();
+        let mut monomials: HashSet<Monomial> = HashSet::new();
+        let mut next = 0;
+        while next < rows.len() {
+            let new: Vec<Monomial> = rows[next].support().cloned().collect();
+            next += 1;
+            for m in new {
+                if monomials.insert(m.clone()) && done.insert(m.clone()) {
+                    if let Some((g, t)) = basis.reducer(&m) {
+                        rows.push(basis.polynomials[g].mul_term(&R::ONE, &t));
+                    }
+                }
+            }
+        }
+        let leads: HashSet<&Monomial> = rows
+            .iter()
+            .map(|f| f.leading_monomial().expect("nonzero"))
+            .collect();
+        let mut columns: Vec<Monomial> = monomials.into_iter().collect();
+        columns.sort_by(|a, b| O::compare(b, a));
+        let index: HashMap<&Monomial, usize> =
+            columns.iter().enumerate().map(|(i, m)| (m, i)).collect();
+        let mut matrix: Vec<Row<R>> = rows
+            .iter()
+            .map(|f| f.terms().map(|(m, c)| (index[m], c.clone())).collect())
+            .collect();
+        // Smallest leading monomials first, so the reducers for a row's tail are already pivots
+        // when we reach it. Then every pivot is reduced as far as it can be, which keeps the
+        // coefficients over ℚ from growing.
+        matrix.sort_by_key(|row| Reverse(row[0].0));
+        for row in echelon(matrix) {
+            if leads.contains(&columns[row[0].0]) {
+                continue;
+            }
+            let h = MPoly::from_terms(row.into_iter().map(|(c, a)| (columns[c].clone(), a)));
+            basis.update(h, sugar, &mut pairs);
+        }
+    }
+    let active: Vec<MPoly<R, O>> = basis
+        .active
+        .iter()
+        .map(|&g| basis.polynomials[g].clone())
+        .collect();
+    reduce_basis(&active)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ringlike::{
+        finite_field::PrimeField,
+        groebner,
+        multivariate::{GRevLex, GrLex, Lex},
+        rational::Rational,
+        Ring,
+    };
+
+    /// A polynomial from its terms, as exponents with integer coefficients.
+    fn poly<R, O>(terms: &[(&[u32], i128)]) -> MPoly<R, O>
+    where
+        R: Field + Clone + PartialEq + From<i128>,
+        O: MonomialOrder,
+    {
+        MPoly::new(terms.iter().map(|&(e, c)| (e.to_vec(), c.into())).collect())
+    }
+
+    fn systems<R, O>() -> Vec<Vec<MPoly<R, O>>>
+    where
+        R: Field + Clone + PartialEq + From<i128>,
+        O: MonomialOrder,
+    {
+        let circle = poly(&[(&[2], 1), (&[0, 2], 1), (&[], -1)]);
+        let line = poly(&[(&[1], 1), (&[0, 1], -1)]);
+        vec![
+            // The circle meeting a line.
+            vec![circle.clone(), line.clone()],
+            // The twisted cubic.
+            vec![
+                poly(&[(&[0, 1], 1), (&[2], -1)]),
+                poly(&[(&[0, 0, 1], 1), (&[3], -1)]),
+            ],
+            // Cyclic 3-roots.
+            vec![
+                poly(&[(&[1], 1), (&[0, 1], 1), (&[0, 0, 1], 1)]),
+                poly(&[(&[1, 1], 1), (&[0, 1, 1], 1), (&[1, 0, 1], 1)]),
+                poly(&[(&[1, 1, 1], 1), (&[], -1)]),
+            ],
+            // Katsura 3.
+            vec![
+                poly(&[(&[1], 1), (&[0, 1], 2), (&[0, 0, 1], 2), (&[], -1)]),
+                poly(&[(&[2], 1), (&[0, 2], 2), (&[0, 0, 2], 2), (&[1], -1)]),
+                poly(&[(&[1, 1], 2), (&[0, 1, 1], 2), (&[0, 1], -1)]),
+            ],
+            // A positive dimensional ideal which isn't prime.
+            vec![
+                poly(&[(&[1, 1], 1), (&[0, 0, 1], -1)]),
+                poly(&[(&[1, 0, 1], 1), (&[0, 1], -1)]),
+            ],
+            // Redundant generators: a repeat, a multiple and a combination of the others.
+            vec![
+                circle.clone(),
+                line.clone(),
+                circle.clone(),
+                &line * &poly(&[(&[0, 3], 2), (&[1], 1)]),
+                &circle + &(&line * &line),
+            ],
+            // The unit ideal.
+            vec![poly(&[(&[1], 1)]), poly(&[(&[1], 1), (&[], -1)])],
+            vec![&circle * &line, poly(&[(&[], 3)])],
+            // No generators, only zero generators, and some zero generators.
+            vec![],
+            vec![MPoly::ZERO, MPoly::ZERO],
+            vec![MPoly::ZERO, line, MPoly::ZERO, circle],
+        ]
+    }
+
+    /// Systems whose coefficients over ℚ overflow an [`i128`], so we only use them over prime
+    /// fields.
+    fn modular_systems<R, O>() -> Vec<Vec<MPoly<R, O>>>
+    where
+        R: Field + Clone + PartialEq + From<i128>,
+        O: MonomialOrder,
+    {
+        vec![
+            // This exhausts memory under lex if we choose pairs by the degree of their lcm.
+            vec![
+                poly(&[(&[2, 1], 3), (&[0, 2, 1], -3), (&[0, 0, 1], -3)]),
+                poly(&[(&[1, 2, 2], -2), (&[1], -1), (&[0, 2, 2], -2)]),
+                poly(&[(&[2, 2], 3), (&[0, 1, 2], -2), (&[0, 0, 1], 1)]),
+            ],
+        ]
+    }
+
+    fn agrees_with_buchberger<R, O>(systems: Vec<Vec<MPoly<R, O>>>)
+    where
+        R: Field + Clone + PartialEq + From<i128> + std::fmt::Debug,
+        O: MonomialOrder,
+    {
+        for generators in systems {
+            let basis = groebner_basis(&generators);
+            assert_eq!(basis, groebner::groebner_basis(&generators));
+            assert!(generators.iter().all(|f| f.reduce(&basis).is_zero()));
+        }
+    }
+
+    #[test]
+    fn agrees_over_the_rationals() {
+        agrees_with_buchberger(systems::<Rational, Lex>());
+        agrees_with_buchberger(systems::<Rational, GrLex>());
+        agrees_with_buchberger(systems::<Rational, GRevLex>());
+    }
+
+    #[test]
+    fn agrees_over_a_prime_field() {
+        type F = PrimeField<32003>;
+        agrees_with_buchberger([systems::<F, Lex>(), modular_systems()].concat());
+        agrees_with_buchberger([systems::<F, GrLex>(), modular_systems()].concat());
+        agrees_with_buchberger([systems::<F, GRevLex>(), modular_systems()].concat());
+    }
+
+    #[test]
+    fn unit_and_zero_ideals() {
+        let unit: [MPoly<Rational, GRevLex>; 2] =
+            [poly(&[(&[1], 1)]), poly(&[(&[1], 1), (&[], -1)])];
+        assert_eq!(groebner_basis(&unit), [MPoly::ONE]);
+        let unit: [MPoly<PrimeField<32003>, Lex>; 2] = [
+            poly(&[(&[0, 1], 2)]),
+            poly(&[(&[1, 1], 1), (&[0, 1], 1), (&[], -3)]),
+        ];
+        assert_eq!(groebner_basis(&unit), [MPoly::ONE]);
+        assert!(groebner_basis::<Rational, GrLex>(&[]).is_empty());
+        assert!(groebner_basis::<Rational, GrLex>(&[MPoly::ZERO, MPoly::ZERO]).is_empty());
+    }
+
+    #[test]
+    fn echelon_reduces_every_row() {
+        type F = PrimeField<7>;
+        let row = |entries: &[(usize, i128)]| -> Row<F> {
+            entries.iter().map(|&(c, a)| (c, F::new(a))).collect()
+        };
+        // The third row is the sum of the first two, and the fourth cancels down to column 3.
+        let rows = vec![
+            row(&[(0, 2), (2, 1)]),
+            row(&[(1, 1), (2, 3), (3, 1)]),
+            row(&[(0, 2), (1, 1), (2, 4), (3, 1)]),
+            row(&[(0, 1), (2, 4), (3, 2)]),
+        ];
+        assert_eq!(
+            echelon(rows),
+            [
+                row(&[(0, 1), (2, 4)]),
+                row(&[(1, 1), (2, 3), (3, 1)]),
+                row(&[(3, 1)]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Changing the monomial order of a Gröbner basis with the FGLM algorithm, of Faugère, Gianni,
+//! Lazard and Mora.
+//!
+//! Lex Gröbner bases are the ones we want for solving systems of equations, but they're usually
+//! much slower to compute than grevlex ones. If the ideal *I* ⊆ *K*\[*x*₁, …, *xₙ*\] is
+//! *zero-dimensional*, i.e. it has finitely many solutions over the algebraic closure, then the
+//! quotient *K*\[*x*₁, …, *xₙ*\]/*I* is a finite-dimensional vector space. The *standard
+//! monomials*, those not divisible by any leading monomial of the basis, form a basis of it, and
+//! the remainder on division by the basis gives the coordinates of any polynomial.
+//!
+//! FGLM goes through the monomials in the new order from the smallest up. If the remainder of a
+//! monomial *m* is linearly independent of those we've kept so far, then *m* is a standard
+//! monomial for the new order. Otherwise, the linear dependency gives a polynomial in *I* with
+//! leading monomial *m*, which goes into the new basis, and we skip the multiples of *m*. This
+//! takes only linear algebra, without any S-polynomials.
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{
+//!     groebner::{fglm, groebner_basis},
+//!     multivariate::{GRevLex, Lex, MPoly},
+//!     rational::Rational,
+//! };
+//!
+//! type P = MPoly<Rational, GRevLex>;
+//!
+//! // The circle x² + y² = 1 meets the parabola y = x² at four points.
+//! let (x, y) = (P::var(0), P::var(1));
+//! let generators = [
+//!     &(&(&x * &x) + &(&y * &y)) - &P::constant(1.into()),
+//!     &y - &(&x * &x),
+//! ];
+//! let basis = groebner_basis(&generators);
+//! assert_eq!(fglm::standard_monomials(&basis, 2).map(|s| s.len()), Some(4));
+//! let lex = fglm::fglm::<_, _, Lex>(&basis, 2).expect("the ideal is zero-dimensional");
+//! let reordered: Vec<MPoly<Rational, Lex>> = generators.iter().map(|f| f.reorder()).collect();
+//! assert_eq!(lex, groebner_basis(&reordered));
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use crate::ringlike::{
+    multivariate::{MPoly, Monomial, MonomialOrder},
+    Field,
+};
+
+/// The standard monomials of the ideal with the Gröbner basis `basis` in `variables` variables,
+/// i.e. those not divisible by any leading monomial, from smallest to largest. There are finitely
+/// many exactly when the ideal is zero-dimensional, and otherwise we return [`None`].
+pub fn standard_monomials<R, O>(basis: &[MPoly<R, O>], variables: usize) -> Option<Vec<Monomial>>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    let leads: Vec<&Monomial> = basis.iter().filter_map(|f| f.leading_monomial()).collect();
+    // Every variable needs a pure power among the leading monomials, otherwise all its powers are
+    // standard, unless the ideal is the whole ring.
+    let bounded = leads.iter().any(|m| m.is_one())
+        || (0..variables).all(|i| {
+            leads
+                .iter()
+                .any(|m| m.exponent(i) > 0 && m.degree() == m.exponent(i))
+        });
+    if !bounded {
+        return None;
+    }
+    let is_standard = |m: &Monomial| !leads.iter().any(|l| l.divides(m));
+    let mut found: Vec<Monomial> = Vec::new();
+    if is_standard(&Monomial::one()) {
+        found.push(Monomial::one());
+    }
+    let mut seen: HashSet<Monomial> = found.iter().cloned().collect();
+    let mut next = 0;
+    while next < found.len() {
+        let m = found[next].clone();
+        next += 1;
+        for i in 0..variables {
+            let n = &m * &Monomial::var(i);
+            if is_standard(&n) && seen.insert(n.clone()) {
+                found.push(n);
+            }
+        }
+    }
+    found.sort_by(O::compare);
+    Some(found)
+}
+
+/// The reduced Gröbner basis for the order `P` of the ideal with the reduced Gröbner basis
+/// `basis` for `O`, in `variables` variables, or [`None`] if the ideal isn't zero-dimensional.
+/// See the [module documentation](self).
+pub fn fglm<R, O, P>(basis: &[MPoly<R, O>], variables: usize) -> Option<Vec<MPoly<R, P>>>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+    P: MonomialOrder,
+{
+    standard_monomials(basis, variables)?;
+    // Each row is a remainder, in echelon form, and the combination of monomials it's the
+    // remainder of. The rows are indexed by their leading monomials.
+    let mut rows: Vec<(MPoly<R, O>, MPoly<R, P>)> = Vec::new();
+    let mut pivots: HashMap<Monomial, usize> = HashMap::new();
+    let mut new_basis: Vec<MPoly<R, P>> = Vec::new();
+    let mut candidates: Vec<Monomial> = vec![Monomial::one()];
+    let mut seen: HashSet<Monomial> = candidates.iter().cloned().collect();
+    while let Some(k) =
+        (0..candidates.len()).min_by(|&a, &b| P::compare(&candidates[a], &candidates[b]))
+    {
+        let m = candidates.swap_remove(k);
+        if new_basis
+            .iter()
+            .any(|g| g.leading_monomial().expect("nonzero").divides(&m))
+        {
+            continue;
+        }
+        let mut remainder = MPoly::term(R::ONE, m.clone()).reduce(basis);
+        let mut combination = MPoly::<R, P>::term(R::ONE, m.clone());
+        while let Some(&row) = remainder.leading_monomial().and_then(|l| pivots.get(l)) {
+            let (r, c) = &rows[row];
+            let factor = remainder.leading_coefficient().div(r.leading_coefficient());
+            remainder = &remainder - &r.scale(&factor);
+            combination = &combination - &c.scale(&factor);
+        }
+        match remainder.leading_monomial() {
+            // Everything else in the combination is a smaller standard monomial, so it's already
+            // reduced.
+            None => new_basis.push(combination),
+            Some(lead) => {
+                pivots.insert(lead.clone(), rows.len());
+                rows.push((remainder, combination));
+                for i in 0..variables {
+                    let n = &m * &Monomial::var(i);
+                    if seen.insert(n.clone()) {
+                        candidates.push(n);
+                    }
+                }
+            }
+        }
+    }
+    Some(new_basis)
+}
@@ -0,0 +1,375 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Signature-based Gröbner bases, using the algorithm of Gao, Volny and Wang (GVW).
+//!
+//! Most of the time in Buchberger's algorithm goes on S-polynomials which reduce to zero, and so
+//! tell us nothing new. Signature-based algorithms like F5 and GVW avoid most of them by
+//! remembering where each polynomial came from. If *f*₁, …, *fₘ* are the generators, every *v* in
+//! the ideal is *u*₁*f*₁ + ⋯ + *uₘfₘ* for some (*u*₁, …, *uₘ*) in the free module *R*ᵐ, and the
+//! *signature* of *v* is the leading monomial *te*ᵢ of such a *u*. We compare signatures position
+//! over term: first by *i*, then by *t*.
+//!
+//! We only ever reduce *v* by multiples of other elements with smaller signatures, so that
+//! signatures never change. Then:
+//!
+//! * If a pair's signature is divisible by that of a known syzygy, such as
+//!   *v*₂(*u*₁, *v*₁) - *v*₁(*u*₂, *v*₂), its S-polynomial would reduce to zero, so we skip it.
+//! * If some element of the basis with a signature dividing the pair's would give a smaller
+//!   leading monomial, the pair is *covered*, and again we skip it.
+//!
+//! Every pair which does reduce to zero gives a new syzygy, which then rules out others.
+//! [`groebner_basis_with_statistics`] reports how well this works.
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{
+//!     groebner::{groebner_basis, signature},
+//!     multivariate::{GRevLex, MPoly},
+//!     rational::Rational,
+//!     Ring,
+//! };
+//!
+//! type P = MPoly<Rational, GRevLex>;
+//!
+//! // The cyclic 3-roots problem.
+//! let (x, y, z) = (P::var(0), P::var(1), P::var(2));
+//! let generators = [
+//!     &(&x + &y) + &z,
+//!     &(&(&x * &y) + &(&y * &z)) + &(&z * &x),
+//!     &(&(&x * &y) * &z) - &P::ONE,
+//! ];
+//! let (basis, statistics) = signature::groebner_basis_with_statistics(&generators);
+//! assert_eq!(basis, groebner_basis(&generators));
+//! // The generators form a regular sequence, so nothing reduces to zero.
+//! assert_eq!(statistics.reductions_to_zero, 0);
+//! assert!(statistics.pairs_rejected > 0);
+//! ```
+
+use std::{
+    cmp::Ordering,
+    time::{Duration, Instant},
+};
+
+use crate::ringlike::{
+    groebner::reduce_basis,
+    multivariate::{MPoly, Monomial, MonomialOrder},
+    Field,
+};
+
+/// What happened while running [`groebner_basis_with_statistics`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Statistics {
+    /// The number of pairs we looked at, including those we skipped.
+    pub pairs_considered: usize,
+    /// The number of pairs skipped by the syzygy or covering criteria.
+    pub pairs_rejected: usize,
+    /// The number of pairs which we reduced, only to get zero.
+    pub reductions_to_zero: usize,
+    /// The size of the basis before reducing it.
+    pub basis_size: usize,
+    /// How long the computation took.
+    pub elapsed: Duration,
+}
+
+/// A signature *te*ᵢ, the leading monomial of an element of the free module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Signature {
+    index: usize,
+    monomial: Monomial,
+}
+
+impl Signature {
+    /// Position over term: compare *i*, then *t*.
+    fn compare<O: MonomialOrder>(&self, other: &Self) -> Ordering {
+        self.index
+            .cmp(&other.index)
+            .then_with(|| O::compare(&self.monomial, &other.monomial))
+    }
+
+    fn times(&self, t: &Monomial) -> Self {
+        Signature {
+            index: self.index,
+            monomial: &self.monomial * t,
+        }
+    }
+
+    /// `other` / `self`, if `self` divides `other`.
+    fn divides(&self, other: &Self) -> Option<Monomial> {
+        if self.index == other.index {
+            other.monomial.checked_div(&self.monomial)
+        } else {
+            None
+        }
+    }
+}
+
+/// A J-pair *t*(*uᵢ*, *vᵢ*), with its signature *tuᵢ*.
+struct Pair {
+    signature: Signature,
+    multiplier: Monomial,
+    element: usize,
+}
+
+/// The basis elements (*u*, *v*), each stored as the signature of *u* and the polynomial *v*,
+/// together with the signatures of the syzygies we know about.
+struct Basis<R, O> {
+    elements: Vec<(Signature, MPoly<R, O>)>,
+    syzygies: Vec<Signature>,
+}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Basis<R, O> {
+    fn lead(&self, i: usize) -> &Monomial {
+        self.elements[i]
+            .1
+            .leading_monomial()
+            .expect("basis elements are nonzero")
+    }
+
+    /// The J-pair of elements `i` and `j`, which is the multiple of the one with the larger
+    /// signature that cancels the leading terms, or [`None`] if their signatures are the same.
+    fn j_pair(&self, i: usize, j: usize) -> Option<Pair> {
+        let lcm = self.lead(i).lcm(self.lead(j));
+        let a = lcm.checked_div(self.lead(i)).expect("it divides the lcm");
+        let b = lcm.checked_div(self.lead(j)).expect("it divides the lcm");
+        let (s, t) = (self.elements[i].0.times(&a), self.elements[j].0.times(&b));
+        match s.compare::<O>(&t) {
+            Ordering::Greater => Some(Pair {
+                signature: s,
+                multiplier: a,
+                element: i,
+            }),
+            Ordering::Less => Some(Pair {
+                signature: t,
+                multiplier: b,
+                element: j,
+            }),
+            Ordering::Equal => None,
+        }
+    }
+
+    /// Whether a known syzygy's signature divides `signature`.
+    fn is_syzygy(&self, signature: &Signature) -> bool {
+        self.syzygies.iter().any(|s| s.divides(signature).is_some())
+    }
+
+    /// Whether some element (*u*, *v*) with *u* dividing the pair's signature *T* has
+    /// (*T*/*u*) LM(*v*) smaller than the pair's leading monomial.
+    fn is_covered(&self, pair: &Pair) -> bool {
+        let lead = &pair.multiplier * self.lead(pair.element);
+        self.elements.iter().any(|(u, v)| {
+            u.divides(&pair.signature).is_some_and(|t| {
+                let m = &t * v.leading_monomial().expect("nonzero");
+                O::compare(&m, &lead) == Ordering::Less
+            })
+        })
+    }
+
+    /// An element (*u*, *v*) and a monomial *t* with LM(*v*) *t* = `m`, and with *tu* smaller than
+    /// `signature`, or equal to it if `singular`.
+    fn reducer(
+        &self,
+        m: &Monomial,
+        signature: &Signature,
+        singular: bool,
+    ) -> Option<(usize, Monomial)> {
+        self.elements.iter().enumerate().find_map(|(i, (u, v))| {
+            let t = m.checked_div(v.leading_monomial().expect("nonzero"))?;
+            let ordering = u.times(&t).compare::<O>(signature);
+            (ordering == Ordering::Less || (singular && ordering == Ordering::Equal))
+                .then_some((i, t))
+        })
+    }
+
+    /// Cancels the leading term of `v` with multiples of elements of smaller signature, for as
+    /// long as we can.
+    fn regular_top_reduce(&self, mut v: MPoly<R, O>, signature: &Signature) -> MPoly<R, O> {
+        while let Some(m) = v.leading_monomial().cloned() {
+            let Some((i, t)) = self.reducer(&m, signature, false) else {
+                break;
+            };
+            let g = &self.elements[i].1;
+            let c = v.leading_coefficient().div(g.leading_coefficient());
+            v = &v - &g.mul_term(&c, &t);
+        }
+        v
+    }
+
+    /// Adds (`signature`, `v`) to the basis, along with its J-pairs and the principal syzygies
+    /// with every other element.
+    fn insert(&mut self, signature: Signature, v: MPoly<R, O>, pairs: &mut Vec<Pair>) {
+        let lead = v.leading_monomial().expect("nonzero").clone();
+        for (u, w) in &self.elements {
+            // The signature of w (signature, v) - v (u, w).
+            let s = signature.times(w.leading_monomial().expect("nonzero"));
+            let t = u.times(&lead);
+            match s.compare::<O>(&t) {
+                Ordering::Greater => self.syzygies.push(s),
+                Ordering::Less => self.syzygies.push(t),
+                Ordering::Equal => {}
+            }
+        }
+        self.elements.push((signature, v.monic()));
+        let new = self.elements.len() - 1;
+        for j in 0..new {
+            let Some(pair) = self.j_pair(new, j) else {
+                continue;
+            };
+            // Of two pairs with the same signature, the one with the smaller leading monomial
+            // covers the other once we've dealt with it.
+            let lead = &pair.multiplier * self.lead(pair.element);
+            match pairs.iter_mut().find(|p| p.signature == pair.signature) {
+                Some(other) => {
+                    let other_lead = &other.multiplier * self.lead(other.element);
+                    if O::compare(&lead, &other_lead) == Ordering::Less {
+                        *other = pair;
+                    }
+                }
+                None => pairs.push(pair),
+            }
+        }
+    }
+}
+
+/// The reduced Gröbner basis of the ideal generated by `generators`, sorted by leading monomial
+/// from smallest to largest, computed with the GVW algorithm. See the
+/// [module documentation](self).
+pub fn groebner_basis<R, O>(generators: &[MPoly<R, O>]) -> Vec<MPoly<R, O>>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    groebner_basis_with_statistics(generators).0
+}
+
+/// Like [`groebner_basis`], but also reports how many pairs the signature criteria saved us from
+/// reducing.
+pub fn groebner_basis_with_statistics<R, O>(
+    generators: &[MPoly<R, O>],
+) -> (Vec<MPoly<R, O>>, Statistics)
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    let start = Instant::now();
+    let mut statistics = Statistics::default();
+    let mut basis = Basis {
+        elements: Vec::new(),
+        syzygies: Vec::new(),
+    };
+    let mut pairs: Vec<Pair> = Vec::new();
+    for (index, f) in generators.iter().filter(|f| !f.is_zero()).enumerate() {
+        let signature = Signature {
+            index,
+            monomial: Monomial::one(),
+        };
+        basis.insert(signature, f.clone(), &mut pairs);
+    }
+    while !pairs.is_empty() {
+        let k = (0..pairs.len())
+            .min_by(|&a, &b| pairs[a].signature.compare::<O>(&pairs[b].signature))
+            .expect("there's a pair");
+        let pair = pairs.swap_remove(k);
+        statistics.pairs_considered += 1;
+        if basis.is_syzygy(&pair.signature) || basis.is_covered(&pair) {
+            statistics.pairs_rejected += 1;
+            continue;
+        }
+        let v = basis.elements[pair.element]
+            .1
+            .mul_term(&R::ONE, &pair.multiplier);
+        let v = basis.regular_top_reduce(v, &pair.signature);
+        match v.leading_monomial() {
+            None => {
+                statistics.reductions_to_zero += 1;
+                basis.syzygies.push(pair.signature);
+            }
+            // An element with the same signature and leading monomial makes this one redundant.
+            Some(m) if basis.reducer(m, &pair.signature, true).is_some() => {
+                statistics.pairs_rejected += 1;
+            }
+            Some(_) => basis.insert(pair.signature, v, &mut pairs),
+        }
+    }
+    statistics.basis_size = basis.elements.len();
+    let polynomials: Vec<MPoly<R, O>> = basis.elements.into_iter().map(|(_, v)| v).collect();
+    let reduced = reduce_basis(&polynomials);
+    statistics.elapsed = start.elapsed();
+    (reduced, statistics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ringlike::{
+        finite_field::PrimeField,
+        groebner,
+        multivariate::{GRevLex, GrLex, Lex},
+        rational::Rational,
+    };
+
+    /// A polynomial from its terms, as exponents with integer coefficients.
+    fn poly<R, O>(terms: &[(&[u32], i128)]) -> MPoly<R, O>
+    where
+        R: Field + Clone + PartialEq + From<i128>,
+        O: MonomialOrder,
+    {
+        MPoly::new(terms.iter().map(|&(e, c)| (e.to_vec(), c.into())).collect())
+    }
+
+    /// Generators which aren't a regular sequence: a repeat, a multiple and a combination of the
+    /// others, so some pairs must reduce to zero before their syzygies are known.
+    fn redundant<R, O>() -> Vec<MPoly<R, O>>
+    where
+        R: Field + Clone + PartialEq + From<i128>,
+        O: MonomialOrder,
+    {
+        let circle = poly(&[(&[2], 1), (&[0, 2], 1), (&[], -1)]);
+        let line = poly(&[(&[1], 1), (&[0, 1], -1)]);
+        vec![
+            circle.clone(),
+            line.clone(),
+            circle.clone(),
+            &line * &poly(&[(&[0, 3], 2), (&[1], 1)]),
+            &circle + &(&line * &line),
+        ]
+    }
+
+    fn records_syzygies<R, O>(generators: Vec<MPoly<R, O>>)
+    where
+        R: Field + Clone + PartialEq + std::fmt::Debug,
+        O: MonomialOrder,
+    {
+        let (basis, statistics) = groebner_basis_with_statistics(&generators);
+        assert_eq!(basis, groebner::groebner_basis(&generators));
+        assert!(statistics.reductions_to_zero > 0);
+        assert!(statistics.pairs_rejected > 0);
+    }
+
+    #[test]
+    fn redundant_generators_over_the_rationals() {
+        records_syzygies(redundant::<Rational, Lex>());
+        records_syzygies(redundant::<Rational, GrLex>());
+        records_syzygies(redundant::<Rational, GRevLex>());
+    }
+
+    #[test]
+    fn redundant_generators_over_a_prime_field() {
+        type F = PrimeField<32003>;
+        records_syzygies(redundant::<F, Lex>());
+        records_syzygies(redundant::<F, GrLex>());
+        records_syzygies(redundant::<F, GRevLex>());
+    }
+}
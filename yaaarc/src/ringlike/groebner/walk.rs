@@ -0,0 +1,260 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Changing the monomial order of a Gröbner basis with the Gröbner walk, of Collart, Kalkbrener
+//! and Mall.
+//!
+//! Unlike [FGLM](super::fglm), this works for any ideal, not just zero-dimensional ones. The
+//! weight vectors *w* ∈ ℝⁿ for which a reduced Gröbner basis *G* has the same leading terms when
+//! we compare monomials by *w* first form a cone, and these cones make up the *Gröbner fan* of the
+//! ideal. The walk follows the line from the start order's [weight](MonomialOrder::weight) to the
+//! target order's, and changes the basis each time it crosses into a new cone.
+//!
+//! At a weight *w* on a wall, we take the *initial forms* in_*w*(*g*), the sums of the terms of
+//! each *g* ∈ *G* of largest weight. They form a Gröbner basis of the initial ideal for the old
+//! order, and since they're homogeneous for *w*, [`groebner_basis`] with the target order gives
+//! its Gröbner basis for the order on the other side of the wall. Writing each new element in
+//! terms of the initial forms, and replacing those by the *g*, lifts it back to a Gröbner basis of
+//! the ideal. These initial ideals tend to be much simpler than the ideal itself.
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{
+//!     groebner::{groebner_basis, walk},
+//!     multivariate::{GRevLex, Lex, MPoly},
+//!     rational::Rational,
+//! };
+//!
+//! type P = MPoly<Rational, GRevLex>;
+//!
+//! // The twisted cubic, (t, t², t³), is a curve, so FGLM can't help.
+//! let (x, y, z) = (P::var(0), P::var(1), P::var(2));
+//! let generators = [
+//!     &(&x * &z) - &(&y * &y),
+//!     &(&y * &z) - &(&(&x * &y) * &x),
+//!     &(&z * &z) - &(&(&x * &x) * &(&x * &y)),
+//! ];
+//! let basis = groebner_basis(&generators);
+//! let lex = walk::walk::<_, _, Lex>(&basis);
+//! let reordered: Vec<MPoly<Rational, Lex>> = generators.iter().map(|f| f.reorder()).collect();
+//! assert_eq!(lex, groebner_basis(&reordered));
+//! ```
+
+use std::cmp::Ordering;
+
+use crate::ringlike::{
+    groebner::{groebner_basis, reduce_basis},
+    multivariate::{MPoly, Monomial, MonomialOrder},
+    rational::gcd_i128,
+    Field, Ring,
+};
+
+/// A monomial order which compares by each weight vector in turn, and then by `tie`.
+struct WeightOrder {
+    weights: Vec<Vec<i128>>,
+    tie: fn(&Monomial, &Monomial) -> Ordering,
+}
+
+impl WeightOrder {
+    fn compare(&self, a: &Monomial, b: &Monomial) -> Ordering {
+        self.weights
+            .iter()
+            .map(|w| dot(w, a).cmp(&dot(w, b)))
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| (self.tie)(a, b))
+    }
+
+    fn lead<'a, R, P>(&self, f: &'a MPoly<R, P>) -> Option<&'a Monomial>
+    where
+        R: Field + Clone + PartialEq,
+        P: MonomialOrder,
+    {
+        f.support().max_by(|a, b| self.compare(a, b))
+    }
+}
+
+/// *w* · α.
+fn dot(w: &[i128], m: &Monomial) -> i128 {
+    w.iter()
+        .zip(m.exponents())
+        .map(|(w, &e)| w * i128::from(e))
+        .sum()
+}
+
+/// The initial form in_*w*(*f*), the sum of the terms of *f* with the largest weight.
+fn initial_form<R, P>(f: &MPoly<R, P>, w: &[i128]) -> MPoly<R, P>
+where
+    R: Field + Clone + PartialEq,
+    P: MonomialOrder,
+{
+    let Some(top) = f.support().map(|m| dot(w, m)).max() else {
+        return MPoly::ZERO;
+    };
+    MPoly::from_terms(
+        f.terms()
+            .filter(|(m, _)| dot(w, m) == top)
+            .map(|(m, c)| (m.clone(), c.clone())),
+    )
+}
+
+/// Divides `f` by the `divisors`, each given with its leading monomial for `order`, and returns
+/// the quotients and the remainder, like [`MPoly::div_rem_list`].
+fn divide<R, P>(
+    f: &MPoly<R, P>,
+    divisors: &[(MPoly<R, P>, Monomial)],
+    order: &WeightOrder,
+) -> (Vec<MPoly<R, P>>, MPoly<R, P>)
+where
+    R: Field + Clone + PartialEq,
+    P: MonomialOrder,
+{
+    let mut quotients = vec![MPoly::ZERO; divisors.len()];
+    let mut remainder = Vec::new();
+    let mut p = f.clone();
+    while let Some(m) = order.lead(&p).cloned() {
+        let c = p.coefficient(&m);
+        let divisor = divisors
+            .iter()
+            .enumerate()
+            .find_map(|(i, (g, lead))| m.checked_div(lead).map(|t| (i, g, lead, t)));
+        match divisor {
+            Some((i, g, lead, t)) => {
+                let factor = c.div(g.coefficient(lead));
+                p = &p - &g.mul_term(&factor, &t);
+                quotients[i] = &quotients[i] + &MPoly::term(factor, t);
+            }
+            None => {
+                p = &p - &MPoly::term(c.clone(), m.clone());
+                remainder.push((m, c));
+            }
+        }
+    }
+    (quotients, MPoly::from_terms(remainder))
+}
+
+/// Turns a minimal Gröbner basis for `order`, with its leading monomials, into the reduced one.
+fn interreduce<R, P>(
+    basis: Vec<(MPoly<R, P>, Monomial)>,
+    order: &WeightOrder,
+) -> Vec<(MPoly<R, P>, Monomial)>
+where
+    R: Field + Clone + PartialEq,
+    P: MonomialOrder,
+{
+    (0..basis.len())
+        .map(|i| {
+            let others: Vec<(MPoly<R, P>, Monomial)> = basis
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, g)| g.clone())
+                .collect();
+            let (g, lead) = &basis[i];
+            let (_, r) = divide(g, &others, order);
+            let scale = r
+                .coefficient(lead)
+                .inverse_mul()
+                .expect("the leading term stays");
+            (r.scale(&scale), lead.clone())
+        })
+        .collect()
+}
+
+/// The next weight on the line from `w` to `target`, where the leading terms of `basis` first
+/// change, or `target` if they never do.
+fn next_weight<R, P>(basis: &[(MPoly<R, P>, Monomial)], w: &[i128], target: &[i128]) -> Vec<i128>
+where
+    R: Field + Clone + PartialEq,
+    P: MonomialOrder,
+{
+    // The point (1 - s) w + s target where s = a / b.
+    let mut step: Option<(i128, i128)> = None;
+    for (g, lead) in basis {
+        for m in g.support().filter(|&m| m != lead) {
+            // (1 - s) w · (α - β) + s target · (α - β) = 0 at s = u / (u - v).
+            let u = dot(w, lead) - dot(w, m);
+            let v = dot(target, lead) - dot(target, m);
+            if v < 0 && step.is_none_or(|(a, b)| u * b < a * (u - v)) {
+                step = Some((u, u - v));
+            }
+        }
+    }
+    let Some((a, b)) = step else {
+        return target.to_vec();
+    };
+    let next: Vec<i128> = w
+        .iter()
+        .zip(target)
+        .map(|(x, y)| (b - a) * x + a * y)
+        .collect();
+    let divisor = next.iter().fold(0, |d, &x| gcd_i128(d, x)).max(1);
+    next.into_iter().map(|x| x / divisor).collect()
+}
+
+/// The reduced Gröbner basis for the order `P` of the ideal with the reduced Gröbner basis
+/// `basis` for `O`, sorted by leading monomial from smallest to largest. See the
+/// [module documentation](self).
+pub fn walk<R, O, P>(basis: &[MPoly<R, O>]) -> Vec<MPoly<R, P>>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+    P: MonomialOrder,
+{
+    let variables = basis.iter().map(|g| g.num_vars()).max().unwrap_or(0);
+    let weight = |w: Vec<u32>| -> Vec<i128> { w.into_iter().map(i128::from).collect() };
+    let target = weight(P::weight(variables));
+    let mut w = weight(O::weight(variables));
+    let mut order = WeightOrder {
+        weights: Vec::new(),
+        tie: O::compare,
+    };
+    let mut current: Vec<(MPoly<R, P>, Monomial)> = basis
+        .iter()
+        .filter(|g| !g.is_zero())
+        .map(|g| (g.reorder(), g.leading_monomial().expect("nonzero").clone()))
+        .collect();
+    loop {
+        // Since w is in the closure of the current cone, the leading terms are in the initial
+        // forms.
+        let initial: Vec<(MPoly<R, P>, Monomial)> = current
+            .iter()
+            .map(|(g, lead)| (initial_form(g, &w), lead.clone()))
+            .collect();
+        let forms: Vec<MPoly<R, P>> = initial.iter().map(|(f, _)| f.clone()).collect();
+        let next_order = WeightOrder {
+            weights: vec![w.clone()],
+            tie: P::compare,
+        };
+        let lifted: Vec<(MPoly<R, P>, Monomial)> = groebner_basis(&forms)
+            .into_iter()
+            .map(|h| {
+                let (quotients, _) = divide(&h, &initial, &order);
+                let g = quotients
+                    .iter()
+                    .zip(&current)
+                    .fold(MPoly::ZERO, |sum, (q, (g, _))| &sum + &(q * g));
+                (g, h.leading_monomial().expect("nonzero").clone())
+            })
+            .collect();
+        current = interreduce(lifted, &next_order);
+        order = next_order;
+        if w == target {
+            break;
+        }
+        w = next_weight(&current, &w, &target);
+    }
+    let polynomials: Vec<MPoly<R, P>> = current.into_iter().map(|(g, _)| g).collect();
+    reduce_basis(&polynomials)
+}
@@ -0,0 +1,1064 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Ideals of multivariate polynomial rings over a field.
+//!
+//! An [`Ideal`] of *K*\[*x*₁, …, *xₙ*\] keeps its reduced [Gröbner basis](crate::ringlike::groebner)
+//! for its [`MonomialOrder`], which answers most questions about it. Since the reduced basis is
+//! unique, two ideals are equal exactly when their bases are. We have to say how many variables
+//! the ring has, because that isn't determined by the generators: (*x*) is zero-dimensional in
+//! *K*\[*x*\] but not in *K*\[*x*, *y*\].
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{
+//!     ideal::Ideal,
+//!     multivariate::{GRevLex, Lex, MPoly},
+//!     rational::Rational,
+//! };
+//!
+//! type P = MPoly<Rational, GRevLex>;
+//!
+//! let (x, y) = (P::var(0), P::var(1));
+//! let one = P::constant(1.into());
+//! // x² + y² = 1 and x = y meet at two points.
+//! let ideal = Ideal::new(2, &[&(&(&x * &x) + &(&y * &y)) - &one, &x - &y]);
+//! assert!(ideal.is_zero_dimensional());
+//! assert_eq!(ideal.quotient_dimension(), Some(2));
+//! assert!(ideal.contains(&(&(&x * &y) - &(&y * &y))));
+//! let lex = ideal.to_order::<Lex>();
+//! assert_eq!(lex.basis().len(), 2);
+//! // x = y is a whole line, so it isn't zero-dimensional.
+//! assert!(!Ideal::new(2, &[&x - &y]).is_zero_dimensional());
+//! ```
+//!
+//! Ideals of the same ring can be added and multiplied, and we can intersect them, take quotients
+//! and saturations, and compare them by inclusion.
+//!
+//! ```rust
+//! use yaaarc::ringlike::{
+//!     ideal::Ideal,
+//!     multivariate::{GRevLex, MPoly},
+//!     rational::Rational,
+//! };
+//!
+//! type P = MPoly<Rational, GRevLex>;
+//!
+//! let (x, y) = (P::var(0), P::var(1));
+//! let i = Ideal::new(2, &[x.clone()]);
+//! let j = Ideal::new(2, &[y.clone()]);
+//! // (x) ∩ (y) = (x)(y) = (xy), and (x) + (y) = (x, y).
+//! let xy = Ideal::new(2, &[&x * &y]);
+//! assert_eq!(i.intersect(&j), xy);
+//! assert_eq!(&i * &j, xy);
+//! assert_eq!(&i + &j, Ideal::new(2, &[x.clone(), y.clone()]));
+//! assert!(xy.is_contained_in(&i));
+//! assert!(!i.is_contained_in(&xy));
+//! // (x²y) : (x) = (xy), and saturating by (x) removes every power of x.
+//! let i = Ideal::new(2, &[&(&x * &x) * &y]);
+//! assert_eq!(i.quotient(&Ideal::new(2, &[x.clone()])), xy);
+//! assert_eq!(i.saturation(&Ideal::new(2, &[x.clone()])), j);
+//! assert_eq!(j.pow(2), Ideal::new(2, &[&y * &y]));
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    grouplike::{Band, CommutativeMagma, Magma, Semigroup, Semilattice},
+    latticelike::{JoinSemilattice, Lattice, MeetSemilattice, ModularLattice},
+    operators::{BinaryOperator, Join, Meet},
+    ringlike::{
+        factorisation::FactorisableField,
+        groebner::{f4, fglm, reduce_basis, walk},
+        multivariate::{Block, GRevLex, Lex, MPoly, Monomial, MonomialOrder},
+        polynomial::{multiple, Poly},
+        Field, Ring, UniqueFactorisationDomain,
+    },
+};
+
+/// An ideal of *K*\[*x*₁, …, *xₙ*\], stored as its reduced Gröbner basis. See the
+/// [module documentation](self).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ideal<R, O> {
+    variables: usize,
+    basis: Vec<MPoly<R, O>>,
+}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Ideal<R, O> {
+    /// The ideal of the polynomial ring in `variables` variables generated by `generators`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a generator involves more than `variables` variables.
+    pub fn new(variables: usize, generators: &[MPoly<R, O>]) -> Self {
+        assert!(
+            generators.iter().all(|f| f.num_vars() <= variables),
+            "a generator has more than {variables} variables"
+        );
+        Ideal {
+            variables,
+            basis: f4::groebner_basis(generators),
+        }
+    }
+
+    /// The ideal whose reduced Gröbner basis is `basis`, which we don't check.
+    pub(crate) fn from_reduced_basis(variables: usize, basis: Vec<MPoly<R, O>>) -> Self {
+        Ideal { variables, basis }
+    }
+
+    /// The number of variables of the polynomial ring.
+    pub fn variables(&self) -> usize {
+        self.variables
+    }
+
+    /// The reduced Gröbner basis, sorted by leading monomial from smallest to largest.
+    pub fn basis(&self) -> &[MPoly<R, O>] {
+        &self.basis
+    }
+
+    /// Whether this is the zero ideal.
+    pub fn is_zero(&self) -> bool {
+        self.basis.is_empty()
+    }
+
+    /// Whether this is the whole ring, i.e. whether it contains 1, so that the generators have no
+    /// common solutions.
+    pub fn is_whole_ring(&self) -> bool {
+        self.basis.iter().any(|f| f.is_constant())
+    }
+
+    /// Whether `f` is in the ideal.
+    pub fn contains(&self, f: &MPoly<R, O>) -> bool {
+        f.reduce(&self.basis).is_zero()
+    }
+
+    /// The remainder of `f` on division by the basis, which is the same for any two polynomials
+    /// congruent modulo the ideal.
+    pub fn normal_form(&self, f: &MPoly<R, O>) -> MPoly<R, O> {
+        f.reduce(&self.basis)
+    }
+
+    /// Whether the ideal is zero-dimensional, i.e. whether the quotient ring is a
+    /// finite-dimensional vector space. Equivalently, the generators have finitely many common
+    /// solutions over the algebraic closure of the field, but at least one.
+    pub fn is_zero_dimensional(&self) -> bool {
+        !self.is_whole_ring() && self.standard_monomials().is_some()
+    }
+
+    /// The monomials not divisible by any leading monomial of the basis, from smallest to largest,
+    /// or [`None`] if there are infinitely many. Their images form a basis of the quotient ring.
+    pub fn standard_monomials(&self) -> Option<Vec<Monomial>> {
+        fglm::standard_monomials(&self.basis, self.variables)
+    }
+
+    /// The dimension of the quotient ring as a vector space, if it's finite. This is the number of
+    /// solutions over the algebraic closure, counted with multiplicity.
+    pub fn quotient_dimension(&self) -> Option<usize> {
+        self.standard_monomials().map(|s| s.len())
+    }
+
+    /// The minimal polynomial of `f` modulo the ideal, i.e. the monic *p* of smallest degree with
+    /// *p*(*f*) in the ideal, or [`None`] if there isn't one because the quotient ring is
+    /// infinite-dimensional.
+    ///
+    /// We reduce the powers of `f` until their normal forms are linearly dependent.
+    pub fn minimal_polynomial(&self, f: &MPoly<R, O>) -> Option<Poly<R>> {
+        self.standard_monomials()?;
+        // Each row is a normal form in echelon form, with the polynomial in f it's the normal
+        // form of. The rows are indexed by their leading monomials.
+        let mut rows: Vec<(MPoly<R, O>, Poly<R>)> = Vec::new();
+        let mut pivots: HashMap<Monomial, usize> = HashMap::new();
+        let mut power = self.normal_form(&MPoly::ONE);
+        let mut degree = 0;
+        loop {
+            let mut remainder = power.clone();
+            let mut combination = Poly::monomial(R::ONE, degree);
+            while let Some(&row) = remainder.leading_monomial().and_then(|m| pivots.get(m)) {
+                let (r, c) = &rows[row];
+                let factor = remainder.leading_coefficient().div(r.leading_coefficient());
+                remainder = &remainder - &r.scale(&factor);
+                combination = &combination - &c.scale(&factor);
+            }
+            match remainder.leading_monomial() {
+                None => return Some(combination),
+                Some(m) => {
+                    pivots.insert(m.clone(), rows.len());
+                    rows.push((remainder, combination));
+                }
+            }
+            power = self.normal_form(&(&power * f));
+            degree += 1;
+        }
+    }
+
+    /// The elimination ideal, of the elements of the ideal which don't involve the variables in
+    /// `eliminated`. It's still an ideal of the same ring, so the other variables keep their
+    /// indices.
+    ///
+    /// We move the eliminated variables to the front, keeping the others in order, and compute a
+    /// single Gröbner basis for the block order [`Block<K, O>`](Block), where *K* is how many
+    /// there are. If the leading monomial of an element doesn't involve them, then none of its
+    /// terms do, and moving the variables back, those elements form a Gröbner basis of the
+    /// elimination ideal for `O`. This relies on `O` comparing the other variables in the same way
+    /// after the move, as [`Lex`], [`GrLex`](crate::ringlike::multivariate::GrLex) and
+    /// [`GRevLex`] do.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::{
+    ///     ideal::Ideal,
+    ///     multivariate::{GRevLex, MPoly},
+    ///     rational::Rational,
+    /// };
+    ///
+    /// type P = MPoly<Rational, GRevLex>;
+    ///
+    /// // Eliminating t from x = t², y = t³ leaves the cuspidal cubic y² = x³.
+    /// let (t, x, y) = (P::var(0), P::var(1), P::var(2));
+    /// let ideal = Ideal::new(3, &[&x - &(&t * &t), &y - &(&(&t * &t) * &t)]);
+    /// let cubic = &(&y * &y) - &(&(&x * &x) * &x);
+    /// assert_eq!(ideal.eliminate(&[0]), Ideal::new(3, &[cubic]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of `eliminated` isn't a variable of the ring.
+    pub fn eliminate(&self, eliminated: &[usize]) -> Self {
+        Self::eliminate_from(self.variables, &self.basis, eliminated)
+    }
+
+    /// The elimination ideal of the ideal generated by `generators` in `variables` variables, as
+    /// in [`Ideal::eliminate`], without computing a Gröbner basis of the ideal itself first.
+    pub(crate) fn eliminate_from(
+        variables: usize,
+        generators: &[MPoly<R, O>],
+        eliminated: &[usize],
+    ) -> Self {
+        assert!(
+            eliminated.iter().all(|&i| i < variables),
+            "an eliminated variable isn't one of the {variables} variables"
+        );
+        let (order, back, block) = block_first(variables, eliminated);
+        let kept: Vec<MPoly<R, O>> = block_basis(generators, &order, block)
+            .into_iter()
+            .filter(|(_, lead)| (0..block).all(|j| lead.exponent(j) == 0))
+            .map(|(g, _)| permute(&g, &back))
+            .collect();
+        if block <= LARGEST_BLOCK {
+            Ideal {
+                variables,
+                basis: reduce_basis(&kept),
+            }
+        } else {
+            Ideal::new(variables, &kept)
+        }
+    }
+
+    /// Whether every element of the ideal is in `other`, which we check on the basis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ideals are in different numbers of variables.
+    pub fn is_contained_in(&self, other: &Self) -> bool {
+        self.assert_same_ring(other);
+        self.basis.iter().all(|g| other.contains(g))
+    }
+
+    /// The intersection *I* ∩ *J* with `other`.
+    ///
+    /// With an extra variable *t*, it's the elimination ideal of *tI* + (1 - *t*)*J*: setting
+    /// *t* = 0 or 1 shows that anything in it without *t* is in both, and *f* = *tf* + (1 - *t*)*f*
+    /// for any *f* in both.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ideals are in different numbers of variables.
+    pub fn intersect(&self, other: &Self) -> Self {
+        self.assert_same_ring(other);
+        let t = MPoly::var(self.variables);
+        let one_minus_t = &MPoly::ONE - &t;
+        let generators: Vec<MPoly<R, O>> = self
+            .basis
+            .iter()
+            .map(|f| &t * f)
+            .chain(other.basis.iter().map(|g| &one_minus_t * g))
+            .collect();
+        Self::eliminate_last(self.variables, &generators)
+    }
+
+    /// The ideal quotient *I* : *J* with `other`, of the *f* with *fJ* ⊆ *I*.
+    ///
+    /// It's the intersection of the *I* : *g* for *g* in the basis of *J*, and *I* : *g* is
+    /// (*I* ∩ (*g*))/*g*. So *I* : (0) is the whole ring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ideals are in different numbers of variables.
+    pub fn quotient(&self, other: &Self) -> Self {
+        self.assert_same_ring(other);
+        let whole = Ideal::new(self.variables, &[MPoly::ONE]);
+        other.basis.iter().fold(whole, |quotient, g| {
+            let multiples = self.intersect(&Ideal::new(self.variables, std::slice::from_ref(g)));
+            let divided: Vec<MPoly<R, O>> = multiples
+                .basis
+                .iter()
+                .map(|f| {
+                    let (mut quotients, _) = f.div_rem_list(std::slice::from_ref(g));
+                    quotients.pop().expect("there's one divisor")
+                })
+                .collect();
+            quotient.intersect(&Ideal::new(self.variables, &divided))
+        })
+    }
+
+    /// The saturation *I* : *J*^∞ by `other`, of the *f* with *fJᵏ* ⊆ *I* for some *k*.
+    ///
+    /// It's the intersection of the *I* : *g*^∞ for *g* in the basis of *J*, and *I* : *g*^∞ is the
+    /// elimination ideal of *I* + (1 - *zg*) with an extra variable *z*, which stands for 1/*g*.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ideals are in different numbers of variables.
+    pub fn saturation(&self, other: &Self) -> Self {
+        self.assert_same_ring(other);
+        let z = MPoly::var(self.variables);
+        let whole = Ideal::new(self.variables, &[MPoly::ONE]);
+        other.basis.iter().fold(whole, |saturation, g| {
+            let mut generators = self.basis.clone();
+            generators.push(&MPoly::ONE - &(&z * g));
+            saturation.intersect(&Self::eliminate_last(self.variables, &generators))
+        })
+    }
+
+    /// The power *Iᵏ*, where the zeroth power is the whole ring.
+    pub fn pow(&self, exponent: u32) -> Self {
+        let whole = Ideal::new(self.variables, &[MPoly::ONE]);
+        (0..exponent).fold(whole, |power, _| &power * self)
+    }
+
+    /// Whether `f` is in the radical of the ideal, i.e. whether some power of `f` is in the ideal.
+    /// By Hilbert's Nullstellensatz, this is when `f` vanishes at every common solution of the
+    /// generators over the algebraic closure.
+    ///
+    /// We use the Rabinowitsch trick: with an extra variable *z*, *fᵏ* is in *I* for some *k*
+    /// exactly when *I* + (1 - *zf*) is the whole ring, because *z* stands for 1/*f* there.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::{
+    ///     ideal::Ideal,
+    ///     multivariate::{GRevLex, MPoly},
+    ///     rational::Rational,
+    /// };
+    ///
+    /// type P = MPoly<Rational, GRevLex>;
+    ///
+    /// // x² = y = 0 only at the origin, so x vanishes at every solution, but x + 1 doesn't.
+    /// let (x, y) = (P::var(0), P::var(1));
+    /// let ideal = Ideal::new(2, &[&x * &x, y.clone()]);
+    /// assert!(!ideal.contains(&x));
+    /// assert!(ideal.radical_contains(&x));
+    /// assert!(!ideal.radical_contains(&(&x + &P::constant(1.into()))));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` involves more than [`Ideal::variables`] variables.
+    pub fn radical_contains(&self, f: &MPoly<R, O>) -> bool {
+        assert!(
+            f.num_vars() <= self.variables,
+            "the polynomial has more than {} variables",
+            self.variables
+        );
+        let z = MPoly::var(self.variables);
+        let mut generators = self.basis.clone();
+        generators.push(&MPoly::ONE - &(&z * f));
+        Ideal::new(self.variables + 1, &generators).is_whole_ring()
+    }
+
+    /// The elimination ideal of the ideal generated by `generators` in `variables` + 1 variables,
+    /// without the last one, as an ideal in `variables` variables.
+    fn eliminate_last(variables: usize, generators: &[MPoly<R, O>]) -> Self {
+        let elimination = Self::eliminate_from(variables + 1, generators, &[variables]);
+        // The reduced basis doesn't involve the last variable, so it's still reduced without it.
+        Ideal {
+            variables,
+            basis: elimination.basis,
+        }
+    }
+
+    fn assert_same_ring(&self, other: &Self) {
+        assert_eq!(
+            self.variables, other.variables,
+            "the ideals are in rings with different numbers of variables"
+        );
+    }
+
+    /// The same ideal, with a Gröbner basis for the order `P` instead.
+    ///
+    /// Zero-dimensional ideals are converted with [`fglm::fglm`], and others with
+    /// [`walk::walk`].
+    pub fn to_order<P: MonomialOrder>(&self) -> Ideal<R, P> {
+        let basis = match fglm::fglm(&self.basis, self.variables) {
+            Some(basis) => basis,
+            None => walk::walk(&self.basis),
+        };
+        Ideal {
+            variables: self.variables,
+            basis,
+        }
+    }
+}
+
+impl<R: FactorisableField, O: MonomialOrder> Ideal<R, O> {
+    /// The radical √*I*, of the polynomials with a power in the ideal, i.e. those vanishing at
+    /// every common solution of the generators over the algebraic closure.
+    ///
+    /// A zero-dimensional ideal which contains a square-free polynomial in each variable is
+    /// radical, by Seidenberg's lemma, so there we add the square-free parts of the minimal
+    /// polynomials of the variables. This works because *K* is perfect.
+    ///
+    /// Otherwise, following Gianni, Trager and Zacharias, we take a set *U* of as many variables as
+    /// the dimension, such that the ideal has no nonzero polynomials in *U* alone. Over the field
+    /// of fractions *K*(*U*), it becomes zero-dimensional, with a Gröbner basis for a block order
+    /// with the other variables larger. If *h* is the lcm of the square-free parts of its leading
+    /// coefficients, which are polynomials in *U*, then √*I* = √(*I* : *h*^∞) ∩ √(*I* + (*h*)). The first is the
+    /// radical over *K*(*U*) intersected with *K*\[*x*₁, …, *xₙ*\], which we find as in the
+    /// zero-dimensional case, and the second is a larger ideal, so we can recurse.
+    ///
+    /// Over *K*(*U*), the square-free parts have to be separable, which in characteristic *p* can
+    /// fail: *xᵖ* - *u* is irreducible over 𝔽*_p*(*u*), but not over its algebraic closure. Then we
+    /// try another choice of *U*.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::{
+    ///     finite_field::PrimeField,
+    ///     ideal::Ideal,
+    ///     multivariate::{GRevLex, MPoly},
+    ///     rational::Rational,
+    /// };
+    ///
+    /// type P = MPoly<Rational, GRevLex>;
+    ///
+    /// // (x - y)²(x + y) = 0 is the two lines x = ±y, the first one doubled.
+    /// let (x, y) = (P::var(0), P::var(1));
+    /// let ideal = Ideal::new(2, &[&(&(&x - &y) * &(&x - &y)) * &(&x + &y)]);
+    /// assert_eq!(ideal.radical(), Ideal::new(2, &[&(&x - &y) * &(&x + &y)]));
+    ///
+    /// // Over 𝔽₃, x³ = y³ = u says that x = y is a cube root of u.
+    /// type Q = MPoly<PrimeField<3>, GRevLex>;
+    /// let (x, y, u) = (Q::var(0), Q::var(1), Q::var(2));
+    /// let ideal = Ideal::new(3, &[&x.pow(3) - &u, &y.pow(3) - &u]);
+    /// assert!(!ideal.contains(&(&x - &y)));
+    /// assert_eq!(ideal.radical(), Ideal::new(3, &[&x - &y, &x.pow(3) - &u]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if every choice of *U* needs an inseparable extension of *K*(*U*). This can only
+    /// happen over a finite field.
+    pub fn radical(&self) -> Self {
+        if self.is_zero() || self.is_whole_ring() {
+            return self.clone();
+        }
+        if self.is_zero_dimensional() {
+            return self.zero_dimensional_radical();
+        }
+        let (away, h) = self
+            .independent_sets()
+            .into_iter()
+            .find_map(|independent| self.radical_away_from(&independent))
+            .expect("some set of independent variables gives a separable extension");
+        if h.is_constant() {
+            return away;
+        }
+        let rest = (self + &Ideal::new(self.variables, &[h])).radical();
+        away.intersect(&rest)
+    }
+
+    /// The radical of a zero-dimensional ideal, with the square-free part of the minimal polynomial
+    /// of each variable added.
+    pub(crate) fn zero_dimensional_radical(&self) -> Self {
+        let mut generators = self.basis.clone();
+        for i in 0..self.variables {
+            let m = self
+                .minimal_polynomial(&MPoly::var(i))
+                .expect("the ideal is zero-dimensional");
+            let part = m.squarefree_part();
+            if part.degree() != m.degree() {
+                generators.push(in_variable(&part, i));
+            }
+        }
+        if generators.len() == self.basis.len() {
+            self.clone()
+        } else {
+            Ideal::new(self.variables, &generators)
+        }
+    }
+
+    /// The sets of as many variables as the dimension, such that the ideal has no nonzero
+    /// polynomials in them alone. Those where no leading monomial only involves them come first,
+    /// and they're the ones which tell us the dimension.
+    ///
+    /// Both properties pass to subsets, so we find the sets with [`maximal_sets`], which never
+    /// extends a set without them. The others need an elimination each, so we only look for them
+    /// if the caller runs out of the first ones.
+    fn independent_sets(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        let n = self.variables;
+        let leads: Vec<&Monomial> = self
+            .basis
+            .iter()
+            .filter_map(|g| g.leading_monomial())
+            .collect();
+        let avoids_leads = |set: &[usize]| {
+            leads.iter().all(|m| {
+                m.exponents()
+                    .iter()
+                    .enumerate()
+                    .any(|(i, &e)| e > 0 && !set.contains(&i))
+            })
+        };
+        let mut first = maximal_sets(n, &avoids_leads);
+        let dimension = first.iter().map(Vec::len).max().unwrap_or(0);
+        first.retain(|set| set.len() == dimension);
+        let independent = move |set: &[usize]| {
+            let others: Vec<usize> = (0..n).filter(|i| !set.contains(i)).collect();
+            self.eliminate(&others).is_zero()
+        };
+        let rest = std::iter::once(first.clone()).flat_map(move |first| {
+            maximal_sets(n, &independent)
+                .into_iter()
+                .filter(move |set| set.len() == dimension && !first.contains(set))
+        });
+        first.into_iter().chain(rest)
+    }
+
+    /// √(*I* : *h*^∞) and *h*, as in [`Ideal::radical`], for the set of variables `independent`,
+    /// or [`None`] if the extension isn't separable.
+    fn radical_away_from(&self, independent: &[usize]) -> Option<(Self, MPoly<R, O>)> {
+        let n = self.variables;
+        let others: Vec<usize> = (0..n).filter(|i| !independent.contains(i)).collect();
+        let h = self.leading_coefficients(&others);
+        let mut generators = self.basis.clone();
+        for &i in &others {
+            let eliminated: Vec<usize> = others.iter().copied().filter(|&j| j != i).collect();
+            // The generator of the ideal over K(U) in x_i alone has the smallest degree in x_i,
+            // and a Gröbner basis for the degree in x_i first contains a multiple of it.
+            let (order, back, _) = block_first(n, &[i]);
+            let g = block_basis(self.eliminate(&eliminated).basis(), &order, 1)
+                .into_iter()
+                .map(|(g, _)| g)
+                .filter(|g| g.degree_in(0) > 0)
+                .min_by_key(|g| g.degree_in(0))
+                .expect("the ideal is zero-dimensional over K(U)");
+            generators.push(separable_part(&permute(&g, &back), i)?);
+        }
+        let extension = Ideal::new(n, &generators);
+        let away = extension.leading_coefficients(&others);
+        Some((extension.saturation(&Ideal::new(n, &[away])), h))
+    }
+
+    /// The lcm of the square-free parts of the leading coefficients, as polynomials in the
+    /// variables not in `block`, of a Gröbner basis for a block order which compares the variables
+    /// in `block` first. We use [`Block`], with [`GRevLex`] for the other variables too.
+    ///
+    /// Any product of the leading coefficients would do for [`Ideal::radical`] as long as it has
+    /// the same radical, and this is the smallest, which keeps the ideal *I* + (*h*) we recurse on
+    /// simple.
+    fn leading_coefficients(&self, block: &[usize]) -> MPoly<R, O> {
+        let (order, back, k) = block_first(self.variables, block);
+        let head = |m: &Monomial| -> Vec<u32> { (0..k).map(|j| m.exponent(j)).collect() };
+        let front: Vec<usize> = (0..k).collect();
+        let generators: Vec<MPoly<R, GRevLex>> = self.basis.iter().map(MPoly::reorder).collect();
+        let mut distinct: Vec<MPoly<R, O>> = Vec::new();
+        for (g, lead) in block_basis(&generators, &order, k) {
+            let top = head(&lead);
+            let coefficient: MPoly<R, GRevLex> = MPoly::from_terms(
+                g.terms()
+                    .filter(|(m, _)| head(m) == top)
+                    .map(|(m, c)| (without(m, &front), c.clone())),
+            );
+            let coefficient = permute(&coefficient, &back).monic();
+            if !distinct.contains(&coefficient) {
+                distinct.push(coefficient);
+            }
+        }
+        distinct.iter().fold(MPoly::ONE, |h, c| {
+            let c = squarefree_part(c);
+            exact_div(&(&h * &c), &gcd(&h, &c))
+        })
+    }
+}
+
+/// The sets of variables out of `variables` on which `keep` holds and which can't be extended
+/// by a later variable, which include all the maximal ones. `keep` must hold on every subset of a
+/// set where it holds, so we search depth first and never extend a set where it fails.
+fn maximal_sets(variables: usize, keep: &impl Fn(&[usize]) -> bool) -> Vec<Vec<usize>> {
+    fn extend(
+        set: &mut Vec<usize>,
+        variables: usize,
+        keep: &impl Fn(&[usize]) -> bool,
+        found: &mut Vec<Vec<usize>>,
+    ) {
+        let start = set.last().map_or(0, |&i| i + 1);
+        let mut extended = false;
+        for i in start..variables {
+            set.push(i);
+            if keep(set) {
+                extended = true;
+                extend(set, variables, keep, found);
+            }
+            set.pop();
+        }
+        if !extended {
+            found.push(set.clone());
+        }
+    }
+    let mut found = Vec::new();
+    if keep(&[]) {
+        extend(&mut Vec::new(), variables, keep, &mut found);
+    }
+    found
+}
+
+/// The polynomial *f*(*xᵢ*).
+fn in_variable<R, O>(f: &Poly<R>, i: usize) -> MPoly<R, O>
+where
+    R: FactorisableField,
+    O: MonomialOrder,
+{
+    MPoly::from_terms(f.coefficients().into_iter().enumerate().map(|(k, c)| {
+        let mut exponents = vec![0; i + 1];
+        exponents[i] = u32::try_from(k).expect("the degree fits in a u32");
+        (Monomial::new(exponents), c)
+    }))
+}
+
+/// `f` with its variables renumbered, so that *x*\_`order[k]` becomes *xₖ*.
+fn permute<R, O, P>(f: &MPoly<R, O>, order: &[usize]) -> MPoly<R, P>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+    P: MonomialOrder,
+{
+    MPoly::from_terms(f.terms().map(|(m, c)| {
+        let exponents = order.iter().map(|&i| m.exponent(i)).collect();
+        (Monomial::new(exponents), c.clone())
+    }))
+}
+
+/// The largest block of variables we have a [`Block`] order for. For bigger blocks we use
+/// [`Lex`], which compares every block at the front first, but is much slower.
+const LARGEST_BLOCK: usize = 8;
+
+/// The renumbering of `variables` variables for [`permute`] which moves those in `block` to the
+/// front, keeping the others in order after them, with its inverse and the size of the block.
+fn block_first(variables: usize, block: &[usize]) -> (Vec<usize>, Vec<usize>, usize) {
+    let (mut order, rest): (Vec<usize>, Vec<usize>) =
+        (0..variables).partition(|i| block.contains(i));
+    let size = order.len();
+    order.extend(rest);
+    let mut inverse = vec![0; variables];
+    for (k, &i) in order.iter().enumerate() {
+        inverse[i] = k;
+    }
+    (order, inverse, size)
+}
+
+/// The reduced Gröbner basis of the ideal generated by `generators`, renumbered by `order`, for
+/// [`Block<K, O>`](Block) with *K* = `block`, or for [`Lex`] if `block` is bigger than
+/// [`LARGEST_BLOCK`]. Each element comes with its leading monomial for that order.
+fn block_basis<R, O>(
+    generators: &[MPoly<R, O>],
+    order: &[usize],
+    block: usize,
+) -> Vec<(MPoly<R, O>, Monomial)>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    fn basis<R, O, P>(generators: &[MPoly<R, O>], order: &[usize]) -> Vec<(MPoly<R, O>, Monomial)>
+    where
+        R: Field + Clone + PartialEq,
+        O: MonomialOrder,
+        P: MonomialOrder,
+    {
+        let renumbered: Vec<MPoly<R, P>> = generators.iter().map(|g| permute(g, order)).collect();
+        f4::groebner_basis(&renumbered)
+            .into_iter()
+            .map(|g| {
+                let lead = g.leading_monomial().expect("nonzero").clone();
+                (g.reorder(), lead)
+            })
+            .collect()
+    }
+    macro_rules! by_size {
+        ($($k:literal)*) => {
+            match block {
+                $($k => basis::<R, O, Block<$k, O>>(generators, order),)*
+                _ => basis::<R, O, Lex>(generators, order),
+            }
+        };
+    }
+    by_size!(0 1 2 3 4 5 6 7 8)
+}
+
+/// `m` with the exponents of the `variables` set to zero.
+fn without(m: &Monomial, variables: &[usize]) -> Monomial {
+    let mut exponents = m.exponents().to_vec();
+    for &i in variables {
+        if let Some(e) = exponents.get_mut(i) {
+            *e = 0;
+        }
+    }
+    Monomial::new(exponents)
+}
+
+/// `a`/`b`, when `b` divides `a`.
+fn exact_div<R, O>(a: &MPoly<R, O>, b: &MPoly<R, O>) -> MPoly<R, O>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    let (mut quotients, _) = a.div_rem_list(std::slice::from_ref(b));
+    quotients.pop().expect("there's one divisor")
+}
+
+/// `f` as a polynomial in *xᵢ*, with coefficients in the other variables.
+fn in_terms_of<R, O>(f: &MPoly<R, O>, i: usize) -> Poly<MPoly<R, O>>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    let mut coefficients = vec![Vec::new(); f.degree_in(i) as usize + 1];
+    for (m, c) in f.terms() {
+        coefficients[m.exponent(i) as usize].push((without(m, &[i]), c.clone()));
+    }
+    Poly::new(coefficients.into_iter().map(MPoly::from_terms).collect())
+}
+
+/// The inverse of [`in_terms_of`].
+fn from_terms_of<R, O>(f: &Poly<MPoly<R, O>>, i: usize) -> MPoly<R, O>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    f.coefficients()
+        .iter()
+        .enumerate()
+        .fold(MPoly::ZERO, |sum, (k, c)| {
+            let mut exponents = vec![0; i + 1];
+            exponents[i] = u32::try_from(k).expect("the degree fits in a u32");
+            &sum + &c.mul_term(&R::ONE, &Monomial::new(exponents))
+        })
+}
+
+/// The gcd of the coefficients of `f`, and `f` divided by it. The gcd is only defined up to a
+/// constant, so we choose the one which makes the leading coefficient of the leading coefficient
+/// of the primitive part 1. Otherwise, when the coefficients are constants, the content would be
+/// 1 and nothing would stop them growing.
+fn content_and_primitive_part<R, O>(f: &Poly<MPoly<R, O>>) -> (MPoly<R, O>, Poly<MPoly<R, O>>)
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    if f.is_zero() {
+        return (MPoly::ZERO, Poly::ZERO);
+    }
+    let content = f
+        .coefficients()
+        .iter()
+        .fold(MPoly::ZERO, |content, c| gcd(&content, c));
+    let unit = f
+        .leading_coefficient()
+        .leading_coefficient()
+        .div(content.leading_coefficient());
+    let content = content.scale(&unit);
+    let primitive = Poly::new(
+        f.coefficients()
+            .iter()
+            .map(|c| exact_div(c, &content))
+            .collect(),
+    );
+    (content, primitive)
+}
+
+/// The monic gcd of `a` and `b`.
+///
+/// We write them as polynomials in their last variable *xᵢ* over *K*\[*x*₁, …, *xᵢ*₋₁\], which is
+/// a unique factorisation domain. The gcd is the gcd of their contents, which have fewer
+/// variables, times that of their primitive parts, which we find by the primitive remainder
+/// sequence: by Gauss's lemma, we can replace the remainders of Euclid's algorithm over the field
+/// of fractions by the primitive parts of pseudo-remainders.
+fn gcd<R, O>(a: &MPoly<R, O>, b: &MPoly<R, O>) -> MPoly<R, O>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    if a.is_zero() || b.is_zero() {
+        return (a + b).monic();
+    }
+    let last = |f: &MPoly<R, O>| (0..f.num_vars()).rev().find(|&i| f.degree_in(i) > 0);
+    let Some(i) = last(a).max(last(b)) else {
+        return MPoly::ONE;
+    };
+    let (a_content, mut f) = content_and_primitive_part(&in_terms_of(a, i));
+    let (b_content, mut g) = content_and_primitive_part(&in_terms_of(b, i));
+    if f.degree() < g.degree() {
+        std::mem::swap(&mut f, &mut g);
+    }
+    while !g.is_zero() {
+        let (_, remainder) = f.pseudo_div_rem(&g);
+        f = g;
+        g = content_and_primitive_part(&remainder).1;
+    }
+    (&gcd(&a_content, &b_content) * &from_terms_of(&f, i)).monic()
+}
+
+/// `g` divided by the gcd of its coefficients as a polynomial in *xᵢ*.
+fn primitive_part<R, O>(g: &MPoly<R, O>, i: usize) -> MPoly<R, O>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    from_terms_of(&content_and_primitive_part(&in_terms_of(g, i)).1, i)
+}
+
+/// The product of the distinct irreducible factors of `f`, which isn't zero, made monic.
+///
+/// Factors whose multiplicity isn't divisible by the characteristic *p* are those of *f*/*g*,
+/// where *g* is the gcd of *f* and its partial derivatives. Taking them out of *g* leaves a *p*-th
+/// power, since *K* is perfect, and we recurse on its *p*-th root.
+fn squarefree_part<R, O>(f: &MPoly<R, O>) -> MPoly<R, O>
+where
+    R: FactorisableField,
+    O: MonomialOrder,
+{
+    let common = (0..f.num_vars()).fold(f.clone(), |g, i| gcd(&g, &f.derivative(i)));
+    let part = exact_div(f, &common);
+    let mut rest = common;
+    loop {
+        let shared = gcd(&rest, &part);
+        if shared.is_constant() {
+            break;
+        }
+        rest = exact_div(&rest, &shared);
+    }
+    if rest.is_constant() {
+        return part.monic();
+    }
+    let root = pth_root(&rest.monic()).expect("K is perfect");
+    (&part * &squarefree_part(&root)).monic()
+}
+
+/// The product of the distinct irreducible factors of `g`, as a polynomial in *xᵢ* over the field
+/// of fractions of the other variables, or [`None`] if one of them is inseparable.
+///
+/// Factors whose multiplicity isn't divisible by the characteristic are those of *g*/gcd(*g*, *g*′).
+/// What's left of gcd(*g*, *g*′) after taking them out is a *p*-th power, unless it has an
+/// inseparable factor, and we recurse on its *p*-th root.
+fn separable_part<R, O>(g: &MPoly<R, O>, i: usize) -> Option<MPoly<R, O>>
+where
+    R: FactorisableField,
+    O: MonomialOrder,
+{
+    let g = primitive_part(g, i);
+    let derivative = g.derivative(i);
+    if derivative.is_zero() {
+        return separable_part(&pth_root(&g)?, i);
+    }
+    let common = gcd(&g, &derivative);
+    let part = exact_div(&g, &common);
+    let mut rest = primitive_part(&common, i);
+    loop {
+        let shared = gcd(&rest, &part);
+        if shared.degree_in(i) == 0 {
+            break;
+        }
+        rest = exact_div(&rest, &shared);
+    }
+    if rest.degree_in(i) == 0 {
+        return Some(part);
+    }
+    let root = pth_root(&primitive_part(&rest, i))?;
+    Some(&part * &separable_part(&root, i)?)
+}
+
+/// The *p*-th root of a nonconstant polynomial, where *p* is the characteristic, or [`None`] if
+/// it isn't a *p*-th power.
+fn pth_root<R, O>(g: &MPoly<R, O>) -> Option<MPoly<R, O>>
+where
+    R: FactorisableField,
+    O: MonomialOrder,
+{
+    let p = (2..=g.total_degree().unwrap_or(0))
+        .find(|&k| multiple(&R::ONE, k as usize) == R::ZERO)
+        .expect("only nonconstant p-th powers have p-th roots");
+    let terms: Option<Vec<(Monomial, R)>> = g
+        .terms()
+        .map(|(m, c)| {
+            m.exponents().iter().all(|e| e % p == 0).then(|| {
+                let exponents = m.exponents().iter().map(|e| e / p).collect();
+                (Monomial::new(exponents), R::pth_root(c))
+            })
+        })
+        .collect();
+    terms.map(MPoly::from_terms)
+}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> std::ops::Add<&Ideal<R, O>> for &Ideal<R, O> {
+    type Output = Ideal<R, O>;
+
+    /// The sum *I* + *J*, generated by the generators of both.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ideals are in different numbers of variables.
+    fn add(self, rhs: &Ideal<R, O>) -> Self::Output {
+        self.assert_same_ring(rhs);
+        let generators: Vec<MPoly<R, O>> = self.basis.iter().chain(&rhs.basis).cloned().collect();
+        Ideal::new(self.variables, &generators)
+    }
+}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> std::ops::Add for Ideal<R, O> {
+    type Output = Ideal<R, O>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> std::ops::Mul<&Ideal<R, O>> for &Ideal<R, O> {
+    type Output = Ideal<R, O>;
+
+    /// The product *IJ*, generated by the products of the generators.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ideals are in different numbers of variables.
+    fn mul(self, rhs: &Ideal<R, O>) -> Self::Output {
+        self.assert_same_ring(rhs);
+        let generators: Vec<MPoly<R, O>> = self
+            .basis
+            .iter()
+            .flat_map(|f| rhs.basis.iter().map(move |g| f * g))
+            .collect();
+        Ideal::new(self.variables, &generators)
+    }
+}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> std::ops::Mul for Ideal<R, O> {
+    type Output = Ideal<R, O>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+/// Ideals of the same ring form a lattice under inclusion, with the sum as join and the
+/// intersection as meet.
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> BinaryOperator<Join> for Ideal<R, O> {
+    fn op(&self, rhs: Self) -> Self {
+        self + &rhs
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = &*self + &rhs;
+    }
+}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> BinaryOperator<Meet> for Ideal<R, O> {
+    fn op(&self, rhs: Self) -> Self {
+        self.intersect(&rhs)
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.intersect(&rhs);
+    }
+}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Magma<Join> for Ideal<R, O> {}
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Semigroup<Join> for Ideal<R, O> {}
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Band<Join> for Ideal<R, O> {}
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> CommutativeMagma<Join> for Ideal<R, O> {}
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Semilattice<Join> for Ideal<R, O> {}
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> JoinSemilattice for Ideal<R, O> {}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Magma<Meet> for Ideal<R, O> {}
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Semigroup<Meet> for Ideal<R, O> {}
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Band<Meet> for Ideal<R, O> {}
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> CommutativeMagma<Meet> for Ideal<R, O> {}
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Semilattice<Meet> for Ideal<R, O> {}
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> MeetSemilattice for Ideal<R, O> {}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> Lattice for Ideal<R, O> {}
+
+/// Like the submodules of any module, ideals satisfy the modular law.
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> ModularLattice for Ideal<R, O> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ringlike::finite_field::PrimeField;
+
+    type P = MPoly<PrimeField<32003>, GRevLex>;
+
+    /// A polynomial from its terms, as exponents with integer coefficients.
+    fn poly(terms: &[(&[u32], i128)]) -> P {
+        MPoly::new(
+            terms
+                .iter()
+                .map(|&(e, c)| (e.to_vec(), PrimeField::new(c)))
+                .collect(),
+        )
+    }
+
+    /// Checks that `radical` is the radical of `ideal`: it contains the ideal, a power of each of
+    /// its elements is in the ideal, and it's its own radical.
+    fn assert_radical(
+        ideal: &Ideal<PrimeField<32003>, GRevLex>,
+        radical: &Ideal<PrimeField<32003>, GRevLex>,
+    ) {
+        assert!(ideal.is_contained_in(radical));
+        assert!(radical.basis().iter().all(|g| ideal.radical_contains(g)));
+        assert_eq!(&radical.radical(), radical);
+    }
+
+    #[test]
+    fn radical_of_a_union_of_planes() {
+        // x²y = xy³z = 0 is the union of the planes x = 0 and y = 0.
+        let ideal = Ideal::new(3, &[poly(&[(&[2, 1], 1)]), poly(&[(&[1, 3, 1], 1)])]);
+        let radical = ideal.radical();
+        assert_eq!(radical, Ideal::new(3, &[poly(&[(&[1, 1], 1)])]));
+        assert_radical(&ideal, &radical);
+    }
+
+    #[test]
+    fn radical_of_a_curve_over_a_prime_field() {
+        let ideal = Ideal::new(
+            3,
+            &[
+                poly(&[(&[0, 1, 1], -2), (&[1, 0, 2], 2), (&[2, 2, 2], -2)]),
+                poly(&[(&[2, 1], 3), (&[1, 2, 2], -1), (&[2, 2, 1], -3)]),
+            ],
+        );
+        let radical = ideal.radical();
+        assert_ne!(radical, ideal);
+        assert_radical(&ideal, &radical);
+    }
+}
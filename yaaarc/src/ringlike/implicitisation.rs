@@ -0,0 +1,160 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Implicit equations of parametrised curves and surfaces, by elimination.
+//!
+//! The image of a polynomial map *φ* = (*f*₁, …, *fₘ*) : *Kⁿ* → *Kᵐ* satisfies *yᵢ* = *fᵢ*(*t*)
+//! for some *t*, so its Zariski closure is cut out by the elimination ideal
+//!
+//! (*y*₁ - *f*₁, …, *yₘ* - *fₘ*) ∩ *K*\[*y*₁, …, *yₘ*\],
+//!
+//! which [`image_of_map`] computes with [`Ideal::eliminate`]. This is the smallest variety
+//! containing the image, though the image itself needn't be a variety. For a rational
+//! parametrisation *yᵢ* = *pᵢ*/*qᵢ*, [`implicitise`] uses *qᵢyᵢ* - *pᵢ* instead, and an extra
+//! variable *z* with 1 - *zq*₁ ⋯ *qₘ* to leave out the parameters where a denominator vanishes.
+//!
+//! In both, the parameters come first, as *x*₁, …, *xₙ*, and the result is an ideal of
+//! *K*\[*y*₁, …, *yₘ*\], with *yᵢ* as *xᵢ*.
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{
+//!     ideal::Ideal,
+//!     implicitisation::{image_of_map, implicitise},
+//!     multivariate::{GRevLex, MPoly},
+//!     rational::Rational,
+//! };
+//!
+//! type P = MPoly<Rational, GRevLex>;
+//!
+//! let t = P::var(0);
+//! let one = P::constant(1.into());
+//! let two = P::constant(2.into());
+//! // The unit circle is ((1 - t²)/(1 + t²), 2t/(1 + t²)).
+//! let denominator = &one + &(&t * &t);
+//! let circle = implicitise(
+//!     1,
+//!     &[(&one - &(&t * &t), denominator.clone()), (&two * &t, denominator)],
+//! );
+//! let (x, y) = (P::var(0), P::var(1));
+//! assert_eq!(circle, Ideal::new(2, &[&(&(&x * &x) + &(&y * &y)) - &one]));
+//! // The twisted cubic is the image of t ↦ (t, t², t³).
+//! let cubic = image_of_map(1, &[t.clone(), &t * &t, &(&t * &t) * &t]);
+//! assert_eq!(cubic.basis().len(), 3);
+//! assert!(cubic.contains(&(&(&x * &x) - &y)));
+//! ```
+
+use crate::ringlike::{
+    ideal::Ideal,
+    multivariate::{MPoly, Monomial, MonomialOrder},
+    Field,
+};
+
+/// A rational function *p*/*q*, as its numerator and denominator.
+pub type Fraction<R, O> = (MPoly<R, O>, MPoly<R, O>);
+
+/// `f` with each variable *xᵢ* replaced by *xᵢ*₋ₖ, where `f` doesn't involve the first *k*.
+fn shift_down<R, O>(f: &MPoly<R, O>, k: usize) -> MPoly<R, O>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    MPoly::from_terms(f.terms().map(|(m, c)| {
+        let exponents = m.exponents().get(k..).unwrap_or_default();
+        (Monomial::new(exponents.to_vec()), c.clone())
+    }))
+}
+
+/// Eliminates the first `k` of `variables` variables from the ideal generated by `generators`,
+/// and shifts the rest down.
+fn eliminate_first<R, O>(variables: usize, k: usize, generators: &[MPoly<R, O>]) -> Ideal<R, O>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    let eliminated: Vec<usize> = (0..k).collect();
+    let elimination = Ideal::eliminate_from(variables, generators, &eliminated);
+    let shifted: Vec<MPoly<R, O>> = elimination
+        .basis()
+        .iter()
+        .map(|g| shift_down(g, k))
+        .collect();
+    // The reduced basis doesn't involve the first k variables, so it's still reduced without them.
+    Ideal::from_reduced_basis(variables - k, shifted)
+}
+
+/// The ideal of the closure of the image of the map from *K*ⁿ, where *n* is `parameters`, with
+/// the components in `map`. See the [module documentation](self).
+///
+/// # Panics
+///
+/// Panics if a component involves more than `parameters` variables.
+pub fn image_of_map<R, O>(parameters: usize, map: &[MPoly<R, O>]) -> Ideal<R, O>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    assert!(
+        map.iter().all(|f| f.num_vars() <= parameters),
+        "a component has more than {parameters} variables"
+    );
+    let generators: Vec<MPoly<R, O>> = map
+        .iter()
+        .enumerate()
+        .map(|(i, f)| &MPoly::var(parameters + i) - f)
+        .collect();
+    eliminate_first(parameters + map.len(), parameters, &generators)
+}
+
+/// The ideal of the closure of the image of the rational map from *K*ⁿ, where *n* is
+/// `parameters`, with the components in `parametrisation` given as numerators and denominators.
+/// See the [module documentation](self).
+///
+/// # Panics
+///
+/// Panics if a denominator is zero, or a numerator or denominator involves more than `parameters`
+/// variables.
+pub fn implicitise<R, O>(parameters: usize, parametrisation: &[Fraction<R, O>]) -> Ideal<R, O>
+where
+    R: Field + Clone + PartialEq,
+    O: MonomialOrder,
+{
+    assert!(
+        parametrisation.iter().all(|(_, q)| !q.is_zero()),
+        "a denominator is zero"
+    );
+    assert!(
+        parametrisation
+            .iter()
+            .all(|(p, q)| p.num_vars() <= parameters && q.num_vars() <= parameters),
+        "a component has more than {parameters} variables"
+    );
+    // The parameters, then z, then the coordinates.
+    let z = MPoly::var(parameters);
+    let mut generators: Vec<MPoly<R, O>> = parametrisation
+        .iter()
+        .enumerate()
+        .map(|(i, (p, q))| &(q * &MPoly::var(parameters + 1 + i)) - p)
+        .collect();
+    let denominators = parametrisation
+        .iter()
+        .fold(z, |product, (_, q)| &product * q);
+    generators.push(&MPoly::constant(R::ONE) - &denominators);
+    eliminate_first(
+        parameters + 1 + parametrisation.len(),
+        parameters + 1,
+        &generators,
+    )
+}
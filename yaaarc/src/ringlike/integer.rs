@@ -0,0 +1,285 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! The ring of integers, ℤ, using Rust's signed integer types.
+//!
+//! The types [`i8`], [`i16`], [`i32`], [`i64`], [`i128`] and [`isize`] are all Euclidean domains
+//! with the usual arithmetic. Of course, they're really ℤ/2^*n*ℤ, so like [`super::rational`],
+//! every operation panics rather than silently wrapping around if it overflows.
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{EuclideanDomain, GCDDomain, IntegralDomain, Ring};
+//!
+//! assert_eq!(12_i64.gcd(-18), 6);
+//! assert_eq!((-7_i32).valuation(), 7);
+//! assert!(3_i128.associates(-3));
+//! assert_eq!((-1_i8).inverse_mul(), Some(-1));
+//! assert_eq!(2_i16.inverse_mul(), None);
+//! ```
+//!
+//! They're unique factorisation domains too, with the primes as the irreducible elements.
+//!
+//! ```rust
+//! use yaaarc::ringlike::UniqueFactorisationDomain;
+//!
+//! let factors = 1_000_000_016_000_000_063_i128.factor();
+//! assert_eq!(factors.factors(), [(1_000_000_007, 1), (1_000_000_009, 1)]);
+//! assert_eq!(factors.product(), 1_000_000_016_000_000_063);
+//! assert!((-2_147_483_647_i32).is_prime());
+//! assert_eq!(72_i8.squarefree_part(), 6);
+//! ```
+//!
+//! Factorisation uses trial division by small primes, then Pollard's rho method with Brent's
+//! improvements. The Miller–Rabin test we use to recognise primes is deterministic up to
+//! 3.3 × 10²⁴, and beyond that it's a strong probable prime test with 20 bases.
+
+use crate::{
+    arithmetic::{add_mod, gcd, is_prime, mul_mod},
+    grouplike::{
+        AbelianGroup, CommutativeMagma, Magma, Monoid, Quasigroup, Semigroup, UnitalMagma,
+    },
+    operators::{BinaryOperator, Plus, Times},
+    ringlike::{
+        factorisation::Factorisation, polynomial, CommutativeRing, EuclideanDomain, IntegralDomain,
+        PrincipalIdealDomain, Ring, UniqueFactorisationDomain,
+    },
+};
+
+/// A nontrivial factor of an odd composite `n`, by Pollard's rho method with Brent's cycle
+/// detection, using the map *x* ↦ *x*² + `c`. Returns `n` if this `c` doesn't work.
+fn pollard_brent(n: u128, c: u128) -> u128 {
+    let f = |x: u128| add_mod(mul_mod(x, x, n), c, n);
+    let (mut x, mut y, mut saved) = (0, 2, 2);
+    let (mut r, mut q, mut g) = (1_u64, 1, 1);
+    while g == 1 {
+        x = y;
+        for _ in 0..r {
+            y = f(y);
+        }
+        let mut k = 0;
+        while k < r && g == 1 {
+            saved = y;
+            // Multiply the differences together and only take a gcd every so often.
+            for _ in 0..128.min(r - k) {
+                y = f(y);
+                q = mul_mod(q, x.abs_diff(y), n);
+            }
+            g = gcd(q, n);
+            k += 128;
+        }
+        r *= 2;
+    }
+    if g == n {
+        // We overshot, so go back and take the gcds one at a time.
+        loop {
+            saved = f(saved);
+            g = gcd(x.abs_diff(saved), n);
+            if g > 1 {
+                break;
+            }
+        }
+    }
+    g
+}
+
+/// The prime factorisation of a positive `n`, as primes in increasing order with multiplicities.
+fn factor_unsigned(mut n: u128) -> Vec<(u128, usize)> {
+    let mut primes = Vec::new();
+    let mut d = 2;
+    while d < 1000 && d * d <= n {
+        while n.is_multiple_of(d) {
+            primes.push(d);
+            n /= d;
+        }
+        d += 1;
+    }
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            primes.push(m);
+            continue;
+        }
+        let factor = (1..)
+            .map(|c| pollard_brent(m, c))
+            .find(|&g| g != m)
+            .expect("some c gives a factor");
+        stack.push(factor);
+        stack.push(m / factor);
+    }
+    primes.sort_unstable();
+    let mut factors: Vec<(u128, usize)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+macro_rules! integer_ring {
+    ($($t:ty),*) => {$(
+        impl BinaryOperator<Plus> for $t {
+            fn op(&self, rhs: Self) -> Self {
+                self.checked_add(rhs).expect("integer arithmetic overflowed")
+            }
+            fn op_assign(&mut self, rhs: Self) {
+                *self = BinaryOperator::<Plus>::op(self, rhs);
+            }
+        }
+
+        impl BinaryOperator<Times> for $t {
+            fn op(&self, rhs: Self) -> Self {
+                self.checked_mul(rhs).expect("integer arithmetic overflowed")
+            }
+            fn op_assign(&mut self, rhs: Self) {
+                *self = BinaryOperator::<Times>::op(self, rhs);
+            }
+        }
+
+        impl Magma<Plus> for $t {}
+        impl Semigroup<Plus> for $t {}
+        impl UnitalMagma<Plus> for $t {
+            const IDENTITY: Self = 0;
+        }
+        impl Quasigroup<Plus> for $t {
+            fn inverse(&self) -> Self {
+                self.checked_neg().expect("integer arithmetic overflowed")
+            }
+        }
+        impl CommutativeMagma<Plus> for $t {}
+        impl AbelianGroup<Plus> for $t {}
+
+        impl Magma<Times> for $t {}
+        impl Semigroup<Times> for $t {}
+        impl UnitalMagma<Times> for $t {
+            const IDENTITY: Self = 1;
+        }
+        impl CommutativeMagma<Times> for $t {}
+        impl Monoid<Times> for $t {}
+
+        impl Ring for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn left_inverse_mul(&self) -> Option<Self> {
+                self.inverse_mul()
+            }
+
+            fn right_inverse_mul(&self) -> Option<Self> {
+                self.inverse_mul()
+            }
+
+            fn inverse_mul(&self) -> Option<Self> {
+                // The only units are ±1.
+                match *self {
+                    1 => Some(1),
+                    -1 => Some(-1),
+                    _ => None,
+                }
+            }
+        }
+
+        impl CommutativeRing for $t {
+            /// Multiplies in [`i128`] arithmetic, whatever the width, using the multi-modular
+            /// method for long polynomials and Toom-3 for the rest. This panics if a coefficient
+            /// of the product doesn't fit.
+            fn mul_polynomials(a: &[Self], b: &[Self]) -> Vec<Self> {
+                let widen = |v: &[$t]| -> Vec<i128> { v.iter().map(|&c| c as i128).collect() };
+                polynomial::integer_product(&widen(a), &widen(b))
+                    .into_iter()
+                    .map(|c| <$t>::try_from(c).expect("integer arithmetic overflowed"))
+                    .collect()
+            }
+        }
+
+        impl IntegralDomain for $t {
+            fn associates(&self, rhs: Self) -> bool {
+                self.unsigned_abs() == rhs.unsigned_abs()
+            }
+        }
+
+        impl UniqueFactorisationDomain for $t {
+            /// Factorises into a sign and positive primes in increasing order.
+            fn factor(&self) -> Factorisation<Self> {
+                assert!(*self != 0, "zero has no factorisation");
+                let factors = factor_unsigned(self.unsigned_abs() as u128)
+                    .into_iter()
+                    .map(|(p, e)| (<$t>::try_from(p).expect("a prime factor fits"), e))
+                    .collect();
+                Factorisation::new(self.signum(), factors)
+            }
+
+            /// Uses the Miller–Rabin test rather than factorising.
+            fn is_irreducible(&self) -> bool {
+                is_prime(self.unsigned_abs() as u128)
+            }
+        }
+        impl PrincipalIdealDomain for $t {}
+
+        impl EuclideanDomain for $t {
+            /// The absolute value, saturating at [`usize::MAX`].
+            fn valuation(&self) -> usize {
+                usize::try_from(self.unsigned_abs()).unwrap_or(usize::MAX)
+            }
+
+            /// Euclidean division, so the remainder is always nonnegative.
+            fn div_rem(&self, rhs: Self) -> (Self, Self) {
+                assert!(rhs != 0, "division by zero");
+                (
+                    self.checked_div_euclid(rhs).expect("integer arithmetic overflowed"),
+                    self.checked_rem_euclid(rhs).expect("integer arithmetic overflowed"),
+                )
+            }
+
+            /// Makes the gcd nonnegative.
+            fn extended_gcd(&self, b: Self) -> (Self, Self, Self) {
+                let (mut r0, mut r1) = (*self, b);
+                let (mut s0, mut s1) = (1, 0);
+                let (mut t0, mut t1) = (0, 1);
+                while r1 != 0 {
+                    let (q, r) = r0.div_rem(r1);
+                    (r0, r1) = (r1, r);
+                    (s0, s1) = (s1, s0.sub(q.mul(s1)));
+                    (t0, t1) = (t1, t0.sub(q.mul(t1)));
+                }
+                if r0 < 0 {
+                    (r0.inverse_add(), s0.inverse_add(), t0.inverse_add())
+                } else {
+                    (r0, s0, t0)
+                }
+            }
+
+            /// Makes the gcd nonnegative.
+            fn euclid(&self, b: Self) -> Self {
+                let (mut r0, mut r1) = (*self, b);
+                while r1 != 0 {
+                    (r0, r1) = (r1, r0.rem(r1));
+                }
+                if r0 < 0 {
+                    r0.inverse_add()
+                } else {
+                    r0
+                }
+            }
+        }
+    )*};
+}
+
+integer_ring!(i8, i16, i32, i64, i128, isize);
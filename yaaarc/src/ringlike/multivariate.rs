@@ -0,0 +1,1008 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Multivariate polynomial rings, *R*\[*x*₁, …, *xₙ*\].
+//!
+//! Unlike [`super::polynomial`], polynomials here are stored sparsely, as a list of terms *cx*^α
+//! where *x*^α = *x*₁^α₁ ⋯ *xₙ*^αₙ is a [`Monomial`]. To talk about the leading term, which
+//! division and Gröbner bases need, the terms are sorted by a [`MonomialOrder`]. The order is part
+//! of the type, so [`MPoly<R, Lex>`] and [`MPoly<R, GRevLex>`] are different types, and
+//! [`MPoly::reorder`] converts between them.
+//!
+//! There's no fixed number of variables: *x*₁ and *x*₁*x*₂ can be added together, and we treat
+//! *R*\[*x*₁, …, *xₙ*\] as a subring of *R*\[*x*₁, …, *xₙ*₊₁\].
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{
+//!     multivariate::{GRevLex, MPoly},
+//!     rational::Rational,
+//! };
+//!
+//! type P = MPoly<Rational, GRevLex>;
+//!
+//! let (x, y) = (P::var(0), P::var(1));
+//! let f = &(&x * &x) - &y;
+//! let g = &x + &y;
+//! assert_eq!(format!("{}", &f * &g), "x1^3 + x1^2*x2 - x1*x2 - x2^2");
+//! assert_eq!(f.total_degree(), Some(2));
+//! assert_eq!(f.evaluate(&[Rational::from(3), Rational::from(2)]), Rational::from(7));
+//! ```
+
+use std::{cmp::Ordering, collections::HashMap, fmt, hash::Hash, marker::PhantomData};
+
+use crate::{
+    grouplike::{
+        AbelianGroup, CommutativeMagma, Magma, Monoid, Quasigroup, Semigroup, UnitalMagma,
+    },
+    operators::{BinaryOperator, Plus, Times},
+    ringlike::{polynomial::multiple, CommutativeRing, Field, IntegralDomain, Ring},
+};
+
+/// A monomial *x*₁^α₁ ⋯ *xₙ*^αₙ, stored as its exponent vector α.
+///
+/// Trailing zero exponents are never stored, so *x*₁ is the same monomial whether we think of it
+/// as living in one variable or ten.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::ringlike::multivariate::Monomial;
+///
+/// let a = Monomial::new(vec![2, 1, 0]);
+/// let b = Monomial::new(vec![1, 3]);
+/// assert_eq!(a.exponents(), [2, 1]);
+/// assert_eq!(a.lcm(&b), Monomial::new(vec![2, 3]));
+/// assert_eq!(&a * &b, Monomial::new(vec![3, 4]));
+/// assert!(!a.divides(&b));
+/// assert_eq!(format!("{a}"), "x1^2*x2");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Monomial(Vec<u32>);
+
+/// The monomial 1, so that we can hand out references to it.
+static ONE: Monomial = Monomial(Vec::new());
+
+impl Monomial {
+    /// The monomial with the given exponents.
+    pub fn new(mut exponents: Vec<u32>) -> Self {
+        while exponents.last() == Some(&0) {
+            exponents.pop();
+        }
+        Monomial(exponents)
+    }
+
+    /// The monomial 1, with every exponent zero.
+    pub const fn one() -> Self {
+        Monomial(Vec::new())
+    }
+
+    /// The variable *xᵢ*₊₁, counting from zero.
+    pub fn var(i: usize) -> Self {
+        let mut exponents = vec![0; i + 1];
+        exponents[i] = 1;
+        Monomial(exponents)
+    }
+
+    /// The exponents, without trailing zeros.
+    pub fn exponents(&self) -> &[u32] {
+        &self.0
+    }
+
+    /// The exponent of the `i`-th variable, counting from zero.
+    pub fn exponent(&self, i: usize) -> u32 {
+        self.0.get(i).copied().unwrap_or(0)
+    }
+
+    /// The number of variables up to the last one which appears.
+    pub fn num_vars(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether this is the monomial 1.
+    pub fn is_one(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The total degree α₁ + ⋯ + αₙ.
+    pub fn degree(&self) -> u32 {
+        self.0.iter().sum()
+    }
+
+    /// Whether `self` divides `other`, i.e. every exponent of `self` is at most that of `other`.
+    pub fn divides(&self, other: &Self) -> bool {
+        self.0.len() <= other.0.len() && self.0.iter().zip(&other.0).all(|(a, b)| a <= b)
+    }
+
+    /// `self` / `other`, or [`None`] if `other` doesn't divide `self`.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.divides(self).then(|| {
+            Monomial::new(
+                (0..self.num_vars())
+                    .map(|i| self.exponent(i) - other.exponent(i))
+                    .collect(),
+            )
+        })
+    }
+
+    /// The least common multiple, taking the larger exponent of each variable.
+    pub fn lcm(&self, other: &Self) -> Self {
+        let n = self.num_vars().max(other.num_vars());
+        Monomial(
+            (0..n)
+                .map(|i| self.exponent(i).max(other.exponent(i)))
+                .collect(),
+        )
+    }
+
+    /// The greatest common divisor, taking the smaller exponent of each variable.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let n = self.num_vars().min(other.num_vars());
+        Monomial::new(
+            (0..n)
+                .map(|i| self.exponent(i).min(other.exponent(i)))
+                .collect(),
+        )
+    }
+
+    /// Whether `self` and `other` have no variables in common.
+    pub fn is_coprime(&self, other: &Self) -> bool {
+        self.0.iter().zip(&other.0).all(|(a, b)| *a == 0 || *b == 0)
+    }
+}
+
+impl std::ops::Mul<&Monomial> for &Monomial {
+    type Output = Monomial;
+
+    fn mul(self, rhs: &Monomial) -> Self::Output {
+        let n = self.num_vars().max(rhs.num_vars());
+        Monomial((0..n).map(|i| self.exponent(i) + rhs.exponent(i)).collect())
+    }
+}
+
+impl std::ops::Mul for Monomial {
+    type Output = Monomial;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl fmt::Display for Monomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_one() {
+            return write!(f, "1");
+        }
+        let mut first = true;
+        for (i, &e) in self.0.iter().enumerate() {
+            if e == 0 {
+                continue;
+            }
+            if !first {
+                write!(f, "*")?;
+            }
+            first = false;
+            write!(f, "x{}", i + 1)?;
+            if e > 1 {
+                write!(f, "^{e}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A monomial order, a total order on monomials which respects multiplication and has 1 as its
+/// smallest element.
+///
+/// That is, if *a* < *b* then *ac* < *bc* for every monomial *c*. Together with 1 being smallest,
+/// this makes it a well-order, so repeatedly taking away leading terms always stops.
+///
+/// Orders are types rather than values, so that a polynomial's type says which order its terms
+/// are sorted by.
+pub trait MonomialOrder: Clone + Copy + fmt::Debug + Default + PartialEq + Eq + Hash {
+    /// Compares two monomials.
+    fn compare(a: &Monomial, b: &Monomial) -> Ordering;
+
+    /// A weight vector *w* for monomials in `variables` variables which this order refines, i.e.
+    /// if *w* · α > *w* · β then *x*^α > *x*^β. The Gröbner walk (see
+    /// [`crate::ringlike::groebner::walk`]) goes between these.
+    ///
+    /// The zero vector always works, which is the default, but then the walk has to do all its work
+    /// in one step.
+    fn weight(variables: usize) -> Vec<u32> {
+        vec![0; variables]
+    }
+}
+
+/// The lexicographic order, comparing the exponents of *x*₁ first, then *x*₂ and so on.
+///
+/// This is the order for eliminating variables and solving systems, since a Gröbner basis for it
+/// is triangular. It's also the most expensive one to compute Gröbner bases for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Lex;
+
+/// The graded lexicographic order, comparing total degrees first and breaking ties
+/// lexicographically.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GrLex;
+
+/// The graded reverse lexicographic order, comparing total degrees first, and then the monomial
+/// with the *smaller* exponent of the last variable where they differ is bigger.
+///
+/// Gröbner bases for this order are usually the smallest and quickest to compute.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GRevLex;
+
+/// Compares the exponent vectors of `a` and `b` from the first variable onwards.
+fn lex(a: &Monomial, b: &Monomial) -> Ordering {
+    let n = a.num_vars().max(b.num_vars());
+    (0..n)
+        .map(|i| a.exponent(i).cmp(&b.exponent(i)))
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+impl MonomialOrder for Lex {
+    fn compare(a: &Monomial, b: &Monomial) -> Ordering {
+        lex(a, b)
+    }
+
+    /// The exponent of *x*₁.
+    fn weight(variables: usize) -> Vec<u32> {
+        (0..variables).map(|i| u32::from(i == 0)).collect()
+    }
+}
+
+impl MonomialOrder for GrLex {
+    fn compare(a: &Monomial, b: &Monomial) -> Ordering {
+        a.degree().cmp(&b.degree()).then_with(|| lex(a, b))
+    }
+
+    /// The total degree.
+    fn weight(variables: usize) -> Vec<u32> {
+        vec![1; variables]
+    }
+}
+
+impl MonomialOrder for GRevLex {
+    fn compare(a: &Monomial, b: &Monomial) -> Ordering {
+        let n = a.num_vars().max(b.num_vars());
+        a.degree().cmp(&b.degree()).then_with(|| {
+            (0..n)
+                .rev()
+                .map(|i| b.exponent(i).cmp(&a.exponent(i)))
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+
+    /// The total degree.
+    fn weight(variables: usize) -> Vec<u32> {
+        vec![1; variables]
+    }
+}
+
+/// The block order which compares the first `K` variables by [`GRevLex`], and breaks ties with
+/// `O`.
+///
+/// Every monomial involving *x*₁, …, *x*\_`K` is bigger than every one without them, so this is
+/// an *elimination order*: the elements of a Gröbner basis for it which don't involve those
+/// variables form a Gröbner basis of the *elimination ideal* *I* ∩ *K*\[*x*\_`K`+1, …, *xₙ*\]
+/// for `O`. Unlike [`Lex`], which is an elimination order for every block at the front, it
+/// doesn't compare the variables within each block lexicographically, so its Gröbner bases are
+/// usually much smaller. To eliminate other variables, move them to the front first.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::ringlike::{
+///     groebner::groebner_basis,
+///     multivariate::{Block, GRevLex, MPoly},
+///     rational::Rational,
+/// };
+///
+/// type P = MPoly<Rational, Block<2, GRevLex>>;
+///
+/// // Eliminating s and t from x = s + t, y = st, z = s² + t² leaves z = x² - 2y.
+/// let (s, t, x, y, z) = (P::var(0), P::var(1), P::var(2), P::var(3), P::var(4));
+/// let basis = groebner_basis(&[&x - &(&s + &t), &y - &(&s * &t), &z - &(&(&s * &s) + &(&t * &t))]);
+/// let eliminated: Vec<&P> = basis
+///     .iter()
+///     .filter(|g| g.degree_in(0) == 0 && g.degree_in(1) == 0)
+///     .collect();
+/// let two = P::constant(2.into());
+/// assert_eq!(eliminated, [&(&(&(&x * &x) - &(&two * &y)) - &z)]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Block<const K: usize, O>(PhantomData<O>);
+
+impl<const K: usize, O: MonomialOrder> MonomialOrder for Block<K, O> {
+    fn compare(a: &Monomial, b: &Monomial) -> Ordering {
+        let degree = |m: &Monomial| (0..K).map(|i| m.exponent(i)).sum::<u32>();
+        degree(a)
+            .cmp(&degree(b))
+            .then_with(|| {
+                (0..K)
+                    .rev()
+                    .map(|i| b.exponent(i).cmp(&a.exponent(i)))
+                    .find(|o| o.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .then_with(|| O::compare(a, b))
+    }
+
+    /// The total degree in *x*₁, …, *x*\_`K`.
+    fn weight(variables: usize) -> Vec<u32> {
+        (0..variables).map(|i| u32::from(i < K)).collect()
+    }
+}
+
+/// A polynomial in several variables with coefficients in a commutative ring *R*, with its terms
+/// sorted by the monomial order `O`.
+///
+/// Zero coefficients are never stored, so two polynomials are equal exactly when their terms are.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::ringlike::multivariate::{Lex, MPoly, Monomial};
+///
+/// // 3x²y - x + 5 over the integers.
+/// let f: MPoly<i64, Lex> = MPoly::new(vec![(vec![2, 1], 3), (vec![1], -1), (vec![], 5)]);
+/// assert_eq!(f.leading_monomial(), Some(&Monomial::new(vec![2, 1])));
+/// assert_eq!(f.leading_coefficient(), 3);
+/// assert_eq!(f.num_terms(), 3);
+/// assert_eq!(f.degree_in(1), 1);
+/// assert_eq!(format!("{}", f.pow(2)), "9*x1^4*x2^2 - 6*x1^3*x2 + 30*x1^2*x2 + x1^2 - 10*x1 + 25");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MPoly<R, O> {
+    /// The nonconstant terms in increasing order, so the leading term is last.
+    terms: Vec<(Monomial, R)>,
+    /// The constant term, which is the smallest in every monomial order.
+    constant: R,
+    order: PhantomData<O>,
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> MPoly<R, O> {
+    /// Creates a polynomial from its terms, given as exponent vectors and coefficients. The terms
+    /// can be in any order, and repeated monomials are added together.
+    pub fn new(terms: Vec<(Vec<u32>, R)>) -> Self {
+        MPoly::from_terms(terms.into_iter().map(|(e, c)| (Monomial::new(e), c)))
+    }
+
+    /// Creates a polynomial from its terms in any order, adding together repeated monomials.
+    pub fn from_terms(terms: impl IntoIterator<Item = (Monomial, R)>) -> Self {
+        let mut sums: HashMap<Monomial, R> = HashMap::new();
+        for (m, c) in terms {
+            match sums.get_mut(&m) {
+                Some(sum) => *sum = sum.add(c),
+                None => {
+                    sums.insert(m, c);
+                }
+            }
+        }
+        let mut terms: Vec<(Monomial, R)> =
+            sums.into_iter().filter(|(_, c)| *c != R::ZERO).collect();
+        terms.sort_by(|(a, _), (b, _)| O::compare(a, b));
+        MPoly::from_increasing(terms)
+    }
+
+    /// Creates a polynomial from nonzero terms which are already in strictly increasing order.
+    fn from_increasing(mut terms: Vec<(Monomial, R)>) -> Self {
+        let constant = match terms.first() {
+            Some((m, _)) if m.is_one() => terms.remove(0).1,
+            _ => R::ZERO,
+        };
+        MPoly {
+            terms,
+            constant,
+            order: PhantomData,
+        }
+    }
+
+    /// The terms in increasing order, including the constant term if it's nonzero.
+    fn increasing(&self) -> impl Iterator<Item = (&Monomial, &R)> {
+        let constant = (self.constant != R::ZERO).then_some((&ONE, &self.constant));
+        constant
+            .into_iter()
+            .chain(self.terms.iter().map(|(m, c)| (m, c)))
+    }
+
+    /// The constant polynomial *c*.
+    pub fn constant(c: R) -> Self {
+        MPoly {
+            terms: Vec::new(),
+            constant: c,
+            order: PhantomData,
+        }
+    }
+
+    /// The variable *xᵢ*₊₁, counting from zero.
+    pub fn var(i: usize) -> Self {
+        MPoly::term(R::ONE, Monomial::var(i))
+    }
+
+    /// The single term *cx*^α.
+    pub fn term(c: R, monomial: Monomial) -> Self {
+        if c == R::ZERO {
+            MPoly::ZERO
+        } else if monomial.is_one() {
+            MPoly::constant(c)
+        } else {
+            MPoly::from_increasing(vec![(monomial, c)])
+        }
+    }
+
+    /// Whether this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty() && self.constant == R::ZERO
+    }
+
+    /// Whether this is a constant, including zero.
+    pub fn is_constant(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The constant term.
+    pub fn constant_term(&self) -> R {
+        self.constant.clone()
+    }
+
+    /// The number of nonzero terms.
+    pub fn num_terms(&self) -> usize {
+        self.terms.len() + usize::from(self.constant != R::ZERO)
+    }
+
+    /// The terms in decreasing order, as monomials and their nonzero coefficients.
+    pub fn terms(&self) -> impl Iterator<Item = (&Monomial, &R)> {
+        let constant = (self.constant != R::ZERO).then_some((&ONE, &self.constant));
+        self.terms.iter().rev().map(|(m, c)| (m, c)).chain(constant)
+    }
+
+    /// The monomials with nonzero coefficients, in decreasing order.
+    pub fn support(&self) -> impl Iterator<Item = &Monomial> {
+        self.terms().map(|(m, _)| m)
+    }
+
+    /// The coefficient of `monomial`, which is zero if it doesn't appear.
+    pub fn coefficient(&self, monomial: &Monomial) -> R {
+        if monomial.is_one() {
+            return self.constant.clone();
+        }
+        self.terms
+            .binary_search_by(|(m, _)| O::compare(m, monomial))
+            .map_or(R::ZERO, |i| self.terms[i].1.clone())
+    }
+
+    /// The largest monomial in the order `O`, or [`None`] for the zero polynomial.
+    pub fn leading_monomial(&self) -> Option<&Monomial> {
+        self.terms().next().map(|(m, _)| m)
+    }
+
+    /// The coefficient of the leading monomial, which is zero for the zero polynomial.
+    pub fn leading_coefficient(&self) -> R {
+        self.terms().next().map_or(R::ZERO, |(_, c)| c.clone())
+    }
+
+    /// The leading term, as a polynomial.
+    pub fn leading_term(&self) -> Self {
+        self.terms()
+            .next()
+            .map_or(MPoly::ZERO, |(m, c)| MPoly::term(c.clone(), m.clone()))
+    }
+
+    /// Removes and returns the leading term.
+    fn pop_leading(&mut self) -> Option<(Monomial, R)> {
+        match self.terms.pop() {
+            Some(term) => Some(term),
+            None if self.constant != R::ZERO => Some((
+                Monomial::one(),
+                std::mem::replace(&mut self.constant, R::ZERO),
+            )),
+            None => None,
+        }
+    }
+
+    /// The largest total degree of a term, or [`None`] for the zero polynomial.
+    pub fn total_degree(&self) -> Option<u32> {
+        self.support().map(Monomial::degree).max()
+    }
+
+    /// The largest exponent of the `i`-th variable, counting from zero.
+    pub fn degree_in(&self, i: usize) -> u32 {
+        self.support().map(|m| m.exponent(i)).max().unwrap_or(0)
+    }
+
+    /// The number of variables up to the last one which appears.
+    pub fn num_vars(&self) -> usize {
+        self.support().map(Monomial::num_vars).max().unwrap_or(0)
+    }
+
+    /// Evaluates the polynomial with *xᵢ*₊₁ = `point[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `point` doesn't have a value for every variable which appears.
+    pub fn evaluate(&self, point: &[R]) -> R {
+        assert!(
+            point.len() >= self.num_vars(),
+            "there must be a value for each variable"
+        );
+        self.terms().fold(R::ZERO, |acc, (m, c)| {
+            let value = m
+                .exponents()
+                .iter()
+                .zip(point)
+                .fold(c.clone(), |v, (&e, x)| {
+                    (0..e).fold(v, |v, _| v.mul(x.clone()))
+                });
+            acc.add(value)
+        })
+    }
+
+    /// Multiplies every coefficient by `c`.
+    pub fn scale(&self, c: &R) -> Self {
+        MPoly::from_increasing(
+            self.increasing()
+                .map(|(m, a)| (m.clone(), a.mul(c.clone())))
+                .filter(|(_, a)| *a != R::ZERO)
+                .collect(),
+        )
+    }
+
+    /// Multiplies by the term *cx*^α. Since monomial orders respect multiplication, the terms
+    /// stay in order.
+    pub fn mul_term(&self, c: &R, monomial: &Monomial) -> Self {
+        MPoly::from_increasing(
+            self.increasing()
+                .map(|(m, a)| (m * monomial, a.mul(c.clone())))
+                .filter(|(_, a)| *a != R::ZERO)
+                .collect(),
+        )
+    }
+
+    /// Raises `self` to the power `exponent` by repeated squaring.
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut result = MPoly::ONE;
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.times(&base);
+            }
+            base = base.times(&base);
+            exponent /= 2;
+        }
+        result
+    }
+
+    /// The formal partial derivative with respect to *xᵢ*.
+    pub fn derivative(&self, i: usize) -> Self {
+        MPoly::from_terms(
+            self.terms()
+                .filter(|(m, _)| m.exponent(i) > 0)
+                .map(|(m, c)| {
+                    let mut exponents = m.exponents().to_vec();
+                    exponents[i] -= 1;
+                    (
+                        Monomial::new(exponents),
+                        multiple(c, m.exponent(i) as usize),
+                    )
+                }),
+        )
+    }
+
+    /// The same polynomial with its terms sorted by another monomial order.
+    pub fn reorder<P: MonomialOrder>(&self) -> MPoly<R, P> {
+        let mut terms: Vec<(Monomial, R)> = self
+            .increasing()
+            .map(|(m, c)| (m.clone(), c.clone()))
+            .collect();
+        terms.sort_by(|(a, _), (b, _)| P::compare(a, b));
+        MPoly::from_increasing(terms)
+    }
+
+    /// Applies `f` to every coefficient, such as to reduce integer coefficients modulo a prime.
+    pub fn map_coefficients<S, F>(&self, f: F) -> MPoly<S, O>
+    where
+        S: CommutativeRing + Clone + PartialEq,
+        F: Fn(&R) -> S,
+    {
+        MPoly::from_increasing(
+            self.increasing()
+                .map(|(m, c)| (m.clone(), f(c)))
+                .filter(|(_, c)| *c != S::ZERO)
+                .collect(),
+        )
+    }
+
+    fn plus(&self, rhs: &Self) -> Self {
+        let mut result = Vec::with_capacity(self.num_terms() + rhs.num_terms());
+        let mut a = self.increasing().peekable();
+        let mut b = rhs.increasing().peekable();
+        loop {
+            let ordering = match (a.peek(), b.peek()) {
+                (Some((m, _)), Some((n, _))) => O::compare(m, n),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match ordering {
+                Ordering::Less => {
+                    let (m, c) = a.next().expect("peeked");
+                    result.push((m.clone(), c.clone()));
+                }
+                Ordering::Greater => {
+                    let (m, c) = b.next().expect("peeked");
+                    result.push((m.clone(), c.clone()));
+                }
+                Ordering::Equal => {
+                    let (m, c) = a.next().expect("peeked");
+                    let (_, d) = b.next().expect("peeked");
+                    let sum = c.add(d.clone());
+                    if sum != R::ZERO {
+                        result.push((m.clone(), sum));
+                    }
+                }
+            }
+        }
+        MPoly::from_increasing(result)
+    }
+
+    fn negated(&self) -> Self {
+        MPoly::from_increasing(
+            self.increasing()
+                .map(|(m, c)| (m.clone(), c.inverse_add()))
+                .collect(),
+        )
+    }
+
+    fn times(&self, rhs: &Self) -> Self {
+        MPoly::from_terms(self.increasing().flat_map(|(m, c)| {
+            rhs.increasing()
+                .map(move |(n, d)| (m * n, c.mul(d.clone())))
+        }))
+    }
+}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> MPoly<R, O> {
+    /// Divides by the leading coefficient, so the result is zero or has leading coefficient 1.
+    pub fn monic(&self) -> Self {
+        match self.leading_coefficient().inverse_mul() {
+            Some(inverse) => self.scale(&inverse),
+            None => MPoly::ZERO,
+        }
+    }
+
+    /// `self` - *cx*^α `g`.
+    fn sub_mul(&self, c: &R, monomial: &Monomial, g: &Self) -> Self {
+        self.plus(&g.mul_term(&c.inverse_add(), monomial))
+    }
+
+    /// Division by several polynomials, which returns quotients *qᵢ* and a remainder *r* with
+    /// `self` = *q*₁ `divisors[0]` + ⋯ + *r*, where no term of *r* is divisible by the leading
+    /// monomial of any divisor.
+    ///
+    /// While there's a term left, we take the largest one and cancel it with the first divisor
+    /// whose leading monomial divides it, or move it to the remainder if there's none. Unlike in
+    /// one variable, the result depends on the order of the divisors, unless they're a Gröbner
+    /// basis. See [`crate::ringlike::groebner`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::{
+    ///     multivariate::{Lex, MPoly},
+    ///     rational::Rational,
+    /// };
+    ///
+    /// let p = |terms: Vec<(Vec<u32>, i128)>| {
+    ///     MPoly::<Rational, Lex>::new(terms.into_iter().map(|(e, c)| (e, c.into())).collect())
+    /// };
+    /// // x²y + xy² + y² divided by xy - 1 and y² - 1.
+    /// let f = p(vec![(vec![2, 1], 1), (vec![1, 2], 1), (vec![0, 2], 1)]);
+    /// let divisors = [p(vec![(vec![1, 1], 1), (vec![], -1)]), p(vec![(vec![0, 2], 1), (vec![], -1)])];
+    /// let (quotients, remainder) = f.div_rem_list(&divisors);
+    /// assert_eq!(quotients, [p(vec![(vec![1], 1), (vec![0, 1], 1)]), p(vec![(vec![], 1)])]);
+    /// assert_eq!(remainder, p(vec![(vec![1], 1), (vec![0, 1], 1), (vec![], 1)]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a divisor is zero.
+    pub fn div_rem_list(&self, divisors: &[Self]) -> (Vec<Self>, Self) {
+        assert!(
+            divisors.iter().all(|g| !g.is_zero()),
+            "cannot divide by the zero polynomial"
+        );
+        let mut quotients = vec![MPoly::ZERO; divisors.len()];
+        let mut remainder = Vec::new();
+        let mut p = self.clone();
+        while let Some(m) = p.leading_monomial().cloned() {
+            let division = divisors.iter().enumerate().find_map(|(i, g)| {
+                let lead = g.leading_monomial().expect("divisors are nonzero");
+                m.checked_div(lead).map(|t| (i, t))
+            });
+            match division {
+                Some((i, t)) => {
+                    let c = p
+                        .leading_coefficient()
+                        .div(divisors[i].leading_coefficient());
+                    quotients[i] = quotients[i].plus(&MPoly::term(c.clone(), t.clone()));
+                    p = p.sub_mul(&c, &t, &divisors[i]);
+                }
+                None => remainder.push(p.pop_leading().expect("p is nonzero")),
+            }
+        }
+        remainder.reverse();
+        (quotients, MPoly::from_increasing(remainder))
+    }
+
+    /// The remainder of `self` on division by `divisors`, as in [`MPoly::div_rem_list`].
+    ///
+    /// If `divisors` is a Gröbner basis, this is the normal form of `self` modulo the ideal they
+    /// generate, and it's zero exactly when `self` is in the ideal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a divisor is zero.
+    pub fn reduce(&self, divisors: &[Self]) -> Self {
+        assert!(
+            divisors.iter().all(|g| !g.is_zero()),
+            "cannot divide by the zero polynomial"
+        );
+        let mut remainder = Vec::new();
+        let mut p = self.clone();
+        while let Some(m) = p.leading_monomial().cloned() {
+            let division = divisors.iter().find_map(|g| {
+                let lead = g.leading_monomial().expect("divisors are nonzero");
+                m.checked_div(lead).map(|t| (g, t))
+            });
+            match division {
+                Some((g, t)) => {
+                    let c = p.leading_coefficient().div(g.leading_coefficient());
+                    p = p.sub_mul(&c, &t, g);
+                }
+                None => remainder.push(p.pop_leading().expect("p is nonzero")),
+            }
+        }
+        remainder.reverse();
+        MPoly::from_increasing(remainder)
+    }
+
+    /// The S-polynomial of `self` and `other`, the combination of them which cancels both of
+    /// their leading terms out.
+    ///
+    /// If *m* is the lcm of the leading monomials, this is (*m*/LT(`self`)) `self` -
+    /// (*m*/LT(`other`)) `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either is zero.
+    pub fn s_polynomial(&self, other: &Self) -> Self {
+        let (a, b) = (
+            self.leading_monomial().expect("self is nonzero"),
+            other.leading_monomial().expect("other is nonzero"),
+        );
+        let lcm = a.lcm(b);
+        let left = self.mul_term(
+            &self.leading_coefficient().inverse_mul().expect("nonzero"),
+            &lcm.checked_div(a).expect("a divides the lcm"),
+        );
+        let right = other.mul_term(
+            &other.leading_coefficient().inverse_mul().expect("nonzero"),
+            &lcm.checked_div(b).expect("b divides the lcm"),
+        );
+        left.plus(&right.negated())
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> Default for MPoly<R, O> {
+    fn default() -> Self {
+        MPoly::ZERO
+    }
+}
+
+impl<R, O> fmt::Display for MPoly<R, O>
+where
+    R: CommutativeRing + Clone + PartialEq + fmt::Display,
+    O: MonomialOrder,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        for (i, (m, c)) in self.terms().enumerate() {
+            let mut coefficient = c.to_string();
+            if coefficient.contains(' ') {
+                coefficient = format!("({coefficient})");
+            }
+            let (negative, magnitude) = match coefficient.strip_prefix('-') {
+                Some(magnitude) => (true, magnitude),
+                None => (false, coefficient.as_str()),
+            };
+            match (i == 0, negative) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            match (m.is_one(), magnitude == "1") {
+                (true, _) => write!(f, "{magnitude}")?,
+                (false, true) => write!(f, "{m}")?,
+                (false, false) => write!(f, "{magnitude}*{m}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> std::ops::Add<&MPoly<R, O>>
+    for &MPoly<R, O>
+{
+    type Output = MPoly<R, O>;
+
+    fn add(self, rhs: &MPoly<R, O>) -> Self::Output {
+        self.plus(rhs)
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> std::ops::Add for MPoly<R, O> {
+    type Output = MPoly<R, O>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.plus(&rhs)
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> std::ops::Sub<&MPoly<R, O>>
+    for &MPoly<R, O>
+{
+    type Output = MPoly<R, O>;
+
+    fn sub(self, rhs: &MPoly<R, O>) -> Self::Output {
+        self.plus(&rhs.negated())
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> std::ops::Sub for MPoly<R, O> {
+    type Output = MPoly<R, O>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.plus(&rhs.negated())
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> std::ops::Mul<&MPoly<R, O>>
+    for &MPoly<R, O>
+{
+    type Output = MPoly<R, O>;
+
+    fn mul(self, rhs: &MPoly<R, O>) -> Self::Output {
+        self.times(rhs)
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> std::ops::Mul for MPoly<R, O> {
+    type Output = MPoly<R, O>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.times(&rhs)
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> std::ops::Neg for &MPoly<R, O> {
+    type Output = MPoly<R, O>;
+
+    fn neg(self) -> Self::Output {
+        self.negated()
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> std::ops::Neg for MPoly<R, O> {
+    type Output = MPoly<R, O>;
+
+    fn neg(self) -> Self::Output {
+        self.negated()
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> BinaryOperator<Plus>
+    for MPoly<R, O>
+{
+    fn op(&self, rhs: Self) -> Self {
+        self.plus(&rhs)
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.plus(&rhs);
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> BinaryOperator<Times>
+    for MPoly<R, O>
+{
+    fn op(&self, rhs: Self) -> Self {
+        self.times(&rhs)
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.times(&rhs);
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> Magma<Plus> for MPoly<R, O> {}
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> Semigroup<Plus> for MPoly<R, O> {}
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> UnitalMagma<Plus> for MPoly<R, O> {
+    const IDENTITY: Self = MPoly::ZERO;
+}
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> Quasigroup<Plus> for MPoly<R, O> {
+    fn inverse(&self) -> Self {
+        self.negated()
+    }
+}
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> CommutativeMagma<Plus>
+    for MPoly<R, O>
+{
+}
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> AbelianGroup<Plus> for MPoly<R, O> {}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> Magma<Times> for MPoly<R, O> {}
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> Semigroup<Times> for MPoly<R, O> {}
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> UnitalMagma<Times> for MPoly<R, O> {
+    const IDENTITY: Self = MPoly::ONE;
+}
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> CommutativeMagma<Times>
+    for MPoly<R, O>
+{
+}
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> Monoid<Times> for MPoly<R, O> {}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> Ring for MPoly<R, O> {
+    const ZERO: Self = MPoly {
+        terms: Vec::new(),
+        constant: R::ZERO,
+        order: PhantomData,
+    };
+    const ONE: Self = MPoly {
+        terms: Vec::new(),
+        constant: R::ONE,
+        order: PhantomData,
+    };
+
+    fn left_inverse_mul(&self) -> Option<Self> {
+        self.inverse_mul()
+    }
+
+    fn right_inverse_mul(&self) -> Option<Self> {
+        self.inverse_mul()
+    }
+
+    /// The inverse of a unit of *R*, as a constant polynomial. Like [`super::polynomial::Poly`],
+    /// we don't look for the nonconstant units which exist when *R* has nilpotent elements.
+    fn inverse_mul(&self) -> Option<Self> {
+        if self.terms.is_empty() {
+            self.constant.inverse_mul().map(MPoly::constant)
+        } else {
+            None
+        }
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq, O: MonomialOrder> CommutativeRing for MPoly<R, O> {}
+
+impl<R: Field + Clone + PartialEq, O: MonomialOrder> IntegralDomain for MPoly<R, O> {
+    fn associates(&self, rhs: Self) -> bool {
+        self.monic() == rhs.monic()
+    }
+}
@@ -0,0 +1,1292 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Univariate polynomial rings, *R*\[*x*\].
+//!
+//! If *R* is a commutative ring, then so is *R*\[*x*\]. If *R* is a field, then *R*\[*x*\] is even a
+//! Euclidean domain, with the degree as its valuation: we can divide with remainder, and so we get
+//! greatest common divisors by Euclid's algorithm.
+//!
+//! Polynomials are stored densely, so this is a poor choice for something like *x*¹⁰⁰⁰⁰ + 1.
+//! Multiplication picks an algorithm by the coefficient ring and the size of the polynomials, as
+//! [`CommutativeRing::mul_polynomials`] describes, and division and gcds build on it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{polynomial::Poly, rational::Rational, GCDDomain};
+//!
+//! let q = |n: i128| Rational::from(n);
+//! // x² - 1 and x² - 3x + 2 share the factor x - 1.
+//! let f = Poly::new(vec![q(-1), q(0), q(1)]);
+//! let g = Poly::new(vec![q(2), q(-3), q(1)]);
+//!
+//! assert_eq!(f.gcd(g.clone()), Poly::new(vec![q(-1), q(1)]));
+//! assert_eq!(g.evaluate(&q(2)), q(0));
+//! assert_eq!(format!("{}", f * g), "x^4 - 3*x^3 + x^2 + 3*x - 2");
+//! ```
+
+use std::fmt;
+
+use crate::{
+    arithmetic::{inverse_mod, mul_mod},
+    grouplike::{
+        AbelianGroup, CommutativeMagma, Magma, Monoid, Quasigroup, Semigroup, UnitalMagma,
+    },
+    operators::{BinaryOperator, Plus, Times},
+    ringlike::{
+        factorisation::{self, FactorisableField, Factorisation},
+        finite_field::PrimeField,
+        CommutativeRing, EuclideanDomain, Field, GCDDomain, IntegralDomain, PrincipalIdealDomain,
+        Ring, UniqueFactorisationDomain,
+    },
+};
+
+/// A polynomial *c*₀ + *c*₁*x* + ⋯ + *cₙxⁿ* with coefficients in a commutative ring *R*.
+///
+/// Trailing zero coefficients are never stored, so two polynomials are equal exactly when their
+/// coefficients are.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::ringlike::polynomial::Poly;
+///
+/// // (x + 1)³ = x³ + 3x² + 3x + 1 over the integers.
+/// let f = Poly::new(vec![1_i64, 1]).pow(3);
+/// assert_eq!(f.coefficients(), vec![1, 3, 3, 1]);
+/// assert_eq!(f.degree(), Some(3));
+/// assert_eq!(f.derivative(), Poly::new(vec![3, 6, 3]));
+/// // Substituting x - 1 for x gives back x³.
+/// assert_eq!(f.compose(&Poly::new(vec![-1, 1])), Poly::monomial(1, 3));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Poly<R> {
+    /// The constant coefficient *c*₀.
+    constant: R,
+    /// `coefficients[i]` is the coefficient of *x*^(i + 1). Trailing zeros are trimmed.
+    coefficients: Vec<R>,
+}
+
+/// Adds *n* copies of `c` together, by doubling.
+pub(crate) fn multiple<R: Ring + Clone>(c: &R, mut n: usize) -> R {
+    let mut result = R::ZERO;
+    let mut power = c.clone();
+    while n > 0 {
+        if n % 2 == 1 {
+            result = result.add(power.clone());
+        }
+        power = power.add(power.clone());
+        n /= 2;
+    }
+    result
+}
+
+/// Below this many coefficients, Karatsuba multiplication falls back to the schoolbook method.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Below this many coefficients, Toom-3 multiplication falls back to Karatsuba.
+const TOOM3_THRESHOLD: usize = 128;
+
+/// Below this many coefficients, multiplication over a prime field doesn't use the
+/// number-theoretic transform.
+const NTT_THRESHOLD: usize = 128;
+
+/// Below this many coefficients, multiplication over ℤ doesn't use the multi-modular method.
+const MULTIMODULAR_THRESHOLD: usize = 2048;
+
+/// Below this degree, the half-gcd algorithm falls back to Euclid's algorithm.
+const HALF_GCD_THRESHOLD: usize = 64;
+
+/// A 2 × 2 matrix of polynomials, used to record steps of Euclid's algorithm.
+type Matrix<R> = [[Poly<R>; 2]; 2];
+
+/// Adds `src`, shifted up by `offset` places, into `acc`.
+fn add_at<R: Ring + Clone>(acc: &mut Vec<R>, src: &[R], offset: usize) {
+    if acc.len() < src.len() + offset {
+        acc.resize(src.len() + offset, R::ZERO);
+    }
+    for (a, b) in acc[offset..].iter_mut().zip(src) {
+        *a = a.add(b.clone());
+    }
+}
+
+/// Subtracts `src`, shifted up by `offset` places, from `acc`.
+fn sub_at<R: Ring + Clone>(acc: &mut Vec<R>, src: &[R], offset: usize) {
+    if acc.len() < src.len() + offset {
+        acc.resize(src.len() + offset, R::ZERO);
+    }
+    for (a, b) in acc[offset..].iter_mut().zip(src) {
+        *a = a.sub(b.clone());
+    }
+}
+
+/// The sum of two coefficient vectors.
+fn sum<R: Ring + Clone>(a: &[R], b: &[R]) -> Vec<R> {
+    let mut result = a.to_vec();
+    add_at(&mut result, b, 0);
+    result
+}
+
+/// The difference of two coefficient vectors.
+fn difference<R: Ring + Clone>(a: &[R], b: &[R]) -> Vec<R> {
+    let mut result = a.to_vec();
+    sub_at(&mut result, b, 0);
+    result
+}
+
+/// Splits `a` into the parts below and above `at`.
+fn split<R>(a: &[R], at: usize) -> (&[R], &[R]) {
+    a.split_at(at.min(a.len()))
+}
+
+/// The product of two coefficient vectors by the schoolbook method. Either may be empty.
+fn schoolbook<R: Ring + Clone>(a: &[R], b: &[R]) -> Vec<R> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![R::ZERO; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (y, p) in b.iter().zip(&mut product[i..]) {
+            *p = p.add(x.mul(y.clone()));
+        }
+    }
+    product
+}
+
+/// The product of two coefficient vectors by Karatsuba's method. This is how
+/// [`CommutativeRing::mul_polynomials`] multiplies unless a ring has something better.
+///
+/// Writing *a* = *a*₀ + *a*₁*xʰ* and *b* = *b*₀ + *b*₁*xʰ*, the middle coefficient
+/// *a*₀*b*₁ + *a*₁*b*₀ is (*a*₀ + *a*₁)(*b*₀ + *b*₁) - *a*₀*b*₀ - *a*₁*b*₁, so three half-size
+/// products are enough rather than four.
+pub(crate) fn karatsuba<R: Ring + Clone>(a: &[R], b: &[R]) -> Vec<R> {
+    if a.len().min(b.len()) <= KARATSUBA_THRESHOLD {
+        return schoolbook(a, b);
+    }
+    let h = a.len().max(b.len()) / 2;
+    let ((a0, a1), (b0, b1)) = (split(a, h), split(b, h));
+    let low = karatsuba(a0, b0);
+    let high = karatsuba(a1, b1);
+    let mut middle = karatsuba(&sum(a0, a1), &sum(b0, b1));
+    sub_at(&mut middle, &low, 0);
+    sub_at(&mut middle, &high, 0);
+    let mut product = low;
+    add_at(&mut product, &middle, h);
+    add_at(&mut product, &high, 2 * h);
+    product.truncate(a.len() + b.len() - 1);
+    product
+}
+
+/// The product of two coefficient vectors by the Toom–Cook method, splitting into three parts.
+///
+/// The parts are evaluated at 0, 1, -1, -2 and ∞, multiplied there, and interpolated using
+/// Bodrato's sequence. The interpolation divides by 2 and 3, with `halve` and `third`, but only
+/// ever when the division is exact. So this works over ℤ with integer division as well as over a
+/// field whose characteristic isn't 2 or 3.
+fn toom3<R, H, T>(a: &[R], b: &[R], halve: &H, third: &T) -> Vec<R>
+where
+    R: Ring + Clone,
+    H: Fn(&R) -> R,
+    T: Fn(&R) -> R,
+{
+    if a.len().min(b.len()) <= TOOM3_THRESHOLD {
+        return karatsuba(a, b);
+    }
+    let k = a.len().max(b.len()).div_ceil(3);
+    let evaluate = |p: &[R]| {
+        let (m0, rest) = split(p, k);
+        let (m1, m2) = split(rest, k);
+        let p0 = sum(m0, m2);
+        let at_one = sum(&p0, m1);
+        let at_minus_one = difference(&p0, m1);
+        let doubled = sum(&at_minus_one, m2);
+        let at_minus_two = difference(&sum(&doubled, &doubled), m0);
+        [m0.to_vec(), at_one, at_minus_one, at_minus_two, m2.to_vec()]
+    };
+    let (ea, eb) = (evaluate(a), evaluate(b));
+    let [r0, r1, rm1, rm2, rinf]: [Vec<R>; 5] =
+        std::array::from_fn(|i| toom3(&ea[i], &eb[i], halve, third));
+    let divide = |v: &[R], by: &dyn Fn(&R) -> R| -> Vec<R> { v.iter().map(by).collect() };
+    let r3 = divide(&difference(&rm2, &r1), third);
+    let r1 = divide(&difference(&r1, &rm1), halve);
+    let r2 = difference(&rm1, &r0);
+    let r3 = sum(&divide(&difference(&r2, &r3), halve), &sum(&rinf, &rinf));
+    let r2 = difference(&sum(&r2, &r1), &rinf);
+    let r1 = difference(&r1, &r3);
+    let mut product = r0;
+    for (i, part) in [r1, r2, r3, rinf].iter().enumerate() {
+        add_at(&mut product, part, (i + 1) * k);
+    }
+    product.truncate(a.len() + b.len() - 1);
+    product
+}
+
+/// The product of two coefficient vectors over a field, by Toom-3 if we can divide by 2 and 3, and
+/// Karatsuba if not.
+pub(crate) fn field_product<R: Field + Clone>(a: &[R], b: &[R]) -> Vec<R> {
+    let (two, three) = (multiple(&R::ONE, 2), multiple(&R::ONE, 3));
+    match (two.inverse_mul(), three.inverse_mul()) {
+        (Some(half), Some(third)) => toom3(a, b, &|x: &R| x.mul(half.clone()), &|x: &R| {
+            x.mul(third.clone())
+        }),
+        _ => karatsuba(a, b),
+    }
+}
+
+/// The product of two coefficient vectors over ℤ, by Toom-3 with exact division.
+fn integer_toom3(a: &[i128], b: &[i128]) -> Vec<i128> {
+    toom3(a, b, &|x: &i128| x / 2, &|x: &i128| x / 3)
+}
+
+/// The in-place number-theoretic transform of `a`, whose length is a power of two *n*, where
+/// `root` is a primitive *n*-th root of unity.
+fn ntt<const P: u64>(a: &mut [PrimeField<P>], root: PrimeField<P>) {
+    let n = a.len();
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let step = root.pow((n / len) as u64);
+        for block in a.chunks_mut(len) {
+            let (low, high) = block.split_at_mut(len / 2);
+            let mut w = PrimeField::<P>::ONE;
+            for (u, v) in low.iter_mut().zip(high) {
+                let t = *v * w;
+                (*u, *v) = (*u + t, *u - t);
+                w *= step;
+            }
+        }
+        len *= 2;
+    }
+}
+
+/// The product of two coefficient vectors over 𝔽*ₚ* using the number-theoretic transform, or
+/// [`None`] if 𝔽*ₚ* doesn't have the roots of unity we need.
+fn ntt_product<const P: u64>(
+    a: &[PrimeField<P>],
+    b: &[PrimeField<P>],
+) -> Option<Vec<PrimeField<P>>> {
+    if a.is_empty() || b.is_empty() {
+        return Some(Vec::new());
+    }
+    let length = a.len() + b.len() - 1;
+    let n = length.next_power_of_two();
+    let root = PrimeField::<P>::root_of_unity(n as u64)?;
+    let (mut fa, mut fb) = (a.to_vec(), b.to_vec());
+    fa.resize(n, PrimeField::ZERO);
+    fb.resize(n, PrimeField::ZERO);
+    ntt(&mut fa, root);
+    ntt(&mut fb, root);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x *= *y;
+    }
+    let inverse = root.recip().expect("roots of unity are nonzero");
+    ntt(&mut fa, inverse);
+    let scale = PrimeField::<P>::new(n as i128)
+        .recip()
+        .expect("n divides p - 1, so it's nonzero mod p");
+    fa.truncate(length);
+    Some(fa.iter().map(|&x| x * scale).collect())
+}
+
+/// The product of two coefficient vectors over 𝔽*ₚ*, using the number-theoretic transform if
+/// they're long enough and 𝔽*ₚ* has the roots of unity it needs, and [`field_product`] if not.
+pub(crate) fn prime_field_product<const P: u64>(
+    a: &[PrimeField<P>],
+    b: &[PrimeField<P>],
+) -> Vec<PrimeField<P>> {
+    if a.len().min(b.len()) > NTT_THRESHOLD {
+        if let Some(product) = ntt_product(a, b) {
+            return product;
+        }
+    }
+    field_product(a, b)
+}
+
+/// The product of two coefficient vectors over ℤ, using the multi-modular method if they're long
+/// enough and the primes we have can pin down the coefficients of the product, and Toom-3 if not.
+pub(crate) fn integer_product(a: &[i128], b: &[i128]) -> Vec<i128> {
+    if a.len().min(b.len()) > MULTIMODULAR_THRESHOLD {
+        if let Some(product) = multimodular(a, b) {
+            return product;
+        }
+    }
+    integer_toom3(a, b)
+}
+
+/// The product of `a` and `b` modulo the prime `P`, as representatives in 0, 1, …, `P` - 1.
+fn residues<const P: u64>(a: &[i128], b: &[i128]) -> Vec<u64> {
+    let reduce =
+        |v: &[i128]| -> Vec<PrimeField<P>> { v.iter().map(|&c| PrimeField::new(c)).collect() };
+    let (a, b) = (reduce(a), reduce(b));
+    ntt_product(&a, &b)
+        .unwrap_or_else(|| karatsuba(&a, &b))
+        .iter()
+        .map(PrimeField::value)
+        .collect()
+}
+
+/// Primes of the form *c* ⋅ 2*ᵏ* + 1 with large *k*, for multi-modular multiplication. Their
+/// product is about 2¹¹⁵.
+const NTT_PRIMES: [u64; 4] = [998244353, 167772161, 469762049, 754974721];
+
+/// A function computing the product of two integer polynomials modulo some fixed prime.
+type Residues = fn(&[i128], &[i128]) -> Vec<u64>;
+
+/// The products modulo each of [`NTT_PRIMES`].
+const NTT_RESIDUES: [Residues; 4] = [
+    residues::<998244353>,
+    residues::<167772161>,
+    residues::<469762049>,
+    residues::<754974721>,
+];
+
+/// The product of two coefficient vectors over ℤ by the multi-modular method, or [`None`] if the
+/// primes we have aren't enough to pin down its coefficients. See [`Poly::mul_multimodular`].
+fn multimodular(a: &[i128], b: &[i128]) -> Option<Vec<i128>> {
+    let height = |v: &[i128]| v.iter().map(|c| c.unsigned_abs()).max().unwrap_or(0);
+    let bound = height(a)
+        .saturating_mul(height(b))
+        .saturating_mul(a.len().min(b.len()) as u128);
+    let mut modulus: u128 = 1;
+    let mut count = 0;
+    while count < NTT_PRIMES.len() && modulus / 2 <= bound {
+        modulus *= u128::from(NTT_PRIMES[count]);
+        count += 1;
+    }
+    if modulus / 2 <= bound {
+        return None;
+    }
+    let residues: Vec<Vec<u64>> = NTT_RESIDUES[..count].iter().map(|f| f(a, b)).collect();
+    let length = residues[0].len();
+    Some(
+        (0..length)
+            .map(|i| {
+                // Garner's algorithm: add on a multiple of the modulus so far to fix the
+                // residue modulo the next prime.
+                let (mut value, mut modulus): (u128, u128) = (0, 1);
+                for (r, &p) in residues.iter().zip(&NTT_PRIMES) {
+                    let p = u128::from(p);
+                    let correction = mul_mod(
+                        (u128::from(r[i]) + p - value % p) % p,
+                        inverse_mod(modulus % p, p),
+                        p,
+                    );
+                    value += correction * modulus;
+                    modulus *= p;
+                }
+                if value > modulus / 2 {
+                    value as i128 - modulus as i128
+                } else {
+                    value as i128
+                }
+            })
+            .collect(),
+    )
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> Poly<R> {
+    /// Creates the polynomial with the given coefficients, lowest degree first.
+    pub fn new(coefficients: Vec<R>) -> Self {
+        let mut coefficients = coefficients.into_iter();
+        let constant = coefficients.next().unwrap_or(R::ZERO);
+        let mut coefficients: Vec<R> = coefficients.collect();
+        while coefficients.last().is_some_and(|c| *c == R::ZERO) {
+            coefficients.pop();
+        }
+        Poly {
+            constant,
+            coefficients,
+        }
+    }
+
+    /// The constant polynomial `c`.
+    pub fn constant(c: R) -> Self {
+        Poly {
+            constant: c,
+            coefficients: Vec::new(),
+        }
+    }
+
+    /// The polynomial *x*.
+    pub fn x() -> Self {
+        Poly::monomial(R::ONE, 1)
+    }
+
+    /// The monomial *cxⁿ*.
+    pub fn monomial(c: R, n: usize) -> Self {
+        let mut coefficients = vec![R::ZERO; n];
+        coefficients.push(c);
+        Poly::new(coefficients)
+    }
+
+    /// Whether this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty() && self.constant == R::ZERO
+    }
+
+    /// The degree, or [`None`] for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self.coefficients.len())
+        }
+    }
+
+    /// The coefficient of *xⁱ*.
+    pub fn coefficient(&self, i: usize) -> R {
+        match i {
+            0 => self.constant.clone(),
+            _ => self.coefficients.get(i - 1).cloned().unwrap_or(R::ZERO),
+        }
+    }
+
+    /// The coefficients, lowest degree first. This is empty for the zero polynomial, and otherwise
+    /// its last entry is nonzero.
+    pub fn coefficients(&self) -> Vec<R> {
+        if self.is_zero() {
+            return Vec::new();
+        }
+        let mut dense = Vec::with_capacity(self.coefficients.len() + 1);
+        dense.push(self.constant.clone());
+        dense.extend(self.coefficients.iter().cloned());
+        dense
+    }
+
+    /// The coefficient of the highest power of *x*, or zero for the zero polynomial.
+    pub fn leading_coefficient(&self) -> R {
+        self.coefficients.last().unwrap_or(&self.constant).clone()
+    }
+
+    /// Whether the leading coefficient is 1.
+    pub fn is_monic(&self) -> bool {
+        self.leading_coefficient() == R::ONE
+    }
+
+    /// Evaluates the polynomial at `x` using Horner's method.
+    pub fn evaluate(&self, x: &R) -> R {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(R::ZERO, |acc, c| acc.mul(x.clone()).add(c.clone()))
+            .mul(x.clone())
+            .add(self.constant.clone())
+    }
+
+    /// The composition *f*(*g*(*x*)), where *f* is `self`, using Horner's method.
+    pub fn compose(&self, g: &Self) -> Self {
+        self.coefficients()
+            .into_iter()
+            .rev()
+            .fold(Poly::ZERO, |acc, c| &(&acc * g) + &Poly::constant(c))
+    }
+
+    /// The formal derivative.
+    pub fn derivative(&self) -> Self {
+        Poly::new(
+            self.coefficients
+                .iter()
+                .enumerate()
+                .map(|(i, c)| multiple(c, i + 1))
+                .collect(),
+        )
+    }
+
+    /// Multiplies every coefficient by `c`.
+    pub fn scale(&self, c: &R) -> Self {
+        Poly::new(
+            self.coefficients()
+                .into_iter()
+                .map(|a| a.mul(c.clone()))
+                .collect(),
+        )
+    }
+
+    /// Raises `self` to the power `exponent` by repeated squaring.
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut result = Poly::ONE;
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exponent /= 2;
+        }
+        result
+    }
+
+    /// Pseudo-division, which divides with remainder without needing to invert coefficients.
+    ///
+    /// If `self` has degree *m*, `divisor` has degree *n* ≤ *m* and leading coefficient *l*, this
+    /// returns *q* and *r* with *l*^(*m* - *n* + 1) `self` = *q* `divisor` + *r*, where *r* has
+    /// degree less than *n*. If *m* < *n*, it returns 0 and `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::polynomial::Poly;
+    ///
+    /// let f = Poly::new(vec![1_i64, 0, 1]);
+    /// let g = Poly::new(vec![1, 2]);
+    /// let (q, r) = f.pseudo_div_rem(&g);
+    /// // 4(x² + 1) = (2x - 1)(2x + 1) + 5
+    /// assert_eq!(q, Poly::new(vec![-1, 2]));
+    /// assert_eq!(r, Poly::constant(5));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn pseudo_div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let n = divisor.degree().expect("division by zero");
+        let Some(m) = self.degree().filter(|&m| m >= n) else {
+            return (Poly::ZERO, self.clone());
+        };
+        let lead = divisor.leading_coefficient();
+        let divisor = divisor.coefficients();
+        let mut remainder = self.coefficients();
+        // powers[i] is lⁱ.
+        let mut powers = vec![R::ONE];
+        for _ in n..m {
+            let next = powers[powers.len() - 1].mul(lead.clone());
+            powers.push(next);
+        }
+        // The step which cancels the coefficient of x^(k + n) multiplies everything by l first.
+        // We only scale the n coefficients it changes, and catch up with the others as they come
+        // into reach, so that remainder[k] has been scaled by l once for each earlier step.
+        let mut quotient = vec![R::ZERO; m - n + 1];
+        for (step, k) in (0..=m - n).rev().enumerate() {
+            remainder[k] = remainder[k].mul(powers[step].clone());
+            let c = remainder[k + n].clone();
+            for (r, d) in remainder[k..k + n].iter_mut().zip(&divisor) {
+                *r = r.mul(lead.clone()).sub(c.mul(d.clone()));
+            }
+            // The k steps after this one each multiply the quotient by l.
+            quotient[k] = c.mul(powers[k].clone());
+        }
+        remainder.truncate(n);
+        (Poly::new(quotient), Poly::new(remainder))
+    }
+
+    fn plus(&self, rhs: &Self) -> Self {
+        let (long, short) = if self.coefficients.len() >= rhs.coefficients.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let mut coefficients = long.coefficients.clone();
+        for (a, b) in coefficients.iter_mut().zip(&short.coefficients) {
+            *a = a.add(b.clone());
+        }
+        while coefficients.last().is_some_and(|c| *c == R::ZERO) {
+            coefficients.pop();
+        }
+        Poly {
+            constant: self.constant.add(rhs.constant.clone()),
+            coefficients,
+        }
+    }
+
+    fn negated(&self) -> Self {
+        Poly {
+            constant: self.constant.inverse_add(),
+            coefficients: self.coefficients.iter().map(Ring::inverse_add).collect(),
+        }
+    }
+
+    fn times(&self, rhs: &Self) -> Self {
+        if self.is_zero() || rhs.is_zero() {
+            return Poly::ZERO;
+        }
+        Poly::new(R::mul_polynomials(
+            &self.coefficients(),
+            &rhs.coefficients(),
+        ))
+    }
+
+    /// Multiplies by the schoolbook method, which takes quadratic time.
+    pub fn mul_schoolbook(&self, rhs: &Self) -> Self {
+        Poly::new(schoolbook(&self.coefficients(), &rhs.coefficients()))
+    }
+
+    /// Multiplies by Karatsuba's method, which takes *O*(*n*^1.585) time. This is what `*` uses
+    /// unless [`CommutativeRing::mul_polynomials`] knows something better for *R*.
+    pub fn mul_karatsuba(&self, rhs: &Self) -> Self {
+        Poly::new(karatsuba(&self.coefficients(), &rhs.coefficients()))
+    }
+
+    /// The remainder modulo *xⁿ*, i.e. the terms of degree less than *n*.
+    pub fn truncate(&self, n: usize) -> Self {
+        let mut coefficients = self.coefficients();
+        coefficients.truncate(n);
+        Poly::new(coefficients)
+    }
+
+    /// The quotient by *xⁿ*, i.e. dropping the terms of degree less than *n*.
+    pub fn shift_down(&self, n: usize) -> Self {
+        Poly::new(self.coefficients().into_iter().skip(n).collect())
+    }
+
+    /// The reversal *x*^`degree` *f*(1/*x*), where `degree` is at least the degree of *f*.
+    fn reversed(&self, degree: usize) -> Self {
+        let mut coefficients = self.coefficients();
+        coefficients.resize(degree + 1, R::ZERO);
+        coefficients.reverse();
+        Poly::new(coefficients)
+    }
+}
+
+impl<R: Field + Clone + PartialEq> Poly<R> {
+    /// Divides by the leading coefficient. The zero polynomial is left alone.
+    pub fn monic(&self) -> Self {
+        if self.is_zero() {
+            return Poly::ZERO;
+        }
+        self.scale(&R::ONE.div(self.leading_coefficient()))
+    }
+
+    /// Raises `self` to the power `exponent` modulo `modulus`, by repeated squaring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn pow_mod(&self, mut exponent: u128, modulus: &Self) -> Self {
+        let mut result = Poly::ONE.div_rem(modulus).1;
+        let mut base = self.div_rem(modulus).1;
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = (&result * &base).div_rem(modulus).1;
+            }
+            base = (&base * &base).div_rem(modulus).1;
+            exponent /= 2;
+        }
+        result
+    }
+
+    /// Long division, which returns the quotient *q* and remainder *r* with `self` = *q* `divisor`
+    /// + *r*, where *r* has smaller degree than `divisor`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let n = divisor.degree().expect("division by zero");
+        let Some(m) = self.degree().filter(|&m| m >= n) else {
+            return (Poly::ZERO, self.clone());
+        };
+        let lead = divisor.leading_coefficient();
+        let divisor = divisor.coefficients();
+        let mut remainder = self.coefficients();
+        let mut quotient = vec![R::ZERO; m - n + 1];
+        for k in (0..=m - n).rev() {
+            let c = remainder[k + n].div(lead.clone());
+            if c != R::ZERO {
+                for (r, d) in remainder[k..k + n].iter_mut().zip(&divisor) {
+                    *r = r.sub(c.mul(d.clone()));
+                }
+            }
+            quotient[k] = c;
+        }
+        remainder.truncate(n);
+        (Poly::new(quotient), Poly::new(remainder))
+    }
+
+    /// The extended Euclidean algorithm. This returns the monic gcd *g* of `self` and `other`,
+    /// along with *s* and *t* such that *s* `self` + *t* `other` = *g*.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::{polynomial::Poly, rational::Rational};
+    ///
+    /// let q = |n: i128| Rational::from(n);
+    /// let f = Poly::new(vec![q(1), q(0), q(1)]);
+    /// let g = Poly::new(vec![q(0), q(1)]);
+    /// let (gcd, s, t) = f.extended_gcd(&g);
+    /// assert_eq!(gcd, Poly::constant(q(1)));
+    /// assert_eq!(&(&s * &f) + &(&t * &g), gcd);
+    /// ```
+    pub fn extended_gcd(&self, other: &Self) -> (Self, Self, Self) {
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut s0, mut s1) = (Poly::ONE, Poly::ZERO);
+        let (mut t0, mut t1) = (Poly::ZERO, Poly::ONE);
+        while !r1.is_zero() {
+            let (quotient, remainder) = r0.div_rem(&r1);
+            let s2 = &s0 - &(&quotient * &s1);
+            let t2 = &t0 - &(&quotient * &t1);
+            (r0, r1) = (r1, remainder);
+            (s0, s1) = (s1, s2);
+            (t0, t1) = (t1, t2);
+        }
+        if r0.is_zero() {
+            return (r0, s0, t0);
+        }
+        let unit = R::ONE.div(r0.leading_coefficient());
+        (r0.scale(&unit), s0.scale(&unit), t0.scale(&unit))
+    }
+}
+
+impl<R: Field + Clone + PartialEq> Poly<R> {
+    /// Multiplies by the Toom–Cook method with three parts, which takes *O*(*n*^1.465) time.
+    ///
+    /// This needs to divide by 2 and 3, so over fields of characteristic 2 or 3 it uses
+    /// [`Poly::mul_karatsuba`] instead.
+    pub fn mul_toom3(&self, rhs: &Self) -> Self {
+        Poly::new(field_product(&self.coefficients(), &rhs.coefficients()))
+    }
+
+    /// The inverse of `self` as a power series, modulo *xⁿ*.
+    ///
+    /// This uses Newton iteration, *g* ↦ *g*(2 - *fg*), which doubles the number of correct
+    /// coefficients each time, so it costs about as much as a few multiplications.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::{polynomial::Poly, rational::Rational};
+    ///
+    /// // 1/(1 - x) = 1 + x + x² + ⋯
+    /// let f = Poly::new(vec![Rational::from(1), Rational::from(-1)]);
+    /// assert_eq!(f.series_inverse(4), Poly::new(vec![Rational::from(1); 4]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constant coefficient is zero.
+    pub fn series_inverse(&self, n: usize) -> Self {
+        let constant = self
+            .constant
+            .inverse_mul()
+            .expect("a power series needs a nonzero constant term to be invertible");
+        let two = Poly::constant(multiple(&R::ONE, 2));
+        let mut inverse = Poly::constant(constant).truncate(n);
+        let mut precision = 1;
+        while precision < n {
+            precision = (2 * precision).min(n);
+            let error = &self.truncate(precision) * &inverse;
+            inverse = (&inverse * &(&two - &error)).truncate(precision);
+        }
+        inverse
+    }
+
+    /// The same as [`Poly::div_rem`], but computing the quotient with [`Poly::series_inverse`].
+    ///
+    /// Reversing the coefficients turns division into multiplying by a power series inverse, so
+    /// this takes about as long as a multiplication, rather than quadratic time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem_newton(&self, divisor: &Self) -> (Self, Self) {
+        let n = divisor.degree().expect("division by zero");
+        let Some(m) = self.degree().filter(|&m| m >= n) else {
+            return (Poly::ZERO, self.clone());
+        };
+        let k = m - n + 1;
+        let inverse = divisor.reversed(n).series_inverse(k);
+        let quotient = (&self.reversed(m) * &inverse).truncate(k).reversed(k - 1);
+        let remainder = self - &(&quotient * divisor);
+        (quotient, remainder)
+    }
+
+    /// The monic gcd, computed with the half-gcd algorithm.
+    ///
+    /// The quotients at the start of Euclid's algorithm only depend on the leading coefficients,
+    /// so we can find the first half of them recursively from the top halves of the polynomials.
+    /// Together with fast multiplication, this takes *O*(*M*(*n*) log *n*) time, where *M*(*n*) is
+    /// the cost of a multiplication, rather than the quadratic time of [`GCDDomain::gcd`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::{finite_field::PrimeField, polynomial::Poly, GCDDomain};
+    ///
+    /// type F = PrimeField<101>;
+    /// let f = Poly::new((0..200).map(|i| F::new(i * i + 1)).collect());
+    /// let g = Poly::new((0..150).map(|i| F::new(3 * i + 7)).collect());
+    /// let h = Poly::new((0..90).map(|i| F::new(i ^ 5)).collect());
+    /// assert_eq!((&f * &h).fast_gcd(&(&g * &h)), (&f * &h).gcd(&g * &h));
+    /// ```
+    pub fn fast_gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        if a.degree() < b.degree() {
+            (a, b) = (b, a);
+        }
+        while !b.is_zero() {
+            let remainder = a.div_rem_newton(&b).1;
+            (a, b) = (b, remainder);
+            if a.degree().is_some_and(|d| d >= HALF_GCD_THRESHOLD) && !b.is_zero() {
+                let matrix = Poly::half_gcd(&a, &b);
+                (a, b) = Poly::apply(&matrix, &a, &b);
+            }
+        }
+        a.monic()
+    }
+
+    /// A matrix of Euclid's algorithm steps which takes `a` and `b` to consecutive remainders
+    /// *c* and *d* with deg *c* ≥ ⌈deg `a` / 2⌉ > deg *d*. We need deg `a` > deg `b`.
+    fn half_gcd(a: &Self, b: &Self) -> Matrix<R> {
+        let identity = [[Poly::ONE, Poly::ZERO], [Poly::ZERO, Poly::ONE]];
+        let m = a.degree().expect("a has larger degree than b").div_ceil(2);
+        if b.degree().is_none_or(|d| d < m) {
+            return identity;
+        }
+        let first = Poly::half_gcd(&a.shift_down(m), &b.shift_down(m));
+        let (c, d) = Poly::apply(&first, a, b);
+        if d.degree().is_none_or(|e| e < m) {
+            return first;
+        }
+        let (quotient, remainder) = c.div_rem_newton(&d);
+        let step = [[Poly::ZERO, Poly::ONE], [Poly::ONE, -quotient]];
+        let k = 2 * m - d.degree().expect("d is nonzero");
+        let second = Poly::half_gcd(&d.shift_down(k), &remainder.shift_down(k));
+        Poly::mat_mul(&second, &Poly::mat_mul(&step, &first))
+    }
+
+    /// Applies the matrix to the column vector (`a`, `b`).
+    fn apply(matrix: &Matrix<R>, a: &Self, b: &Self) -> (Self, Self) {
+        let [[p, q], [r, s]] = matrix;
+        (&(p * a) + &(q * b), &(r * a) + &(s * b))
+    }
+
+    fn mat_mul(x: &Matrix<R>, y: &Matrix<R>) -> Matrix<R> {
+        std::array::from_fn(|i| {
+            std::array::from_fn(|j| &(&x[i][0] * &y[0][j]) + &(&x[i][1] * &y[1][j]))
+        })
+    }
+}
+
+impl<const P: u64> Poly<PrimeField<P>> {
+    /// Multiplies using the number-theoretic transform, which takes *O*(*n* log *n*) time.
+    ///
+    /// This is the discrete Fourier transform over 𝔽*ₚ*, so it needs a primitive 2*ᵏ*-th root of
+    /// unity, where 2*ᵏ* is at least the length of the product. If *p* - 1 isn't divisible by
+    /// 2*ᵏ*, it uses [`Poly::mul_karatsuba`] instead. Primes like 998244353 = 119 ⋅ 2²³ + 1 support
+    /// products of degree up to 2²³ - 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::{finite_field::PrimeField, polynomial::Poly};
+    ///
+    /// type F = PrimeField<998244353>;
+    /// let f = Poly::new((0..1000).map(|i| F::new(i)).collect());
+    /// let g = Poly::new((0..700).map(|i| F::new(i * i)).collect());
+    /// assert_eq!(f.mul_ntt(&g), f.mul_schoolbook(&g));
+    /// ```
+    pub fn mul_ntt(&self, rhs: &Self) -> Self {
+        let (a, b) = (self.coefficients(), rhs.coefficients());
+        Poly::new(ntt_product(&a, &b).unwrap_or_else(|| karatsuba(&a, &b)))
+    }
+}
+
+impl Poly<i128> {
+    /// Multiplies by reducing modulo several NTT-friendly primes, multiplying there with
+    /// [`Poly::mul_ntt`], and putting the results back together with the Chinese remainder
+    /// theorem.
+    ///
+    /// We use just enough primes for their product to be more than twice the largest possible
+    /// coefficient of the product, so the result is exact. If even all the primes aren't enough,
+    /// then the coefficients might not fit in an [`i128`], so we use [`Poly::mul_karatsuba`], which
+    /// panics on overflow.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::polynomial::Poly;
+    ///
+    /// let f = Poly::new((0..500).map(|i: i128| (i - 250) * 1_000_000_007).collect());
+    /// let g = Poly::new((0..300).map(|i: i128| i * i * i - 12345).collect());
+    /// assert_eq!(f.mul_multimodular(&g), f.mul_karatsuba(&g));
+    /// ```
+    pub fn mul_multimodular(&self, rhs: &Self) -> Self {
+        let (a, b) = (self.coefficients(), rhs.coefficients());
+        Poly::new(multimodular(&a, &b).unwrap_or_else(|| karatsuba(&a, &b)))
+    }
+
+    /// Multiplies by the Toom–Cook method with three parts, like [`Poly::mul_toom3`] over a
+    /// field. The divisions by 2 and 3 in the interpolation are always exact, so we can do them
+    /// in ℤ.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::polynomial::Poly;
+    ///
+    /// let f = Poly::new((0..400).map(|i: i128| i * i - 1000).collect());
+    /// let g = Poly::new((0..300).map(|i: i128| 7 - 3 * i).collect());
+    /// assert_eq!(f.mul_toom3(&g), f.mul_schoolbook(&g));
+    /// ```
+    pub fn mul_toom3(&self, rhs: &Self) -> Self {
+        Poly::new(integer_toom3(&self.coefficients(), &rhs.coefficients()))
+    }
+}
+
+impl<R: GCDDomain + Clone + PartialEq> Poly<R> {
+    /// The content, the gcd of all the coefficients. It's only defined up to a unit.
+    pub fn content(&self) -> R {
+        self.coefficients()
+            .into_iter()
+            .fold(R::ZERO, |acc, c| acc.gcd(c))
+    }
+
+    /// The primitive part, `self` divided by its [`Poly::content`]. Its coefficients have no
+    /// common factor other than units.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::polynomial::Poly;
+    ///
+    /// let f = Poly::new(vec![6_i64, -4, 2]);
+    /// assert_eq!(f.content(), 2);
+    /// assert_eq!(f.primitive_part(), Poly::new(vec![3, -2, 1]));
+    /// ```
+    pub fn primitive_part(&self) -> Self
+    where
+        R: EuclideanDomain,
+    {
+        if self.is_zero() {
+            return Poly::ZERO;
+        }
+        let content = self.content();
+        Poly::new(
+            self.coefficients()
+                .into_iter()
+                .map(|c| c.quo(content.clone()))
+                .collect(),
+        )
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> Default for Poly<R> {
+    fn default() -> Self {
+        Poly::ZERO
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq + fmt::Display> fmt::Display for Poly<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut first = true;
+        for (i, c) in self.coefficients().into_iter().enumerate().rev() {
+            if c == R::ZERO {
+                continue;
+            }
+            let mut coefficient = c.to_string();
+            if coefficient.contains(' ') {
+                coefficient = format!("({coefficient})");
+            }
+            let (negative, magnitude) = match coefficient.strip_prefix('-') {
+                Some(magnitude) => (true, magnitude),
+                None => (false, coefficient.as_str()),
+            };
+            match (first, negative) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            first = false;
+            match (i, magnitude == "1") {
+                (0, _) => write!(f, "{magnitude}")?,
+                (_, true) => write!(f, "x")?,
+                (_, false) => write!(f, "{magnitude}*x")?,
+            }
+            if i > 1 {
+                write!(f, "^{i}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> std::ops::Add<&Poly<R>> for &Poly<R> {
+    type Output = Poly<R>;
+
+    fn add(self, rhs: &Poly<R>) -> Self::Output {
+        self.plus(rhs)
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> std::ops::Add for Poly<R> {
+    type Output = Poly<R>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.plus(&rhs)
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> std::ops::Sub<&Poly<R>> for &Poly<R> {
+    type Output = Poly<R>;
+
+    fn sub(self, rhs: &Poly<R>) -> Self::Output {
+        self.plus(&rhs.negated())
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> std::ops::Sub for Poly<R> {
+    type Output = Poly<R>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.plus(&rhs.negated())
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> std::ops::Mul<&Poly<R>> for &Poly<R> {
+    type Output = Poly<R>;
+
+    fn mul(self, rhs: &Poly<R>) -> Self::Output {
+        self.times(rhs)
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> std::ops::Mul for Poly<R> {
+    type Output = Poly<R>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.times(&rhs)
+    }
+}
+
+impl<R: Field + Clone + PartialEq> std::ops::Div for Poly<R> {
+    type Output = Poly<R>;
+
+    /// The quotient of [`Poly::div_rem`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_rem(&rhs).0
+    }
+}
+
+impl<R: Field + Clone + PartialEq> std::ops::Rem for Poly<R> {
+    type Output = Poly<R>;
+
+    /// The remainder of [`Poly::div_rem`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.div_rem(&rhs).1
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> std::ops::Neg for &Poly<R> {
+    type Output = Poly<R>;
+
+    fn neg(self) -> Self::Output {
+        self.negated()
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> std::ops::Neg for Poly<R> {
+    type Output = Poly<R>;
+
+    fn neg(self) -> Self::Output {
+        self.negated()
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> BinaryOperator<Plus> for Poly<R> {
+    fn op(&self, rhs: Self) -> Self {
+        self.plus(&rhs)
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.plus(&rhs);
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> BinaryOperator<Times> for Poly<R> {
+    fn op(&self, rhs: Self) -> Self {
+        self.times(&rhs)
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self = self.times(&rhs);
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> Magma<Plus> for Poly<R> {}
+impl<R: CommutativeRing + Clone + PartialEq> Semigroup<Plus> for Poly<R> {}
+impl<R: CommutativeRing + Clone + PartialEq> UnitalMagma<Plus> for Poly<R> {
+    const IDENTITY: Self = Poly::ZERO;
+}
+impl<R: CommutativeRing + Clone + PartialEq> Quasigroup<Plus> for Poly<R> {
+    fn inverse(&self) -> Self {
+        self.negated()
+    }
+}
+impl<R: CommutativeRing + Clone + PartialEq> CommutativeMagma<Plus> for Poly<R> {}
+impl<R: CommutativeRing + Clone + PartialEq> AbelianGroup<Plus> for Poly<R> {}
+
+impl<R: CommutativeRing + Clone + PartialEq> Magma<Times> for Poly<R> {}
+impl<R: CommutativeRing + Clone + PartialEq> Semigroup<Times> for Poly<R> {}
+impl<R: CommutativeRing + Clone + PartialEq> UnitalMagma<Times> for Poly<R> {
+    const IDENTITY: Self = Poly::ONE;
+}
+impl<R: CommutativeRing + Clone + PartialEq> CommutativeMagma<Times> for Poly<R> {}
+impl<R: CommutativeRing + Clone + PartialEq> Monoid<Times> for Poly<R> {}
+
+impl<R: CommutativeRing + Clone + PartialEq> Ring for Poly<R> {
+    const ZERO: Self = Poly {
+        constant: R::ZERO,
+        coefficients: Vec::new(),
+    };
+    const ONE: Self = Poly {
+        constant: R::ONE,
+        coefficients: Vec::new(),
+    };
+
+    fn left_inverse_mul(&self) -> Option<Self> {
+        self.inverse_mul()
+    }
+
+    fn right_inverse_mul(&self) -> Option<Self> {
+        self.inverse_mul()
+    }
+
+    /// The inverse of a unit of *R*, as a constant polynomial.
+    ///
+    /// When *R* has nilpotent elements there are nonconstant units too, like 1 + 2*x* over ℤ/4ℤ,
+    /// but we don't look for these. Over an integral domain, the constant units are all of them.
+    fn inverse_mul(&self) -> Option<Self> {
+        if self.coefficients.is_empty() {
+            self.constant.inverse_mul().map(Poly::constant)
+        } else {
+            None
+        }
+    }
+}
+
+impl<R: CommutativeRing + Clone + PartialEq> CommutativeRing for Poly<R> {}
+
+impl<R: Field + Clone + PartialEq> IntegralDomain for Poly<R> {
+    fn associates(&self, rhs: Self) -> bool {
+        // Over a field, f ~ g iff they have the same monic form.
+        self.monic() == rhs.monic()
+    }
+}
+
+/// Widens the coefficients to [`i128`].
+fn widen<T: CommutativeRing + Clone + PartialEq>(f: &Poly<T>) -> Poly<i128>
+where
+    i128: TryFrom<T>,
+{
+    Poly::new(
+        f.coefficients()
+            .into_iter()
+            .map(|c| i128::try_from(c).unwrap_or_else(|_| unreachable!("every width fits")))
+            .collect(),
+    )
+}
+
+/// Narrows the coefficients back from [`i128`], panicking if they don't fit.
+fn narrow<T: CommutativeRing + Clone + PartialEq + TryFrom<i128>>(f: Poly<i128>) -> Poly<T> {
+    Poly::new(
+        f.coefficients()
+            .into_iter()
+            .map(|c| T::try_from(c).unwrap_or_else(|_| panic!("the coefficient {c} doesn't fit")))
+            .collect(),
+    )
+}
+
+/// Polynomials over every primitive signed integer type factorise the same way, in [`i128`]
+/// arithmetic. The factors of a polynomial can have larger coefficients than the polynomial itself,
+/// so for the narrower types, this panics if a factor doesn't fit.
+macro_rules! integer_polynomial {
+    ($($t:ty),*) => {$(
+        impl IntegralDomain for Poly<$t> {
+            fn associates(&self, rhs: Self) -> bool {
+                // The only units are ±1.
+                *self == rhs || *self == -rhs
+            }
+        }
+
+        impl UniqueFactorisationDomain for Poly<$t> {
+            /// Factorises into ±1, prime constants and primitive irreducible polynomials with
+            /// positive leading coefficients. See [`crate::ringlike::factorisation`].
+            fn factor(&self) -> Factorisation<Self> {
+                let (unit, factors) =
+                    factorisation::factor_integer_polynomial(&widen(self)).into_parts();
+                Factorisation::new(
+                    narrow(unit),
+                    factors.into_iter().map(|(f, m)| (narrow(f), m)).collect(),
+                )
+            }
+
+            /// Uses square-free decomposition rather than factorising. The result is primitive
+            /// with a positive leading coefficient, times the square-free part of the content.
+            fn squarefree_part(&self) -> Self {
+                narrow(factorisation::squarefree_part_integer_polynomial(&widen(self)))
+            }
+        }
+    )*};
+}
+
+integer_polynomial!(i8, i16, i32, i64, i128, isize);
+
+/// *K*\[*x*\] is a unique factorisation domain for every field *K*, but there are fields where no
+/// algorithm can factorise its elements, so we only implement this when *K* is a
+/// [`FactorisableField`], and the same goes for the traits which build on it.
+impl<R: FactorisableField> UniqueFactorisationDomain for Poly<R> {
+    /// Factorises into the leading coefficient and monic irreducible polynomials.
+    fn factor(&self) -> Factorisation<Self> {
+        R::factor_polynomial(self)
+    }
+
+    fn is_irreducible(&self) -> bool {
+        R::is_irreducible_polynomial(self)
+    }
+}
+
+impl<R: FactorisableField> PrincipalIdealDomain for Poly<R> {}
+
+impl<R: FactorisableField> EuclideanDomain for Poly<R> {
+    /// The degree. The zero polynomial also has valuation 0.
+    fn valuation(&self) -> usize {
+        self.degree().unwrap_or(0)
+    }
+
+    fn div_rem(&self, rhs: Self) -> (Self, Self) {
+        Poly::div_rem(self, &rhs)
+    }
+
+    /// Makes the gcd monic, or zero if both are zero.
+    fn extended_gcd(&self, b: Self) -> (Self, Self, Self) {
+        Poly::extended_gcd(self, &b)
+    }
+
+    /// Makes the gcd monic, or zero if both are zero.
+    fn euclid(&self, b: Self) -> Self {
+        let (mut r0, mut r1) = (self.clone(), b);
+        while !r1.is_zero() {
+            let remainder = Poly::div_rem(&r0, &r1).1;
+            (r0, r1) = (r1, remainder);
+        }
+        r0.monic()
+    }
+}
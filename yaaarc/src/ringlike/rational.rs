@@ -0,0 +1,455 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! The field of rational numbers, ℚ.
+//!
+//! Rationals are stored as a pair of [`i128`]s in lowest terms. We don't have arbitrary precision
+//! integers (yet!), so every operation panics if an intermediate value exceeds [`i128::MAX`] or
+//! [`i128::MIN`] rather than silently wrapping around.
+
+use std::{cmp::Ordering, fmt};
+
+use crate::{
+    arithmetic::gcd,
+    grouplike::{
+        AbelianGroup, CommutativeMagma, Group, Magma, Monoid, Quasigroup, Semigroup, UnitalMagma,
+    },
+    operators::{BinaryOperator, Plus, Times},
+    ringlike::{
+        factorisation::Factorisation, polynomial, CommutativeRing, DivisionRing, EuclideanDomain,
+        Field, IntegralDomain, PrincipalIdealDomain, Ring, UniqueFactorisationDomain,
+    },
+};
+
+/// A rational number *p*/*q*.
+///
+/// The fraction is always kept in lowest terms with a positive denominator, so two rationals are
+/// equal exactly when their numerators and denominators are.
+///
+/// # Example
+///
+/// ```rust
+/// use yaaarc::ringlike::{rational::Rational, Field};
+///
+/// let half = Rational::new(1, 2);
+/// let third = Rational::new(-2, -6);
+///
+/// assert_eq!(half + third, Rational::new(5, 6));
+/// assert_eq!(half.div(third), Rational::new(3, 2));
+/// assert_eq!(format!("{}", half - third), "1/6");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+/// Unwraps the result of a checked integer operation.
+fn checked(value: Option<i128>) -> i128 {
+    value.expect("rational arithmetic overflowed i128")
+}
+
+/// The (nonnegative) greatest common divisor of `a` and `b`.
+pub(crate) fn gcd_i128(a: i128, b: i128) -> i128 {
+    i128::try_from(gcd(a.unsigned_abs(), b.unsigned_abs())).expect("gcd overflowed i128")
+}
+
+impl Rational {
+    /// Creates the rational *n*/1.
+    pub const fn from_integer(n: i128) -> Self {
+        Rational {
+            numerator: n,
+            denominator: 1,
+        }
+    }
+
+    /// Creates the rational `numerator`/`denominator`, reducing it to lowest terms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero.
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(
+            denominator != 0,
+            "the denominator of a rational must be nonzero"
+        );
+        let g = gcd_i128(numerator, denominator);
+        let sign = denominator.signum();
+        Rational {
+            numerator: checked((numerator / g).checked_mul(sign)),
+            denominator: checked((denominator / g).checked_mul(sign)),
+        }
+    }
+
+    /// The numerator when written in lowest terms. This carries the sign of the rational.
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    /// The denominator when written in lowest terms. This is always positive.
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    /// Whether this rational is zero.
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    /// Whether this rational is an integer, i.e. whether its denominator is 1.
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    /// The absolute value.
+    pub fn abs(&self) -> Self {
+        Rational {
+            numerator: checked(self.numerator.checked_abs()),
+            denominator: self.denominator,
+        }
+    }
+
+    /// The multiplicative inverse, or [`None`] if `self` is zero.
+    pub fn recip(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(Rational::new(self.denominator, self.numerator))
+        }
+    }
+
+    /// Raises `self` to the (possibly negative) power `exponent`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero and `exponent` is negative.
+    pub fn pow(&self, exponent: i32) -> Self {
+        let base = if exponent < 0 {
+            self.recip()
+                .expect("zero cannot be raised to a negative power")
+        } else {
+            *self
+        };
+        Rational {
+            numerator: checked(base.numerator.checked_pow(exponent.unsigned_abs())),
+            denominator: checked(base.denominator.checked_pow(exponent.unsigned_abs())),
+        }
+    }
+
+    /// Rational reconstruction, which finds the *p*/*q* congruent to `residue` modulo `modulus`
+    /// with |*p*| and *q* both at most √(`modulus`/2), or [`None`] if there isn't one.
+    ///
+    /// There's at most one such fraction, so if we know a rational only modulo some large *m*,
+    /// and its numerator and denominator are small enough, this recovers it. We run the extended
+    /// Euclidean algorithm on `modulus` and `residue` and stop halfway, when the remainder drops
+    /// below the bound, because each row *rᵢ* ≡ *tᵢ* `residue` gives a candidate *rᵢ*/*tᵢ*.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yaaarc::ringlike::rational::Rational;
+    ///
+    /// // 2/3 ≡ 2 × 34 ≡ 68 (mod 101).
+    /// assert_eq!(Rational::reconstruct(68, 101), Some(Rational::new(2, 3)));
+    /// assert_eq!(Rational::reconstruct(-34, 101), Some(Rational::new(-1, 3)));
+    /// // Nothing with a numerator and denominator up to 7 is congruent to 10.
+    /// assert_eq!(Rational::reconstruct(10, 101), None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` isn't positive.
+    pub fn reconstruct(residue: i128, modulus: i128) -> Option<Self> {
+        assert!(modulus > 0, "the modulus must be positive");
+        let bound = (modulus / 2).isqrt();
+        let (mut r0, mut r1) = (modulus, residue.rem_euclid(modulus));
+        let (mut t0, mut t1) = (0_i128, 1_i128);
+        while r1 > bound {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, checked(t0.checked_sub(checked(q.checked_mul(t1)))));
+        }
+        (t1.unsigned_abs() <= bound.unsigned_abs() && gcd_i128(r1, t1) == 1)
+            .then(|| Rational::new(r1, t1))
+    }
+
+    fn checked_add(&self, rhs: &Self) -> Self {
+        let g = gcd_i128(self.denominator, rhs.denominator);
+        let numerator = checked(
+            checked(self.numerator.checked_mul(rhs.denominator / g))
+                .checked_add(checked(rhs.numerator.checked_mul(self.denominator / g))),
+        );
+        let denominator = checked(self.denominator.checked_mul(rhs.denominator / g));
+        Rational::new(numerator, denominator)
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Self {
+        // Cancel before multiplying to keep intermediate values small.
+        let g1 = gcd_i128(self.numerator, rhs.denominator).max(1);
+        let g2 = gcd_i128(rhs.numerator, self.denominator).max(1);
+        Rational::new(
+            checked((self.numerator / g1).checked_mul(rhs.numerator / g2)),
+            checked((self.denominator / g2).checked_mul(rhs.denominator / g1)),
+        )
+    }
+
+    fn checked_neg(&self) -> Self {
+        Rational {
+            numerator: checked(self.numerator.checked_neg()),
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Rational::ZERO
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Rational::from_integer(n)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Rational::from_integer(n.into())
+    }
+}
+
+impl From<i32> for Rational {
+    fn from(n: i32) -> Self {
+        Rational::from_integer(n.into())
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Denominators are positive, so cross-multiplying preserves the order.
+        checked(self.numerator.checked_mul(other.denominator))
+            .cmp(&checked(other.numerator.checked_mul(self.denominator)))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl std::ops::Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(&rhs)
+    }
+}
+
+impl std::ops::Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_add(&rhs.checked_neg())
+    }
+}
+
+impl std::ops::Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(&rhs)
+    }
+}
+
+impl std::ops::Div for Rational {
+    type Output = Rational;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_mul(&rhs.recip().expect("division by zero"))
+    }
+}
+
+impl std::ops::Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg()
+    }
+}
+
+impl std::ops::AddAssign for Rational {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl std::ops::SubAssign for Rational {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl std::ops::MulAssign for Rational {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl std::ops::DivAssign for Rational {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl std::iter::Sum for Rational {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Rational::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl std::iter::Product for Rational {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Rational::ONE, |acc, x| acc * x)
+    }
+}
+
+impl BinaryOperator<Plus> for Rational {
+    fn op(&self, rhs: Self) -> Self {
+        *self + rhs
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self += rhs;
+    }
+}
+
+impl BinaryOperator<Times> for Rational {
+    fn op(&self, rhs: Self) -> Self {
+        *self * rhs
+    }
+    fn op_assign(&mut self, rhs: Self) {
+        *self *= rhs;
+    }
+}
+
+impl Magma<Plus> for Rational {}
+impl Semigroup<Plus> for Rational {}
+impl UnitalMagma<Plus> for Rational {
+    const IDENTITY: Self = Rational::from_integer(0);
+}
+impl Quasigroup<Plus> for Rational {
+    fn inverse(&self) -> Self {
+        -*self
+    }
+}
+impl CommutativeMagma<Plus> for Rational {}
+impl AbelianGroup<Plus> for Rational {}
+
+impl Magma<Times> for Rational {}
+impl Semigroup<Times> for Rational {}
+impl UnitalMagma<Times> for Rational {
+    const IDENTITY: Self = Rational::from_integer(1);
+}
+/// # Panics
+///
+/// [`Quasigroup::inverse`] panics if called on zero.
+impl Quasigroup<Times> for Rational {
+    fn inverse(&self) -> Self {
+        self.recip().expect("zero has no multiplicative inverse")
+    }
+}
+impl CommutativeMagma<Times> for Rational {}
+impl Monoid<Times> for Rational {}
+impl Group<Times> for Rational {}
+
+impl Ring for Rational {
+    const ZERO: Self = Rational::from_integer(0);
+    const ONE: Self = Rational::from_integer(1);
+
+    fn left_inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+
+    fn right_inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+
+    fn inverse_mul(&self) -> Option<Self> {
+        self.recip()
+    }
+}
+
+impl CommutativeRing for Rational {
+    /// Uses Toom-3, or Karatsuba in characteristic 2 or 3.
+    fn mul_polynomials(a: &[Self], b: &[Self]) -> Vec<Self> {
+        polynomial::field_product(a, b)
+    }
+}
+
+impl DivisionRing for Rational {
+    fn div_right(&self, rhs: Self) -> Self {
+        *self / rhs
+    }
+
+    fn div_left(&self, rhs: Self) -> Self {
+        *self / rhs
+    }
+}
+
+impl IntegralDomain for Rational {
+    fn associates(&self, rhs: Self) -> bool {
+        // Every nonzero element is a unit.
+        self.is_zero() == rhs.is_zero()
+    }
+}
+
+impl UniqueFactorisationDomain for Rational {
+    /// Every nonzero element is a unit, so it has no irreducible factors.
+    fn factor(&self) -> Factorisation<Self> {
+        assert!(!self.is_zero(), "zero has no factorisation");
+        Factorisation::new(*self, Vec::new())
+    }
+}
+impl PrincipalIdealDomain for Rational {}
+
+impl EuclideanDomain for Rational {
+    fn valuation(&self) -> usize {
+        0
+    }
+
+    /// Division is exact in a field, so the remainder is always zero.
+    fn div_rem(&self, rhs: Self) -> (Self, Self) {
+        (*self / rhs, Self::ZERO)
+    }
+}
+
+impl Field for Rational {
+    fn div(&self, rhs: Self) -> Self {
+        self.div_right(rhs)
+    }
+}
@@ -0,0 +1,470 @@
+/* Copyright 2024 Charlotte Ausel
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License. */
+
+//! Solving systems of polynomial equations exactly.
+//!
+//! A system *f*₁ = ⋯ = *fₖ* = 0 in *n* variables over a field *K* has finitely many solutions
+//! over the algebraic closure exactly when its [`Ideal`] is zero-dimensional. We describe them
+//! with the *shape lemma*: after taking the radical, so that every solution is counted once, a
+//! generic linear form *t* = *λ*₁*x*₁ + ⋯ + *λₙxₙ* takes different values at different
+//! solutions. Then the lex Gröbner basis of the ideal with *t* - (*λ*₁*x*₁ + ⋯ + *λₙxₙ*) added,
+//! with *t* smallest, has the shape
+//!
+//! *m*(*t*), *xₙ* - *rₙ*(*t*), …, *x*₁ - *r*₁(*t*),
+//!
+//! where *m* is the minimal polynomial of *t*. So the solutions are (*r*₁(*θ*), …, *rₙ*(*θ*)) for
+//! the roots *θ* of *m*. Factorising *m* splits them into [`Solution`]s, one for each set of
+//! conjugate solutions, each with an irreducible minimal polynomial. Solutions with coordinates in
+//! *K* itself, such as all the points of a system over a finite field, are the ones with linear
+//! minimal polynomials.
+//!
+//! Over a small finite field, there may be no such *λᵢ* in *K*, e.g. *x* and *y* take only two
+//! values at the four points of 𝔽₂². Then we look for a linear form whose minimal polynomial
+//! factorises, and for each factor *p*, solve the system with *p*(*t*) = 0 added, which has only
+//! the solutions where *t* is a root of *p*. Repeating this finds every point.
+//!
+//! # Example
+//!
+//! ```rust
+//! use yaaarc::ringlike::{
+//!     multivariate::{GRevLex, MPoly},
+//!     polynomial::Poly,
+//!     rational::Rational,
+//!     solve::solve,
+//!     EuclideanDomain, Ring,
+//! };
+//!
+//! type P = MPoly<Rational, GRevLex>;
+//!
+//! // The circle x² + y² = 4 meets the hyperbola xy = 1 at four points, none of them rational.
+//! let (x, y) = (P::var(0), P::var(1));
+//! let system = [
+//!     &(&(&x * &x) + &(&y * &y)) - &P::constant(4.into()),
+//!     &(&x * &y) - &P::constant(1.into()),
+//! ];
+//! let solutions = solve(2, &system).unwrap();
+//! assert_eq!(solutions.len(), 1);
+//! let solution = &solutions[0];
+//! assert_eq!(solution.degree(), 4);
+//! assert!(solution.point().is_none());
+//! // Each coordinate satisfies the system at every root of the minimal polynomial.
+//! let (r, s) = (&solution.coordinates()[0], &solution.coordinates()[1]);
+//! let m = solution.minimal_polynomial();
+//! let four = Poly::constant(Rational::from_integer(4));
+//! assert!(((&(r * r) + &(s * s)) - four).rem(m.clone()).is_zero());
+//! assert!(((r * s) - Poly::constant(Rational::ONE)).rem(m.clone()).is_zero());
+//! ```
+//!
+//! Over a finite field, the points are the solutions of degree one.
+//!
+//! ```rust
+//! use yaaarc::ringlike::{
+//!     finite_field::PrimeField,
+//!     multivariate::{GRevLex, MPoly},
+//!     solve::solve,
+//! };
+//!
+//! type F = PrimeField<13>;
+//! type P = MPoly<F, GRevLex>;
+//!
+//! // x² + y² = 5 and xy = 2 over 𝔽₁₃, whose solutions are ±(1, 2) and ±(2, 1).
+//! let (x, y) = (P::var(0), P::var(1));
+//! let system = [
+//!     &(&(&x * &x) + &(&y * &y)) - &P::constant(F::new(5)),
+//!     &(&x * &y) - &P::constant(F::new(2)),
+//! ];
+//! let mut points: Vec<Vec<F>> = solve(2, &system)
+//!     .unwrap()
+//!     .iter()
+//!     .filter_map(|s| s.point())
+//!     .collect();
+//! points.sort_by_key(|p| p.iter().map(|c| c.value()).collect::<Vec<u64>>());
+//! assert_eq!(
+//!     points,
+//!     [
+//!         [F::new(1), F::new(2)],
+//!         [F::new(2), F::new(1)],
+//!         [F::new(11), F::new(12)],
+//!         [F::new(12), F::new(11)],
+//!     ]
+//! );
+//! ```
+
+use std::{error::Error, fmt};
+
+use crate::ringlike::{
+    factorisation::FactorisableField,
+    ideal::Ideal,
+    multivariate::{GRevLex, Lex, MPoly, Monomial, MonomialOrder},
+    polynomial::Poly,
+    Ring, UniqueFactorisationDomain,
+};
+
+/// The reasons we can fail to describe the solutions of a system.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// The ideal isn't zero-dimensional, so there are infinitely many solutions.
+    InfinitelyManySolutions,
+    /// No linear form with coefficients in the field separates the solutions, or splits them into
+    /// smaller sets to solve separately. This can only happen over a finite field with fewer
+    /// elements than pairs of solutions.
+    NoSeparatingForm,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::InfinitelyManySolutions => {
+                write!(f, "the system has infinitely many solutions")
+            }
+            SolveError::NoSeparatingForm => {
+                write!(f, "no linear form over the field separates the solutions")
+            }
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// A set of conjugate solutions of a system over *K*, namely (*r*₁(*θ*), …, *rₙ*(*θ*)) for each
+/// root *θ* of an irreducible minimal polynomial *m*. See the [module documentation](self).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solution<R> {
+    linear_form: Vec<R>,
+    minimal_polynomial: Poly<R>,
+    coordinates: Vec<Poly<R>>,
+}
+
+impl<R: FactorisableField> Solution<R> {
+    /// The coefficients *λᵢ* of the linear form *θ* = *λ*₁*x*₁ + ⋯ + *λₙxₙ*.
+    pub fn linear_form(&self) -> &[R] {
+        &self.linear_form
+    }
+
+    /// The monic irreducible minimal polynomial *m* of *θ*.
+    pub fn minimal_polynomial(&self) -> &Poly<R> {
+        &self.minimal_polynomial
+    }
+
+    /// The polynomials *rᵢ*, of smaller degree than *m*, with *xᵢ* = *rᵢ*(*θ*).
+    pub fn coordinates(&self) -> &[Poly<R>] {
+        &self.coordinates
+    }
+
+    /// The number of solutions this stands for, which is the degree of the minimal polynomial.
+    pub fn degree(&self) -> usize {
+        self.minimal_polynomial.degree().unwrap_or(0)
+    }
+
+    /// The solution, if there's only one, so that its coordinates are in *K*.
+    pub fn point(&self) -> Option<Vec<R>> {
+        (self.degree() == 1).then(|| {
+            let root = self.minimal_polynomial.coefficient(0).inverse_add();
+            self.coordinates.iter().map(|r| r.evaluate(&root)).collect()
+        })
+    }
+}
+
+/// `f` as a polynomial in *xᵢ*, if it doesn't involve any other variables.
+fn univariate<R, O>(f: &MPoly<R, O>, i: usize) -> Option<Poly<R>>
+where
+    R: FactorisableField,
+    O: MonomialOrder,
+{
+    let mut coefficients = vec![R::ZERO; f.degree_in(i) as usize + 1];
+    for (m, c) in f.terms() {
+        if m.degree() != m.exponent(i) {
+            return None;
+        }
+        coefficients[m.exponent(i) as usize] = c.clone();
+    }
+    Some(Poly::new(coefficients))
+}
+
+/// The solutions of the system *f* = 0 for each `f` in `system`, in `variables` variables, split
+/// into sets of conjugates. See the [module documentation](self).
+///
+/// # Errors
+///
+/// Returns [`SolveError::InfinitelyManySolutions`] if the system has infinitely many solutions
+/// over the algebraic closure, and [`SolveError::NoSeparatingForm`] if *K* is too small for a
+/// linear form to either separate the solutions or split them up.
+///
+/// # Panics
+///
+/// Panics if a polynomial in `system` involves more than `variables` variables.
+pub fn solve<R, O>(variables: usize, system: &[MPoly<R, O>]) -> Result<Vec<Solution<R>>, SolveError>
+where
+    R: FactorisableField,
+    O: MonomialOrder,
+{
+    let system: Vec<MPoly<R, GRevLex>> = system.iter().map(|f| f.reorder()).collect();
+    let ideal = Ideal::new(variables, &system);
+    if ideal.is_whole_ring() {
+        return Ok(Vec::new());
+    }
+    if !ideal.is_zero_dimensional() {
+        return Err(SolveError::InfinitelyManySolutions);
+    }
+    solve_radical(variables, &ideal.zero_dimensional_radical())
+}
+
+/// The candidates for the coefficients of a linear form separating `count` solutions. First
+/// *λᵢ* = *c*ⁱ for *c* = 0, 1, 2, …, since each pair of solutions agrees on this for at most
+/// `variables` - 1 values of *c*, and then, in case *K* is too small, the other coordinates.
+fn linear_forms<R: FactorisableField>(variables: usize, count: usize) -> Vec<Vec<R>> {
+    let attempts = variables.max(1) * count * count.saturating_sub(1) / 2 + 1;
+    let mut forms = Vec::new();
+    let mut c = R::ZERO;
+    for attempt in 0..attempts {
+        if attempt > 0 && c == R::ZERO {
+            // We've tried every element of a finite field.
+            break;
+        }
+        forms.push(
+            (0..variables)
+                .scan(R::ONE, |power, _| {
+                    let current = power.clone();
+                    *power = power.mul(c.clone());
+                    Some(current)
+                })
+                .collect(),
+        );
+        c = c.add(R::ONE);
+    }
+    forms.extend((1..variables).map(|i| {
+        let mut lambda = vec![R::ZERO; variables];
+        lambda[i] = R::ONE;
+        lambda
+    }));
+    forms
+}
+
+/// The solutions of a zero-dimensional radical ideal, which isn't the whole ring.
+fn solve_radical<R: FactorisableField>(
+    variables: usize,
+    ideal: &Ideal<R, GRevLex>,
+) -> Result<Vec<Solution<R>>, SolveError> {
+    let count = ideal
+        .quotient_dimension()
+        .expect("the ideal is zero-dimensional");
+    let mut others = Vec::new();
+    for lambda in linear_forms::<R>(variables, count) {
+        let t = (0..variables).fold(MPoly::ZERO, |t, i| {
+            &t + &MPoly::term(lambda[i].clone(), Monomial::var(i))
+        });
+        let m = ideal
+            .minimal_polynomial(&t)
+            .expect("the ideal is zero-dimensional");
+        if m.degree() == Some(count) {
+            return Ok(shape(variables, ideal, lambda, &t));
+        }
+        others.push((t, m));
+    }
+    // Nothing separates the solutions, so split them up by the factors of a minimal polynomial.
+    let (t, factors) = others
+        .into_iter()
+        .map(|(t, m)| (t, m.factor()))
+        .find(|(_, factors)| factors.factors().len() > 1)
+        .ok_or(SolveError::NoSeparatingForm)?;
+    let mut solutions = Vec::new();
+    for (p, _) in factors {
+        let p_of_t = p
+            .coefficients()
+            .into_iter()
+            .rev()
+            .fold(MPoly::ZERO, |acc, c| &(&acc * &t) + &MPoly::constant(c));
+        let mut generators = ideal.basis().to_vec();
+        generators.push(p_of_t);
+        solutions.extend(solve_radical(
+            variables,
+            &Ideal::new(variables, &generators),
+        )?);
+    }
+    Ok(solutions)
+}
+
+/// The solutions of a zero-dimensional radical ideal, given a linear form *t* with coefficients
+/// `lambda` which separates them.
+fn shape<R: FactorisableField>(
+    variables: usize,
+    ideal: &Ideal<R, GRevLex>,
+    lambda: Vec<R>,
+    t: &MPoly<R, GRevLex>,
+) -> Vec<Solution<R>> {
+    // Add t as the smallest variable and switch to lex.
+    let mut generators = ideal.basis().to_vec();
+    generators.push(&MPoly::var(variables) - t);
+    let shape = Ideal::new(variables + 1, &generators).to_order::<Lex>();
+    let mut minimal_polynomial = Poly::ZERO;
+    let mut coordinates = vec![Poly::ZERO; variables];
+    for g in shape.basis() {
+        match univariate(g, variables) {
+            Some(m) => minimal_polynomial = m,
+            None => {
+                let lead = g.leading_monomial().expect("nonzero");
+                let i = (0..variables)
+                    .find(|&i| lead.exponent(i) > 0)
+                    .expect("the lead is a variable");
+                let rest = g - &MPoly::var(i);
+                coordinates[i] = univariate(&rest, variables)
+                    .expect("the basis has the shape")
+                    .inverse_add();
+            }
+        }
+    }
+    minimal_polynomial
+        .factor()
+        .into_iter()
+        .map(|(p, _)| Solution {
+            linear_form: lambda.clone(),
+            coordinates: coordinates.iter().map(|r| r.div_rem(&p).1).collect(),
+            minimal_polynomial: p,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ringlike::{
+        finite_field::{FiniteField, PrimeField},
+        rational::Rational,
+    };
+
+    /// The sorted points of *x*^*q* - *x* = 0 in every variable, which is all of 𝔽*_q*ⁿ.
+    fn every_point<F: FiniteField + FactorisableField>(variables: usize) -> Vec<Vec<u128>> {
+        let system: Vec<MPoly<F, GRevLex>> = (0..variables)
+            .map(|i| &MPoly::var(i).pow(F::ORDER as u32) - &MPoly::var(i))
+            .collect();
+        let solutions = solve(variables, &system).unwrap();
+        assert!(solutions.iter().all(|s| s.degree() == 1));
+        let mut points: Vec<Vec<u128>> = solutions
+            .iter()
+            .map(|s| s.point().unwrap().iter().map(F::index).collect())
+            .collect();
+        points.sort();
+        points
+    }
+
+    #[test]
+    fn every_point_of_a_small_field() {
+        assert_eq!(
+            every_point::<PrimeField<2>>(2),
+            [[0, 0], [0, 1], [1, 0], [1, 1]]
+        );
+        let points = every_point::<PrimeField<3>>(2);
+        assert_eq!(points.len(), 9);
+        assert!(points.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(every_point::<PrimeField<2>>(3).len(), 8);
+    }
+
+    #[test]
+    fn conjugate_points_over_a_small_field() {
+        type F = PrimeField<2>;
+        // x² + x + 1 = y² + y + 1 = 0 has four solutions in 𝔽₄², and no linear form over 𝔽₂
+        // takes four different values there. But x + y is 0 at two of them and 1 at the other
+        // two, which splits them into two pairs of conjugates.
+        let w = |i: usize| -> MPoly<F, GRevLex> {
+            &(&MPoly::var(i).pow(2) + &MPoly::var(i)) + &MPoly::ONE
+        };
+        let solutions = solve(2, &[w(0), w(1)]).unwrap();
+        assert_eq!(solutions.len(), 2);
+        assert!(solutions.iter().all(|s| s.degree() == 2));
+    }
+
+    /// A polynomial from its terms, as exponents with integer coefficients.
+    fn poly(terms: &[(&[u32], i128)]) -> MPoly<Rational, GRevLex> {
+        MPoly::new(terms.iter().map(|&(e, c)| (e.to_vec(), c.into())).collect())
+    }
+
+    /// Checks that every polynomial in `system` vanishes at each of the `solutions`, i.e. it's
+    /// divisible by the minimal polynomial once we substitute the coordinates, and returns how
+    /// many solutions there are over the algebraic closure.
+    fn count_solutions(
+        system: &[MPoly<Rational, GRevLex>],
+        solutions: &[Solution<Rational>],
+    ) -> usize {
+        for solution in solutions {
+            let p = solution.minimal_polynomial();
+            for f in system {
+                let value = f.terms().fold(Poly::ZERO, |sum, (m, c)| {
+                    let term = solution
+                        .coordinates()
+                        .iter()
+                        .enumerate()
+                        .flat_map(|(i, r)| std::iter::repeat_n(r, m.exponent(i) as usize))
+                        .fold(Poly::constant(*c), |term, r| (&term * r).div_rem(p).1);
+                    &sum + &term
+                });
+                assert!(value.div_rem(p).1.is_zero());
+            }
+        }
+        solutions.iter().map(Solution::degree).sum()
+    }
+
+    #[test]
+    fn two_dense_quadrics_over_the_rationals() {
+        let system = [
+            poly(&[(&[2], 1), (&[1, 1], 3), (&[0, 1], -2), (&[], -5)]),
+            poly(&[(&[0, 2], 1), (&[1], -4), (&[], 7)]),
+        ];
+        let solutions = solve(2, &system).unwrap();
+        assert_eq!(count_solutions(&system, &solutions), 4);
+    }
+
+    #[test]
+    fn three_dense_quadrics_over_the_rationals() {
+        let system = [
+            poly(&[
+                (&[2], 2),
+                (&[1, 1], 1),
+                (&[1, 0, 1], 1),
+                (&[0, 2], -1),
+                (&[0, 1, 1], -2),
+                (&[0, 0, 2], -2),
+                (&[1], 1),
+                (&[0, 1], 2),
+                (&[0, 0, 1], 1),
+                (&[], 1),
+            ]),
+            poly(&[
+                (&[2], 2),
+                (&[1, 1], 1),
+                (&[1, 0, 1], 2),
+                (&[0, 2], -2),
+                (&[0, 1, 1], -2),
+                (&[0, 0, 2], 1),
+                (&[1], 1),
+                (&[0, 1], 1),
+                (&[0, 0, 1], -1),
+                (&[], -2),
+            ]),
+            poly(&[
+                (&[2], -2),
+                (&[1, 1], -1),
+                (&[1, 0, 1], -1),
+                (&[0, 2], 1),
+                (&[0, 1, 1], -1),
+                (&[0, 0, 2], -1),
+                (&[1], 1),
+                (&[0, 1], -1),
+                (&[0, 0, 1], 1),
+                (&[], 1),
+            ]),
+        ];
+        let solutions = solve(3, &system).unwrap();
+        assert_eq!(count_solutions(&system, &solutions), 8);
+    }
+}